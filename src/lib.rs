@@ -117,13 +117,34 @@ pub mod futures {
     //! 在浏览异步生态系统时，请记住这一点，并注意一些库可以在任何执行器上工作，或者一些库要求您根据特性标签选择要使用的执行器。
     //! 例如，tokio提供了一个`tokio::main`宏，它会为您创建一个tokio执行器，然后在其中运行您的程序。
     //! 如果您使用`async-std`，则需要使用`async-std::main`宏，它会为您创建一个`async-std`执行器，然后在其中运行您的程序。
+    use crate::runtime;
     use std::{
-        future::Future,
-        pin::Pin,
+        any::Any,
+        error::Error,
+        fmt,
+        future::{poll_fn, ready, Future, Ready},
+        panic::{catch_unwind, AssertUnwindSafe},
+        pin::{pin, Pin},
         task::{Context, Poll},
-        time::SystemTime,
+        time::{Duration, Instant},
     };
 
+    /// What `.await` actually works on. Awaiting `x` calls
+    /// `x.into_future()` first and awaits whatever comes back, so anything
+    /// that can turn itself into a future can be awaited, not only futures.
+    /// Every `Future` turns into itself, which is why the oneshot `Receiver`
+    /// or a `JoinHandle` can be awaited as they are or with an explicit
+    /// `.into_future()`. Things that are a source of futures rather than one,
+    /// like the mpsc `Receiver`, implement it for a reference instead, so
+    /// `(&mut rx).await` is the same as `rx.recv().await`. The trait lives in
+    /// the standard library but isn't in the 2021 prelude, so it's here to
+    /// `use` when you want to call `into_future` yourself.
+    /// `.await`真正作用的东西。await `x`会先调用`x.into_future()`，然后await返回的东西，所以任何能把自己变成future的东西都可以被await，
+    /// 而不仅仅是future。每一个`Future`都会变成它自己，这就是为什么oneshot `Receiver`或者`JoinHandle`既可以原样被await，
+    /// 也可以显式地`.into_future()`。那些是future的来源而不是future本身的东西，比如mpsc `Receiver`，会为一个引用实现它，
+    /// 所以`(&mut rx).await`和`rx.recv().await`是一样的。这个trait在标准库里，但不在2021的prelude中，所以当你想自己调用`into_future`时，可以从这里`use`它。
+    pub use std::future::IntoFuture;
+
     /// A future that will allow us to sleep and block further execution of the
     /// future it's used in without blocking the thread itself. It will be
     /// polled and if the timer is not up, then it will yield execution to the
@@ -131,15 +152,23 @@ pub mod futures {
     /// 这是一个用以阻塞当前线程正在执行的future的future，不会阻塞线程，而是阻塞当前正在执行的future，直到时间到了，
     /// 才会继续执行当前future，所以不会阻塞其他future的执行，这是一个异步的sleep，而不是同步的sleep，
     /// 同步的sleep会阻塞线程，也就是说，同步的sleep会阻塞其他future的执行。
+    ///
+    /// The first time it's polled and not done yet, it hands its `Waker` to the
+    /// runtime's timer so that the task only gets polled again once the
+    /// deadline has passed, instead of over and over until it has. Every poll
+    /// after that swaps in the new `Waker` if it's a different one.
+    /// 第一次被poll且还没有完成时，它会把自己的`Waker`交给运行时的定时器，
+    /// 这样任务只会在截止时间过了之后才会被再次poll，而不是一直被poll直到时间到了为止。之后的每一次poll，如果`Waker`换了，就会把新的换进去。
     pub struct Sleep {
-        /// What time the future was created at, not when it was started to be
-        /// polled.
-        /// 当前future创建的时间，不是开始poll的时间
-        now: SystemTime,
-        /// How long in the future in ms we must wait till we return
-        /// that the future has finished polling.
-        /// future需要等待的时间，单位是ms，也就是说，如果这个值是1000，那么就是1s，如果是2000，那么就是2s，以此类推
-        ms: u128,
+        /// The moment we're allowed to finish: the time the future was created
+        /// at, not when it was started to be polled, plus how long in ms we
+        /// must wait till we return that the future has finished polling.
+        /// 允许完成的时刻：当前future创建的时间（不是开始poll的时间）加上需要等待的时间（单位是ms）。
+        deadline: Instant,
+        /// The timer we registered with the runtime, if we have. Dropping it
+        /// removes it from the timer again.
+        /// 我们在运行时中注册的定时器，如果已经注册了的话。丢弃它会把它从定时器中再次移除。
+        timer: Option<runtime::TimerEntry>,
     }
 
     impl Sleep {
@@ -150,10 +179,39 @@ pub mod futures {
         /// 返回一个future，这个future就是Sleep。
         pub fn new(ms: u128) -> Self {
             Self {
-                now: SystemTime::now(),
-                ms,
+                deadline: deadline_in(ms),
+                timer: None,
             }
         }
+
+        /// Starts the sleep over so that it finishes `ms` milliseconds from
+        /// now instead, whether or not it had already finished. The timer we
+        /// had registered is for the old deadline, so we let go of it and
+        /// register a new one the next time we're polled.
+        /// 让这次睡眠重新开始，改为从现在起`ms`毫秒后结束，无论它之前是否已经结束了。
+        /// 我们注册的定时器是为旧的截止时间准备的，所以我们放掉它，在下一次被poll时注册一个新的。
+        pub fn reset(&mut self, ms: u128) {
+            self.deadline = deadline_in(ms);
+            self.timer = None;
+        }
+    }
+
+    /// How long we wait in place of one too long for an `Instant` to hold
+    /// the end of. It's thirty years, which is as good as forever for
+    /// anything waiting on it.
+    /// 当一次等待长到`Instant`装不下它的结束时刻时，我们改为等待的时长。它是三十年，对任何在等它的东西来说都和永远差不多。
+    const NEVER: Duration = Duration::from_secs(30 * 365 * 24 * 60 * 60);
+
+    /// The moment `ms` milliseconds from now. A `Duration` only holds a `u64`
+    /// worth of milliseconds and an `Instant` can only go so far, so a wait
+    /// longer than either can hold ends `NEVER` from now instead of wrapping
+    /// around to a short one or overflowing.
+    /// 从现在起`ms`毫秒之后的那个时刻。一个`Duration`只能装下`u64`那么多的毫秒，而一个`Instant`也只能走那么远，
+    /// 所以一次比它们能装下的更长的等待会在从现在起`NEVER`之后结束，而不是绕回成一次很短的等待或者溢出。
+    pub(crate) fn deadline_in(ms: u128) -> Instant {
+        let now = Instant::now();
+        let wait = Duration::from_millis(u64::try_from(ms).unwrap_or(u64::MAX));
+        now.checked_add(wait).unwrap_or(now + NEVER)
     }
 
     impl Future for Sleep {
@@ -178,21 +236,314 @@ pub mod futures {
         /// 这个方法的返回值是Poll<Self::Output>，也就是Poll<()>。
         /// Poll是一个枚举，有两个值，Pending和Ready，Pending表示future还没有准备好，需要再次调用poll方法，
         /// Ready表示future已经准备好了，可以继续执行。
-        fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
             // If enough time has passed, then when we're polled we say that
             // we're ready and the future has slept enough. If not, we just say
             // that we're pending and need to be re-polled, because not enough
-            // time has passed.
+            // time has passed. Saying we're pending isn't enough on its own
+            // though, someone has to wake us up later! So the first time we
+            // find ourselves pending we give the runtime's timer our waker and
+            // our deadline and it calls `wake` for us once the time comes.
             // 如果时间已经过去了足够的时间，那么我们就返回Ready，表示future已经准备好了，可以继续执行；
             // 否则我们就返回Pending，表示future还没有准备好，需要再次调用poll方法。
-            if self.now.elapsed().unwrap().as_millis() >= self.ms {
+            // 不过只返回Pending是不够的，之后还得有人来唤醒我们！所以第一次发现自己还是Pending时，
+            // 我们把waker和截止时间交给运行时的定时器，时间一到它就会替我们调用`wake`。
+            if Instant::now() >= self.deadline {
                 Poll::Ready(())
             } else {
+                // We might have been moved to a different task, or be getting
+                // polled by something like `select!` with a different waker
+                // than last time, so the timer has to wake whoever polled last.
+                // 我们可能被移动到了另一个任务中，或者正在被像`select!`这样的东西用一个和上一次不同的waker poll，
+                // 所以定时器必须唤醒最后一个poll我们的人。
+                match &self.timer {
+                    Some(timer) => timer.update(cx.waker()),
+                    None => {
+                        self.timer =
+                            Some(runtime::register_timer(self.deadline, cx.waker().clone()))
+                    }
+                }
                 Poll::Pending
             }
         }
     }
 
+    /// The error you get when something took longer than it was allowed to.
+    /// 当某件事花费的时间超过了允许的时间时你得到的错误。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Elapsed;
+
+    impl fmt::Display for Elapsed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "deadline has elapsed")
+        }
+    }
+
+    impl Error for Elapsed {}
+
+    /// Extra things you can do with any `Future`.
+    /// 你可以对任何`Future`做的额外的事情。
+    pub trait FutureExt: Future {
+        /// Gives up on the future if it's still not done after being polled
+        /// `n` times. A future that keeps waking itself up without getting
+        /// anywhere, like `Sleep` used to before it had a timer, just eats up
+        /// a worker without anything seeming wrong, so this is a way to catch
+        /// that while debugging.
+        /// 如果future在被poll了`n`次之后还没有完成，就放弃它。一个不停地唤醒自己却毫无进展的future，
+        /// 就像`Sleep`在有定时器之前那样，只会白白占用一个工作线程而看起来一切正常，所以这是在调试时抓住这种情况的一种方式。
+        fn poll_limit(self, n: usize) -> PollLimit<Self>
+        where
+            Self: Sized,
+        {
+            PollLimit {
+                future: Box::pin(self),
+                polls: 0,
+                limit: n,
+            }
+        }
+
+        /// Waits on the future for up to `ms` milliseconds, and if it isn't
+        /// done by then starts on `fallback` as well. This is how you hedge a
+        /// request: if the first server is being slow, ask a second one rather
+        /// than sit in the long tail of the first. By default the future keeps
+        /// going once the fallback starts and whichever one finishes first
+        /// wins, since the first one might still be almost done. If you'd
+        /// rather give up on it, say because both doing the work would be a
+        /// waste, call `cancel_on_fallback` and it gets dropped as soon as
+        /// the fallback starts.
+        /// 等待这个future最多`ms`毫秒，如果那时它还没有完成，就同时开始`fallback`。这就是对冲一个请求的方式：
+        /// 如果第一个服务器很慢，就去问第二个，而不是待在第一个的长尾里。默认情况下，一旦备用的开始了，这个future会继续运行，
+        /// 哪个先完成哪个就赢，因为第一个可能已经快完成了。如果你宁愿放弃它，比如因为两个都做这件事会是一种浪费，
+        /// 那就调用`cancel_on_fallback`，它会在备用的开始时立刻被丢弃。
+        fn or_else_after<G>(self, ms: u128, fallback: G) -> OrElseAfter<Self, G>
+        where
+            Self: Sized,
+            G: Future<Output = Self::Output>,
+        {
+            OrElseAfter {
+                primary: Some(Box::pin(self)),
+                fallback: Box::pin(fallback),
+                sleep: Sleep::new(ms),
+                started: false,
+                cancel: false,
+            }
+        }
+
+        /// Catches a panic in any poll of the future and hands it back as an
+        /// `Err` holding the panic's payload, instead of letting it unwind
+        /// through whatever is polling us. Since a worker is what polls a
+        /// task, that's the difference between one task failing and a whole
+        /// worker going down with it.
+        /// 捕获future的任何一次poll中的panic，并把它作为一个装着panic的载荷的`Err`交还，而不是让它展开穿过正在poll我们的东西。
+        /// 因为poll任务的是工作线程，这就是一个任务失败和整个工作线程跟着它一起倒下之间的区别。
+        fn catch_unwind(self) -> CatchUnwind<Self>
+        where
+            Self: Sized,
+        {
+            CatchUnwind {
+                future: Box::pin(self),
+            }
+        }
+
+        /// Calls `on_tick` every `ms` milliseconds for as long as the future
+        /// is still going, which is all a heartbeat or a progress bar around
+        /// a long operation needs. The ticks come from a `Sleep` that starts
+        /// over each time it goes off, and we always let the future have its
+        /// turn first, so once it's done there are no more ticks.
+        /// 只要future还在进行，就每`ms`毫秒调用一次`on_tick`，这就是围绕一个长时间操作的心跳或者进度条所需要的全部。
+        /// 这些tick来自一个每次响起之后都会重新开始的`Sleep`，而且我们总是先让future轮到，所以一旦它完成了就不会再有tick了。
+        fn with_progress<T>(self, ms: u128, on_tick: T) -> WithProgress<Self, T>
+        where
+            Self: Sized,
+            T: Fn(),
+        {
+            WithProgress {
+                future: Box::pin(self),
+                ms,
+                ticker: Sleep::new(ms),
+                on_tick,
+            }
+        }
+    }
+
+    impl<F: Future> FutureExt for F {}
+
+    /// The `Future` returned by `FutureExt::with_progress`.
+    /// `FutureExt::with_progress`返回的`Future`。
+    pub struct WithProgress<F, T> {
+        future: Pin<Box<F>>,
+        ms: u128,
+        ticker: Sleep,
+        on_tick: T,
+    }
+
+    impl<F: Future, T: Fn() + Unpin> Future for WithProgress<F, T> {
+        type Output = F::Output;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+            if let Poll::Ready(output) = self.future.as_mut().poll(cx) {
+                return Poll::Ready(output);
+            }
+            // Polling the ticker again after a reset is what gets it back on
+            // the timer.
+            // 在重置之后再次poll这个ticker，就是让它重新回到定时器上的方式。
+            while Pin::new(&mut self.ticker).poll(cx).is_ready() {
+                (self.on_tick)();
+                let ms = self.ms;
+                self.ticker.reset(ms);
+            }
+            Poll::Pending
+        }
+    }
+
+    /// The `Future` returned by `FutureExt::catch_unwind`.
+    /// `FutureExt::catch_unwind`返回的`Future`。
+    pub struct CatchUnwind<F> {
+        future: Pin<Box<F>>,
+    }
+
+    impl<F: Future> Future for CatchUnwind<F> {
+        type Output = Result<F::Output, Box<dyn Any + Send>>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            // A future that panicked might have been left half way through
+            // changing something, but nobody gets to look at it afterwards
+            // short of polling it again, which we don't.
+            // 一个panic了的future可能停在了修改某个东西的半路上，但是除非再次poll它，没有人能在之后看到它，而我们不会再poll它。
+            match catch_unwind(AssertUnwindSafe(|| self.future.as_mut().poll(cx))) {
+                Ok(poll) => poll.map(Ok),
+                Err(payload) => Poll::Ready(Err(payload)),
+            }
+        }
+    }
+
+    /// The `Future` returned by `FutureExt::or_else_after`.
+    /// `FutureExt::or_else_after`返回的`Future`。
+    pub struct OrElseAfter<F, G> {
+        /// `None` once we've given up on it.
+        /// 一旦我们放弃了它就是`None`。
+        primary: Option<Pin<Box<F>>>,
+        fallback: Pin<Box<G>>,
+        sleep: Sleep,
+        /// Whether we've started on the fallback yet.
+        /// 我们是否已经开始了备用的future。
+        started: bool,
+        /// Whether to drop the primary future once the fallback starts.
+        /// 备用的开始时是否丢弃主future。
+        cancel: bool,
+    }
+
+    impl<F, G> OrElseAfter<F, G> {
+        /// Drop the future it was made from as soon as the fallback starts,
+        /// rather than letting the two race.
+        /// 备用的一开始就丢弃创建它的那个future，而不是让两者赛跑。
+        pub fn cancel_on_fallback(mut self) -> Self {
+            self.cancel = true;
+            self
+        }
+    }
+
+    impl<F, G> Future for OrElseAfter<F, G>
+    where
+        F: Future,
+        G: Future<Output = F::Output>,
+    {
+        type Output = F::Output;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(primary) = self.primary.as_mut() {
+                if let Poll::Ready(output) = primary.as_mut().poll(cx) {
+                    return Poll::Ready(output);
+                }
+            }
+            if !self.started {
+                if Pin::new(&mut self.sleep).poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                self.started = true;
+                if self.cancel {
+                    self.primary = None;
+                }
+            }
+            self.fallback.as_mut().poll(cx)
+        }
+    }
+
+    /// The `Future` returned by `FutureExt::poll_limit`.
+    /// `FutureExt::poll_limit`返回的`Future`。
+    pub struct PollLimit<F> {
+        future: Pin<Box<F>>,
+        polls: usize,
+        limit: usize,
+    }
+
+    impl<F: Future> Future for PollLimit<F> {
+        type Output = Result<F::Output, PollLimitExceeded>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.polls == self.limit {
+                return Poll::Ready(Err(PollLimitExceeded(self.limit)));
+            }
+            self.polls += 1;
+            match self.future.as_mut().poll(cx) {
+                Poll::Ready(output) => Poll::Ready(Ok(output)),
+                // We've used up every poll it gets, so there's no point asking
+                // it again. We wake ourselves up to hand back the error.
+                // 我们已经用完了它能得到的每一次poll，所以再问它也没有意义了。我们唤醒自己来交出这个错误。
+                Poll::Pending if self.polls == self.limit => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    /// The error you get when a future is still pending after being polled
+    /// as many times as `poll_limit` allowed.
+    /// 当一个future在被poll了`poll_limit`所允许的次数之后仍然未完成时你得到的错误。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PollLimitExceeded(pub usize);
+
+    impl fmt::Display for PollLimitExceeded {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "future was still pending after {} polls", self.0)
+        }
+    }
+
+    impl Error for PollLimitExceeded {}
+
+    /// Hands control back to the runtime `n` times before finishing, waking
+    /// itself up each time so it gets put straight back on the queue. That
+    /// lets everything else that's waiting get a turn in between, and it's a
+    /// handy stand in for work that takes a few steps: a task awaiting it gets
+    /// polled `n + 1` times.
+    /// 在完成之前把控制权交还给运行时`n`次，每次都唤醒自己，这样它就会被直接放回队列上。
+    /// 这让其他所有在等待的东西都能在中间轮到一次，而且它很适合用来代替需要几步才能完成的工作：一个await它的任务会被poll`n + 1`次。
+    pub fn yield_n(n: usize) -> YieldN {
+        YieldN { left: n }
+    }
+
+    /// Hands control back to the runtime once, so others get a turn.
+    /// 把控制权交还给运行时一次，让别人也能轮到。
+    pub fn yield_now() -> YieldN {
+        yield_n(1)
+    }
+
+    /// The `Future` returned by `yield_n` and `yield_now`.
+    /// `yield_n`和`yield_now`返回的`Future`。
+    pub struct YieldN {
+        left: usize,
+    }
+
+    impl Future for YieldN {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.left == 0 {
+                return Poll::Ready(());
+            }
+            self.left -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
     // In practice, what we do when we sleep is something like this:
     // ```
     // async fn example() {
@@ -213,805 +564,10976 @@ pub mod futures {
     //        }
     //     }
     // }
-}
 
-#[test]
-/// To understand what we'll build, we need to see and understand what we will
-/// run and the output we expect to see. Note that if you wish to run this test,
-/// you should use the command `cargo test -- --nocapture` so that you can see
-/// the output of `println` being used, otherwise it'll look like nothing is
-/// happening at all for a while.
-/// 为了理解我们要构建的内容，我们需要看一下我们要运行的内容，以及我们期望看到的输出。
-/// 注意，如果你想要运行这个测试，你应该使用`cargo test -- --nocapture`命令，这样你就可以看到`println`的输出，
-/// 否则，你会看到一段时间什么都没有发生。
-fn library_test() {
-    // We're going to import our Sleep future to make sure that it works,
-    // because it's not a complicated future and it's easy to see the
-    // asynchronous nature of the code.
-    // 我们要导入Sleep future，来确保它是可以正常工作的，因为它不是一个复杂的future，所以很容易看出代码的异步本质。
-    use crate::{futures::Sleep, runtime};
-    // We want some random numbers so that the sleep futures finish at different
-    // times. If we didn't, then the code would look synchronous in nature even
-    // if it isn't. This is because we schedule and poll tasks in what is
-    // essentially a loop unless we use block_on.
-    // 我们需要一些随机数，这样sleep future就会在不同的时间结束，如果我们不这样做，那么代码看起来就像是同步的，
-    // 即使本质上它不是。这是因为我们在调度和poll任务时，实际上是在一个循环中，除非我们使用block_on。
-    use rand::Rng;
-    // We need to know the time to show when a future completes. Time is cursed
-    // and it's best we dabble not too much in it.
-    // 我们需要知道时间，来显示future何时完成。时间是被诅咒的，所以我们最好不要太多地涉及它。
+    /// A set of futures that all run at the same time inside of a single task,
+    /// handing back their outputs in whatever order they finish in. This is a
+    /// very plain version of it: whenever we're polled, we poll every future
+    /// in the set with the same `Waker`, so one of them waking us up means all
+    /// of them get polled again. That's fine for a handful of futures, but
+    /// with thousands of them you'd want to keep track of which one actually
+    /// woke us up, which is what the one in the `futures` crate does.
+    /// 一组在单个任务中同时运行的future，按照它们完成的顺序交还它们的输出。这是一个非常朴素的版本：
+    /// 每当我们被poll时，我们就用同一个`Waker`poll集合中的每个future，所以它们中的一个唤醒我们就意味着它们全部都会被再次poll。
+    /// 对于少量的future这没问题，但是如果有成千上万个，你就会想记录到底是哪一个唤醒了我们，`futures` crate中的那个版本就是这么做的。
+    pub struct FuturesUnordered<F> {
+        futures: Vec<Pin<Box<F>>>,
+    }
 
-    println!(
-        "1. Current thread name {} {} {}",
-        thread::current().name().unwrap(),
-        current_thread_id(),
-        current_time()
-    );
-    // This function causes the runtime to block on this future. It does so by
-    // just taking this future and polling it till completion in a loop and
-    // ignoring other tasks on the queue. Sometimes you need to block on async
-    // functions and treat them as sync. A good example is running a webserver.
-    // You'd want it to always be running, not just sometimes, and so blocking
-    // it makes sense. In a single threaded executor this would block all
-    // execution. In our case our executor is single-threaded. Technically it
-    // runs on a separate thread from our program and so blocks running other
-    // tasks, but the main function will keep running. This is why we call
-    // `wait` to make sure we wait till all futures finish executing before
-    // exiting.
-    // block_on方法会创建一个新的Runtime，然后调用这个方法。
-    // Runtime通过在一个循环中获取当前future而且不停的执行poll方法，直到future返回Ready，并且忽略其他的任务。
-    // 有时候我们需要阻塞异步函数，把它当做同步函数来使用。
-    // 一个很好的例子就是运行一个web服务器，你希望它一直运行，而不是偶尔运行，所以阻塞它是有意义的。
-    // 在一个单线程的executor里，这会阻塞所有的执行。在我们的例子里，executor是单线程的。
-    // 从技术上讲，它在一个单独的线程上运行，所以它会阻塞运行其他的任务，但是main函数会一直运行。
-    // 这就是为什么我们调用wait方法，来确保我们等待所有的future执行完毕，然后再退出。
-    runtime::block_on(async {
-        const SECOND: u128 = 1000; //ms
-        println!(
-            "2. Begin Asynchronous Execution,{}, {}",
-            current_thread_id(),
-            current_time()
-        );
-        // Create a random number generator so we can generate random numbers
-        // 创建一个随机数生成器，这样我们就可以生成随机数
-        let mut rng = rand::thread_rng();
+    impl<F: Future> FuturesUnordered<F> {
+        /// Creates an empty set.
+        /// 创建一个空的集合。
+        pub fn new() -> Self {
+            Self {
+                futures: Vec::new(),
+            }
+        }
 
-        // Spawn 5 different futures on our executor
-        // 生成5个不同的future，然后在executor上执行
-        for i in 0..5 {
-            // Generate the two numbers between 1 and 9. We'll spawn two futures
-            // that will sleep for as many seconds as the random number creates
-            // 生成两个1到9之间的随机数。我们会生成两个future，这两个future会睡眠多少秒，取决于随机数的大小
-            let random = rng.gen_range(1..5);
-            let random2 = rng.gen_range(1..5);
-
-            // We now spawn a future onto the runtime from within our future
-            // 我们现在在future里面，从runtime上生成一个future
-            runtime::spawn(async move {
-                println!(
-                    "Spawned Fn #{:02}: Start {} {}",
-                    i,
-                    current_thread_id(),
-                    current_time()
-                );
-                // This future will sleep for a certain amount of time before
-                // continuing execution
-                // 这个future会睡眠一段时间，然后继续执行
-                Sleep::new(SECOND * random).await;
-                // After the future waits for a while, it then spawns another
-                // future before printing that it finished. This spawned future
-                // then sleeps for a while and then prints out when it's done.
-                // Since we're spawning futures inside futures, the order of
-                // execution can change.
-                // 在future等待一段时间后，它会生成另一个future，然后打印它已经完成。
-                // 由于我们在future里面生成future，所以执行的顺序会改变。
-                runtime::spawn(async move {
-                    Sleep::new(SECOND * random2).await;
-                    println!(
-                        "Spawned Fn #{:02}: Inner {} {}",
-                        i,
-                        current_thread_id(),
-                        current_time()
-                    );
-                });
-                println!(
-                    "Spawned Fn #{:02}: Ended {} {}",
-                    i,
-                    current_thread_id(),
-                    current_time()
-                );
-            });
+        /// Adds a future to the set.
+        /// 向集合中添加一个future。
+        pub fn push(&mut self, future: F) {
+            self.futures.push(Box::pin(future));
         }
-        // To demonstrate that block_on works we block inside this future before
-        // we even begin polling the other futures.
-        // 为了演示block_on的工作原理，我们在future里面阻塞，然后再开始poll其他的future。
-        runtime::block_on(async {
-            // This sleeps longer than any of the spawned functions, but we poll
-            // this to completion first even if we await here.
-            // 这个睡眠的时间比生成的future都长，但是我们会先poll这个future，即使我们在这里await。
-            Sleep::new(3000).await;
-            println!(
-                "3. Blocking Function Polled To Completion {} {}",
-                current_thread_id(),
-                current_time()
-            );
-        });
-    });
 
-    // We now wait on the runtime to complete each of the tasks that were
-    // spawned before we exit the program
-    // 现在我们等待runtime完成所有的任务，然后再退出程序
-    runtime::wait();
-    println!(
-        "4. End of Asynchronous Execution {} {}",
-        current_thread_id(),
-        current_time()
-    );
+        /// How many futures haven't finished yet.
+        /// 还有多少future没有完成。
+        pub fn len(&self) -> usize {
+            self.futures.len()
+        }
 
-    // When all is said and done when we run this test we should get output that
-    // looks somewhat like this (though in different order):
-    //
-    // Begin Asynchronous Execution
-    // Blocking Function Polled To Completion
-    // Spawned Fn #00: Start 1634664688
-    // Spawned Fn #01: Start 1634664688
-    // Spawned Fn #02: Start 1634664688
-    // Spawned Fn #03: Start 1634664688
-    // Spawned Fn #04: Start 1634664688
-    // Spawned Fn #01: Ended 1634664690
-    // Spawned Fn #01: Inner 1634664691
-    // Spawned Fn #04: Ended 1634664694
-    // Spawned Fn #04: Inner 1634664695
-    // Spawned Fn #00: Ended 1634664697
-    // Spawned Fn #02: Ended 1634664697
-    // Spawned Fn #03: Ended 1634664697
-    // Spawned Fn #00: Inner 1634664698
-    // Spawned Fn #03: Inner 1634664698
-    // Spawned Fn #02: Inner 1634664702
-    // End of Asynchronous Execution
-}
+        /// Whether every future has finished.
+        /// 是否所有的future都已经完成了。
+        pub fn is_empty(&self) -> bool {
+            self.futures.is_empty()
+        }
 
-// 获取当前线程唯一id
-pub fn current_thread_id() -> String {
-    let thread = thread::current();
-    let id = thread.id();
-    format!("{:?}", id)
-}
+        /// Polls every future in the set and hands back the output of the first
+        /// one that's done, or `None` once there aren't any left.
+        /// poll集合中的每个future，并交还第一个完成的future的输出，或者在没有剩下任何future时返回`None`。
+        pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+            if self.futures.is_empty() {
+                return Poll::Ready(None);
+            }
+            for i in 0..self.futures.len() {
+                if let Poll::Ready(output) = self.futures[i].as_mut().poll(cx) {
+                    drop(self.futures.swap_remove(i));
+                    return Poll::Ready(Some(output));
+                }
+            }
+            Poll::Pending
+        }
 
-// 获取当前时间 yyyy-MM-dd HH:MM:ss
-fn current_time() -> String {
-    let now = Local::now();
-    now.format("%Y-%m-%d %H:%M:%S").to_string()
-}
+        /// Waits for the next future to finish.
+        /// 等待下一个future完成。
+        pub async fn next(&mut self) -> Option<F::Output> {
+            poll_fn(|cx| self.poll_next(cx)).await
+        }
 
-pub mod lazy {
-    use std::{
-        // We don't want to use `static mut` since that's UB and so instead we need
-        // a way to set our statics for our code at runtime. Since we want this to
-        // work across threads, we can't use `Cell` or `RefCell` here, and since it's
-        // a static we can't use a `Mutex` as its `new` function is not const. That
-        // means we need to use the actual type that all of these types use to hold
-        // the data: [`UnsafeCell`]! We'll see below where this is used and how, but
-        // just know that this will let us set some global values at runtime!
-        // 我们不想使用`static mut`，因为这是UB，所以我们需要一种方法来在运行时设置我们的代码的静态变量。
-        // 由于我们希望它能跨线程工作，所以我们不能在这里使用`Cell`或`RefCell`，而且由于它是一个静态变量，
-        // 我们不能使用`Mutex`，因为它的`new`函数不是const。这意味着我们需要使用这些类型用来保存数据的实际类型：[`UnsafeCell`]！
-        // 我们将在下面看到它的使用方式，但是要知道，这将让我们在运行时设置一些全局值！
-        cell::UnsafeCell,
-        mem::{
-            // If you want to import the module to use while also specifying other
-            // imports you can use self to do that. In this case it will let us call
-            // `mem::swap` while also letting us just use `MaybeUninit` without any
-            // extra paths prepended to it. I tend to do this for functions that are
-            // exported at the module level and not encapsulated in a type so that
-            // it's more clear where it comes from, but that's a personal
-            // preference! You could just as easily import `swap` here instead!
-            // 如果你想导入模块来使用，同时还指定其他导入，你可以使用self来做到这一点。
-            // 在这种情况下，它将让我们调用`mem::swap`，同时让我们只使用`MaybeUninit`而不需要任何额外的路径前缀。
-            // 我倾向于为模块级别导出的函数而不是封装在类型中的函数执行此操作，以便更清楚地了解它来自哪里，但这是个人偏好！
-            // 你也可以在这里导入`swap`！
-            self,
-            // `MaybeUninit` is the only way to represent a value that's possibly
-            // uninitialized without causing instant UB with `std::mem::uninitialized`
-            // or `std::mem::zeroed`. There's more info in the docs here:
-            // https://doc.rust-lang.org/stable/std/mem/union.MaybeUninit.html#initialization-invariant
-            //
-            // We need this so that we can have an UnsafeCell with nothing inside it
-            // until we initialize it once and only once without causing UB and
-            // having nasal demons come steal random data and give everyone a bad
-            // time.
-            // `MaybeUninit`是表示可能未初始化的值的唯一方法，而不会导致使用`std::mem::uninitialized`或`std::mem::zeroed`立即产生UB。
-            // 有关更多信息，请参阅文档：https://doc.rust-lang.org/stable/std/mem/union.MaybeUninit.html#initialization-invariant
-            // 我们需要使用它来保证一个没有任何数据的UnsafeCell不会导致UB，直到我们对其进行唯一一次初始化。
-            MaybeUninit,
-        },
-        // Sometimes you need to make sure that something is done once and
-        // only once. We also might want to make sure that no matter on what
-        // thread this holds true. Enter `Once`, a really great synchronization
-        // type that's around for just this purpose. It also has the nice
-        // property that if, say, it gets called to be initialized across many
-        // threads that it only runs the initialization function once and has
-        // the other threads wait until it's done before letting them continue
-        // with their execution.
-        // 有时你需要确保某件事只做一次。我们还可能希望确保无论在哪个线程上都是如此。`Once`，一个非常好的同步类型，就是为了这个目的。
-        // 它还具有一个很好的属性，即如果它被用在多线程同时初始化时，则它保证只运行初始化函数一次，并且其他线程等待直到它完成，然后才让它们继续执行。
-        sync::Once,
-    };
+        /// Waits for every future to finish and gathers up their outputs in
+        /// the order they finished in.
+        /// 等待每个future完成，并按照它们完成的顺序收集它们的输出。
+        pub async fn collect(mut self) -> Vec<F::Output> {
+            let mut outputs = Vec::with_capacity(self.len());
+            while let Some(output) = self.next().await {
+                outputs.push(output);
+            }
+            outputs
+        }
+    }
 
-    /// We want to have a static value that's set at runtime and this executor will
-    /// only use libstd. As of 10/26/21, the lazy types in std are still only on
-    /// nightly and we can't use another crate, so crates like `once_cell` and
-    /// `lazy_static` are also out. Thus, we create our own Lazy type so that it will
-    /// calculate the value only once and only when we need it.
-    /// 我们想要一个在运行时设置的静态值，而且这个执行器只使用标准库。
-    /// 截至2021年10月26日，std中的延迟类型仍然只在夜间版中，我们不能使用另一个crate，因此像`once_cell`和`lazy_static`这样的crate也不行。
-    /// 因此，我们创建自己的Lazy类型，以便它只计算一次值，并且仅在我们需要时才计算。
-    pub struct Lazy<T> {
-        /// `Once` is a neat synchronization primitive that we just talked about
-        /// and this is where we need it! We want to make sure we only write into
-        /// the value of the Lazy type once and only once. Otherwise we'd have some
-        /// really bad things happen if we let static values be mutated. It'd break
-        /// thread safety!
-        /// Once是一个很好的同步原语，我们刚刚谈到了它，这就是我们需要它的地方！我们想确保我们只将值写入Lazy类型一次且仅一次。
-        /// 否则，如果我们让静态值被改变，就会发生一些非常糟糕的事情。它会破坏线程安全！
-        once: Once,
-        /// The cell is where we hold our data. The use of `UnsafeCell` is what lets
-        /// us sidestep Rust's guarantees, provided we actually use it correctly and
-        /// still uphold those guarantees. Rust can't always validate that
-        /// everything is safe, even if it is, and so the flexibility it provides
-        /// with certain library types and unsafe code lets us handle those cases
-        /// where the compiler cannot possibly understand it's okay. We also use the
-        /// `MaybeUninit` type here to avoid undefined behavior with uninitialized
-        /// data. We'll need to drop the inner value ourselves though to avoid
-        /// memory leaks because data may not be initialized and so the type won't
-        /// call drop when it's not needed anymore. We could get away with not doing
-        /// it though since we're only using it for static values, but let's be
-        /// thorough here!
-        /// cell是我们保存数据的地方。使用`UnsafeCell`是让我们绕过Rust的保证限制，只要我们正确使用它并且仍然遵守这些保证。
-        /// Rust不总是能够确认程序是安全的，即使它确实是安全的，因此UnSafe代码提供的灵活性使我们能够处理编译器无法理解的情况。
-        /// 我们还在这里使用`MaybeUninit`类型来避免未初始化数据的未定义行为。但是我们需要自己丢弃内部值，以避免内存泄漏，因为数据可能未初始化，
-        /// 因此`MaybeUninit`不会在不再需要时调用drop。虽然我们只使用它来保存静态值，但我们还是要彻底地做到这一点！
-        cell: UnsafeCell<MaybeUninit<T>>,
+    impl<F, T> FuturesUnordered<F>
+    where
+        F: Future<Output = (usize, T)>,
+    {
+        /// If every future hands back its index along with its output, then we
+        /// can put the outputs back into that order once they're all done.
+        /// 如果每个future都连同它的输出一起交还它的索引，那么一旦它们全部完成，我们就可以把输出按这个顺序放回去。
+        pub async fn collect_ordered(self) -> Vec<T> {
+            let mut outputs = self.collect().await;
+            outputs.sort_by_key(|(index, _)| *index);
+            outputs.into_iter().map(|(_, output)| output).collect()
+        }
     }
 
-    impl<T> Lazy<T> {
-        /// We must construct the type using a const fn so that it can be used in
-        /// `static` contexts. The nice thing is that all of the function calls we
-        /// make here are also const and so this will just work. The compiler will
-        /// figure it all out and make sure the `Lazy` static value exists in our
-        /// final binary.
-        /// 我们必须使用const fn来构造类型，以便它可以在`static`上下文中使用。
-        /// 好的是，我们在这里调用的所有函数都是const的，因此这将正常工作。
-        /// 编译器会弄清楚一切，并确保`Lazy`静态值存在于我们的最终二进制文件中。
-        pub const fn new() -> Self {
+    impl<F: Future> Default for FuturesUnordered<F> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<F: Future> FromIterator<F> for FuturesUnordered<F> {
+        fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
             Self {
-                once: Once::new(),
-                cell: UnsafeCell::new(MaybeUninit::uninit()),
+                futures: iter.into_iter().map(Box::pin).collect(),
             }
         }
-        /// We want a way to check if we have initialized the value so that we can
-        /// get the value from cell without causing who knows what kind of bad
-        /// things if we read garbage data.
-        /// 我们想要一种方法来检查我们是否已经初始化了值，以便我们可以从cell中获取值，而不会导致我们读取垃圾数据而引起未知的坏事情。
-        fn is_initialized(&self) -> bool {
-            self.once.is_completed()
+    }
+
+    /// A `Future` in a `Box`, for when futures of different types need to go
+    /// in the same place, like a `Vec` of closures that each make one. It's
+    /// `Send` so it can move between workers, but it doesn't have to be
+    /// `Sync`, since a task only ever gets at its future from behind a lock.
+    /// Any `BoxFuture<'static, ()>` can go straight to `spawn_boxed`.
+    /// 一个放在`Box`里的`Future`，用于不同类型的future需要放在同一个地方的时候，比如一个`Vec`里装着各自创建一个future的闭包。
+    /// 它是`Send`的，这样它就可以在工作线程之间移动，但它不需要是`Sync`的，因为一个任务只会在一把锁后面访问它的future。
+    /// 任何`BoxFuture<'static, ()>`都可以直接交给`spawn_boxed`。
+    pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+    /// Puts `future` in a `BoxFuture`, which saves spelling out the type
+    /// when futures of different types need to end up in the same `Vec`.
+    /// 把`future`放进一个`BoxFuture`，当不同类型的future需要放进同一个`Vec`时，这样就省得把类型写出来了。
+    pub fn boxed<'a, F>(future: F) -> BoxFuture<'a, F::Output>
+    where
+        F: Future + Send + 'a,
+    {
+        Box::pin(future)
+    }
+
+    /// Runs every future in `futures` at the same time and hands back all of
+    /// their outputs, in the same order the futures came in.
+    /// 同时运行`futures`中的每个future，并交还它们所有的输出，顺序和future进来的顺序一样。
+    pub async fn join_all<I>(futures: I) -> Vec<<I::Item as Future>::Output>
+    where
+        I: IntoIterator,
+        I::Item: Future,
+    {
+        futures
+            .into_iter()
+            .enumerate()
+            .map(|(index, future)| async move { (index, future.await) })
+            .collect::<FuturesUnordered<_>>()
+            .collect_ordered()
+            .await
+    }
+
+    /// A future that holds on to its own output once it's done. Joining a
+    /// few futures means polling all of them until the last one is done, but
+    /// the ones that finished first can't be polled again and their outputs
+    /// have to go somewhere in the meantime. This keeps both in one place:
+    /// polling it drives the future and, once it's done, stores the output
+    /// and keeps saying `Ready` without touching the future again, until
+    /// `take_output` takes the output out.
+    /// 一个在完成之后会留住它自己的输出的future。join几个future意味着不停地poll它们所有直到最后一个完成，
+    /// 但是先完成的那些不能再被poll了，而它们的输出在此期间得有个地方放。这把两者放在了一个地方：
+    /// poll它会驱动里面的future，一旦完成，就把输出存起来，之后一直说`Ready`而不再碰那个future，直到`take_output`把输出拿走。
+    pub enum MaybeDone<F: Future> {
+        /// Still running.
+        /// 还在运行。
+        Future(F),
+        /// Done, with the output waiting to be taken.
+        /// 完成了，输出在等着被拿走。
+        Done(F::Output),
+        /// Done, and the output has been taken.
+        /// 完成了，并且输出已经被拿走了。
+        Gone,
+    }
+
+    /// Wraps `future` in a `MaybeDone`.
+    /// 把`future`包装进一个`MaybeDone`。
+    pub fn maybe_done<F: Future>(future: F) -> MaybeDone<F> {
+        MaybeDone::Future(future)
+    }
+
+    impl<F: Future> MaybeDone<F> {
+        /// Takes the output out, if it's done and nobody took it yet.
+        /// 如果已经完成并且还没有人拿走输出，就把输出拿出来。
+        pub fn take_output(self: Pin<&mut Self>) -> Option<F::Output> {
+            if !matches!(&*self, MaybeDone::Done(_)) {
+                return None;
+            }
+            // The future is gone by now, so nothing pinned gets moved.
+            // 这时future已经不在了，所以没有被pin住的东西会被移动。
+            match std::mem::replace(unsafe { self.get_unchecked_mut() }, MaybeDone::Gone) {
+                MaybeDone::Done(output) => Some(output),
+                _ => unreachable!(),
+            }
         }
+    }
 
-        /// This function will either grab a reference to the type or creates it
-        /// with a given function
-        ///
-        pub fn get_or_init(&self, func: fn() -> T) -> &T {
-            self.once.call_once(|| {
-                // /!\ SAFETY /!\: We only ever write to the cell once
-                //
-                // We first get a `*mut MaybeUninit` to the cell and turn it into a
-                // `&mut MaybeUninit`. That's when we call `write` on `MaybeUninit`
-                // to pass the value of the function into the now initialized
-                // `MaybeUninit`.
-                // 这是安全的！我们只会将值写入一次。
-                // 我们首先获取一个`*mut MaybeUninit`到cell，并将其转换为`&mut MaybeUninit`。
-                // 这时我们调用`MaybeUninit`上的`write`将函数的值传递到现在初始化的`MaybeUninit`。
-                (unsafe { &mut *self.cell.get() }).write(func());
-            });
-            // /!\ SAFETY /!\: We already made sure `Lazy` was initialized with our call to
-            // `call_once` above
-            //
-            // We now want to actually retrieve the value we wrote so that we can
-            // use it! We get the `*mut MaybeUninit` from the cell and turn it into
-            // a `&MaybeUninit` which then lets us call `assume_init_ref` to get
-            // the `&T`. This function - much like `get` - is also unsafe, but since we
-            // know that the value is initialized it's fine to call this!
-            // 这是安全的！我们已经确保`Lazy`已经初始化了。
-            // 我们现在想要实际获取我们写入的值，以便我们可以使用它！
-            // 我们从cell中获取`*mut MaybeUninit`并将其转换为`&MaybeUninit`，然后我们就可以调用`assume_init_ref`来获取`&T`。
-            // `assume_init_ref`这个函数 - 就像`get`一样 - 也是不安全的，但是由于我们知道值已经初始化，所以可以调用这个函数！
-            unsafe { &(*self.cell.get()).assume_init_ref() }
+    impl<F: Future> Future for MaybeDone<F> {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            // We never move the future out of its variant, we only drop it in
+            // place with `set`, so it stays pinned for as long as it lives.
+            // 我们永远不会把future从它的变体中移出来，只会用`set`在原地丢弃它，所以它在活着的时候一直是被pin住的。
+            let output = match unsafe { self.as_mut().get_unchecked_mut() } {
+                MaybeDone::Future(future) => match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                    Poll::Ready(output) => output,
+                    Poll::Pending => return Poll::Pending,
+                },
+                MaybeDone::Done(_) => return Poll::Ready(()),
+                MaybeDone::Gone => panic!("MaybeDone polled after its output was taken"),
+            };
+            self.set(MaybeDone::Done(output));
+            Poll::Ready(())
         }
     }
 
-    /// We now need to implement `Drop` by hand specifically because `MaybeUninit`
-    /// will need us to drop the value it holds by ourselves only if it exists. We
-    /// check if the value exists, swap it out with an uninitialized value and then
-    /// change `MaybeUninit<T>` into just a `T` with a call to `assume_init` and
-    /// then call `drop` on `T` itself
-    /// 我们现在需要手动实现`Drop`，特别是因为`MaybeUninit`需要我们自己丢弃它所持有的值，只有当它存在时才需要这样做。
-    /// 我们检查值是否存在，将其与未初始化的值交换，然后将`MaybeUninit<T>`转换为`T`，并通过调用`assume_init`来改变`T`，然后调用`T`上的`drop`。
-    impl<T> Drop for Lazy<T> {
-        fn drop(&mut self) {
-            if self.is_initialized() {
-                let old = mem::replace(unsafe { &mut *self.cell.get() }, MaybeUninit::uninit());
-                drop(unsafe { old.assume_init() });
+    /// Runs two futures at the same time and hands back both of their
+    /// outputs. Unlike `join_all` they can be of different types with
+    /// outputs of different types, and nothing gets boxed.
+    /// 同时运行两个future，并交还它们两个的输出。和`join_all`不同，它们可以是不同的类型、有着不同类型的输出，而且没有东西会被装箱。
+    pub async fn join2<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        let mut a = pin!(maybe_done(a));
+        let mut b = pin!(maybe_done(b));
+        poll_fn(|cx| {
+            // Everything gets polled every time, it's a `MaybeDone` that
+            // knows not to poll a future that's already done.
+            // 每次都会poll所有的东西，是`MaybeDone`知道不去poll一个已经完成了的future。
+            if [a.as_mut().poll(cx), b.as_mut().poll(cx)]
+                .iter()
+                .all(Poll::is_ready)
+            {
+                Poll::Ready((
+                    a.as_mut().take_output().unwrap(),
+                    b.as_mut().take_output().unwrap(),
+                ))
+            } else {
+                Poll::Pending
             }
-        }
+        })
+        .await
     }
 
-    /// Now you might be asking yourself why we are implementing these traits by
-    /// hand and also why it's unsafe to do so. `UnsafeCell`is the big reason here
-    /// and you can see this by commenting these two lines and trying to compile the
-    /// code. Because of how auto traits work then if any part is not `Send` and
-    /// `Sync` then we can't use `Lazy` for a static. Note that auto traits are a
-    /// compiler specific thing where if everything in a type implements a trait
-    /// then that type also implements it. `Send` and `Sync` are great examples of
-    /// this where any type becomes `Send` and/or `Sync` if all its types implement
-    /// them too! `UnsafeCell` specifically implements !Sync and since it is not
-    /// `Sync` then it can't be used in a `static`. We can override this behavior
-    /// though by implementing these traits for `Lazy` here though. We're saying
-    /// that this is okay and that we uphold the invariants to be `Send + Sync`. We
-    /// restrict it though and say that this is only the case if the type `T`
-    /// *inside* `Lazy` is `Sync` only if `T` is `Send + Sync`. We know then that
-    /// this is okay because the type in `UnsafeCell` can be safely referenced
-    /// through an `&'static` and that the type it holds is also safe to use across
-    /// threads. This means we can set `Lazy` as `Send + Sync` even though the
-    /// internal `UnsafeCell` is !Sync in a safe way since we upheld the invariants
-    /// for these traits.
-    /// 现在你可能会问自己为什么我们要手动实现这些trait，以及为什么这样做是不安全的。
-    /// `UnsafeCell`是这里的一个重要原因，你可以通过注释掉这两行并尝试编译代码来看到这一点。
-    /// 由于自动trait的工作方式，如果任何部分不是`Send`和`Sync`，那么我们就不能在静态变量中使用`Lazy`。
-    /// 请注意，自动trait是编译器特定的东西，如果类型中的所有部分都实现了trait，那么该类型也会实现该trait。
-    /// `Send`和`Sync`就是很好的例子，如果类型中所有部分都实现了它们，那么任何类型也都会变成`Send`和/或`Sync`。
-    /// `UnsafeCell`特别实现了!Sync，因为它不是`Sync`，所以它不能在`static`中使用。
-    /// 我们可以通过在这里为`Lazy`实现这些trait来覆盖此行为。
-    /// 也就是说，我们确认只有在`Lazy`内部的类型`T`是`Sync`时才是`Send + Sync`，以此保证了`Send + Sync`的不变量。
-    /// 我们知道这是可以的，因为`UnsafeCell`中的类型可以通过`&'static`安全地引用，并且它所持有的类型也可以安全地跨线程使用。
-    /// 这意味着我们可以在安全的方式中将`Lazy`设置为`Send + Sync`，即使内部的`UnsafeCell`是!Sync，因为我们保证了这些trait的不变量。
-    unsafe impl<T: Send> Send for Lazy<T> {}
+    /// The same as `join2` for three futures.
+    /// 和`join2`一样，用于三个future。
+    pub async fn join3<A: Future, B: Future, C: Future>(
+        a: A,
+        b: B,
+        c: C,
+    ) -> (A::Output, B::Output, C::Output) {
+        let mut a = pin!(maybe_done(a));
+        let mut b = pin!(maybe_done(b));
+        let mut c = pin!(maybe_done(c));
+        poll_fn(|cx| {
+            if [
+                a.as_mut().poll(cx),
+                b.as_mut().poll(cx),
+                c.as_mut().poll(cx),
+            ]
+            .iter()
+            .all(Poll::is_ready)
+            {
+                Poll::Ready((
+                    a.as_mut().take_output().unwrap(),
+                    b.as_mut().take_output().unwrap(),
+                    c.as_mut().take_output().unwrap(),
+                ))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
 
-    unsafe impl<T: Send + Sync> Sync for Lazy<T> {}
-}
+    /// The same as `join2` for four futures.
+    /// 和`join2`一样，用于四个future。
+    pub async fn join4<A: Future, B: Future, C: Future, D: Future>(
+        a: A,
+        b: B,
+        c: C,
+        d: D,
+    ) -> (A::Output, B::Output, C::Output, D::Output) {
+        let mut a = pin!(maybe_done(a));
+        let mut b = pin!(maybe_done(b));
+        let mut c = pin!(maybe_done(c));
+        let mut d = pin!(maybe_done(d));
+        poll_fn(|cx| {
+            if [
+                a.as_mut().poll(cx),
+                b.as_mut().poll(cx),
+                c.as_mut().poll(cx),
+                d.as_mut().poll(cx),
+            ]
+            .iter()
+            .all(Poll::is_ready)
+            {
+                Poll::Ready((
+                    a.as_mut().take_output().unwrap(),
+                    b.as_mut().take_output().unwrap(),
+                    c.as_mut().take_output().unwrap(),
+                    d.as_mut().take_output().unwrap(),
+                ))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
 
-pub mod runtime {
-    use crate::{current_thread_id, current_time};
-    use std::time::SystemTime;
-    use std::{
-        // We need a place to put the futures that get spawned onto the runtime
-        // somewhere and while we could use something like a `Vec`, we chose a
-        // `LinkedList` here. One reason being that we can put tasks at the front of
-        // the queue if they're a blocking future. The other being that we use a
-        // constant amount of memory. We only ever use as much as we need for tasks.
-        // While this might not matter at a small scale, this does at a larger
-        // scale. If your `Vec` never gets smaller and you have a huge burst of
-        // tasks under, say, heavy HTTP loads in a web server, then you end up eating
-        // up a lot of memory that could be used for other things running on the
-        // same machine. In essence what you've created is a kind of memory leak
-        // unless you make sure to resize the `Vec`. @mycoliza did a good Twitter
-        // thread on this here if you want to learn more!
-        //
-        // https://twitter.com/mycoliza/status/1298399240121544705
-        collections::LinkedList,
-        // A Future is the fundamental block of any async executor. It is a trait
-        // that types can make or an unnameable type that an async function can
-        // make. We say it's unnameable because you don't actually define the type
-        // anywhere and just like a closure you can only specify its behavior with
-        // a trait. You can't give it a name like you would when you do something
-        // like `pub struct Foo;`. These types, whether nameable or not, represent all
-        // the state needed to have an asynchronous function. You poll the future to
-        // drive its computation along like a state machine that makes transistions
-        // from one state to another till it finishes. If you reach a point where it
-        // would yield execution, then it needs to be rescheduled to be polled again
-        // in the future. It yields though so that you can drive other futures
-        // forward in their computation!
-        //
-        // This is the important part to understand here with the executor: the
-        // Future trait defines the API we use to drive forward computation of it,
-        // while the implementor of the trait defines how that computation will work
-        // and when to yield to the executor. You'll see later that we have an
-        // example of writing a `Sleep` future by hand as well as unnameable async
-        // code using `async { }` and we'll expand on when those yield and what it
-        // desugars to in practice. We're here to demystify the mystical magic of
-        // async code.
-        future::Future,
-        // Ah Pin. What a confusing type. The best way to think about `Pin` is that
-        // it records when a value became immovable or pinned in place. `Pin` doesn't
-        // actually pin the value, it just notes that the value will not move, much
-        // in the same way that you can specify Rust lifetimes. It only records what
-        // the lifetime already is, it doesn't actually create said lifetime! At the
-        // bottom of this, I've linked some more in depth reading on Pin, but if you
-        // don't know much about Pin, starting with the standard library docs isn't a
-        // bad place.
-        //
-        // Note: Unpin is also a confusing name and if you think of it as
-        // MaybePinned you'll have a better time as the value may be pinned or it
-        // may not be pinned. It just marks that if you have a Pinned value and it
-        // moves that's okay and it's safe to do so, whereas for types that do not
-        // implement Unpin and they somehow move, will cause some really bad things
-        // to happen since it's not safe for the type to be moved after being
-        // pinned. We create our executor with the assumption that every future we
-        // get will need to be a pinned value, even if it is actually Unpin. This
-        // makes it nicer for everyone using the executor as it's very easy to make
-        // types that do not implement Unpin.
-        pin::Pin,
-        sync::{
-            // What's not to love about Atomics? This lets us have thread safe
-            // access to primitives so that we can modify them or load them using
-            // Ordering to tell the compiler how it should handle giving out access
-            // to the data. Atomics are a rather deep topic that's out of scope for
-            // this. Just note that we want to change a usize safely across threads!
-            atomic::{AtomicUsize, Ordering},
-            // Arc is probably one of the more important types we'll use in the
-            // executor. It lets us freely clone cheap references to the data which
-            // we can use across threads while making it easy to not have to worry about
-            // complicated lifetimes since we can easily own the data with a call to
-            // clone. It's one of my favorite types in the standard library.
-            Arc,
-            // Normally I would use `parking_lot` for a Mutex, but the goal is to
-            // use stdlib only. A personal gripe is that it cares about Mutex
-            // poisoning (when a thread panics with a hold on the lock), which is
-            // not something I've in practice run into (others might!) and so calling
-            // `lock().unwrap()` everywhere can get a bit tedious. That being said
-            // Mutexes are great. You make sure only one thing has access to the data
-            // at any given time to access or change it.
-            Mutex,
-        },
-        // The task module contains all of the types and traits related to
-        // having an executor that can create and run tasks that are `Futures`
-        // that need to be polled.
-        task::{
-            // `Context` is passed in every call to `poll` for a `Future`. We
-            // didn't use it in our `Sleep` one, but it has to be passed in
-            // regardless. It gives us access to the `Waker` for the future so
-            // that we can call it ourselves inside the future if need be!
-            Context,
-            // Poll is the enum returned from when we poll a `Future`. When we
-            // call `poll`, this drives the `Future` forward until it either
-            // yields or it returns a value. `Poll` represents that. It is
-            // either `Poll::Pending` or `Poll::Ready(T)`. We use this to
-            // determine if a `Future` is done or not and if not, then we should
-            // keep polling it.
-            Poll,
-            // This is a trait to define how something in an executor is woken
-            // up. We implement it for `Task` which is what lets us create a
-            // `Waker` from it, to then make a `Context` which can then be
-            // passed into the call to `poll` on the `Future` inside the `Task`.
-            Wake,
-            // A `Waker` is the type that has a handle to the runtime to let it
-            // know when a task is ready to be scheduled for polling. We're
-            // doing a very simple version where as soon as a `Task` is done
-            // polling we tell the executor to wake it. Instead what you might
-            // want to do when creating a `Future` is have a more involved way
-            // to only wake when it would be ready to poll, such as a timer
-            // completing, or listening for some kind of signal from the OS.
-            // It's kind of up to the executor how it wants to do it. Maybe how
-            // it schedules things is different or it has special behavior for
-            // certain `Future`s that it ships with it. The key thing to note
-            // here is that this is how tasks are supposed to be rescheduled for
-            // polling.
-            Waker,
-        },
-    };
+    /// A future that's already done and succeeded with `value`. Handy when
+    /// something wants a fallible future but you already have the answer.
+    /// 一个已经完成并以`value`成功的future。当某个东西想要一个可能失败的future，而你已经有了答案时很方便。
+    pub fn ok<T, E>(value: T) -> Ready<Result<T, E>> {
+        ready(Ok(value))
+    }
 
-    /// This is it, the thing we've been alluding to for most of this file. It's
-    /// the `Runtime`! What is it? What does it do? Well the `Runtime` is what
-    /// actually drives our async code to completion. Remember asynchronous code
-    /// is just code that gets run for a bit, yields part way through the
-    /// function, then continues when polled and it repeats this process till
-    /// being completed. In reality what this means is that the code is run
-    /// using synchronous functions that drive tasks in a concurrent manner.
-    /// They could also be run concurrently and/or in parallel if the executor
-    /// is multithreaded. Tokio is a good example of this model where it runs
-    /// tasks in parallel on separate threads and if it has more tasks than
-    /// threads, it runs them concurrently on those threads.
-    ///
-    /// 这就是我们一直在谈论的东西。它是 `Runtime`！
-    /// 它是什么？它能做什么？好吧，`Runtime` 就是驱动我们的异步代码执行完成的东西！
-    /// 还记得异步代码吗？它只是一些代码，它会运行一段时间，然后通过Sleep函数暂停一段时间，
+    /// A future that's already done and failed with `error`.
+    /// 一个已经完成并以`error`失败的future。
+    pub fn err<T, E>(error: E) -> Ready<Result<T, E>> {
+        ready(Err(error))
+    }
+
+    /// A future that might not be there. Awaiting it drives the inner future
+    /// and hands back `Some` of its output, or hands back `None` right away
+    /// if there isn't one. That way work that only sometimes needs doing can
+    /// still go in a `join` or a `select_biased!` without a branch around it.
+    /// 一个可能不存在的future。await它会驱动里面的future并交出`Some`包着的输出，如果没有里面的future就立刻交出`None`。
+    /// 这样只是有时候需要做的工作仍然可以放进`join`或者`select_biased!`里面，而不需要在它外面加一个分支。
+    pub struct OptionFuture<F> {
+        /// Boxed so that we can poll it without caring whether it's `Unpin`.
+        /// 装箱了，这样我们poll它的时候就不需要关心它是不是`Unpin`。
+        future: Option<Pin<Box<F>>>,
+    }
+
+    impl<F> From<Option<F>> for OptionFuture<F> {
+        fn from(future: Option<F>) -> Self {
+            Self {
+                future: future.map(Box::pin),
+            }
+        }
+    }
+
+    impl<F: Future> Future for OptionFuture<F> {
+        type Output = Option<F::Output>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match self.future.as_mut() {
+                Some(future) => future.as_mut().poll(cx).map(Some),
+                None => Poll::Ready(None),
+            }
+        }
+    }
+
+    /// Runs two fallible futures at the same time and hands back both of
+    /// their values if they both succeed. As soon as either one fails we give
+    /// up on the other. Returning drops it, along with whatever work it had
+    /// left, since there's no point finishing it when the result is going to
+    /// be an error anyway. This is the async version of using `?` on two
+    /// results in a row.
+    /// 同时运行两个可能失败的future，如果它们都成功了，就交还它们两个的值。只要其中一个失败了，我们就放弃另一个。
+    /// 返回会丢弃它以及它剩下的所有工作，因为反正结果都会是一个错误，把它完成也没有意义。这就是连续对两个结果使用`?`的异步版本。
+    pub async fn try_join2<A, B, T1, T2, E>(a: A, b: B) -> Result<(T1, T2), E>
+    where
+        A: Future<Output = Result<T1, E>>,
+        B: Future<Output = Result<T2, E>>,
+    {
+        let mut a = pin!(a);
+        let mut b = pin!(b);
+        let mut a_output = None;
+        let mut b_output = None;
+        poll_fn(|cx| {
+            // A future that's already done must not be polled again, so we
+            // only poll the ones that haven't handed back a value yet.
+            // 一个已经完成的future不能再被poll了，所以我们只poll那些还没有交还值的。
+            if a_output.is_none() {
+                if let Poll::Ready(output) = a.as_mut().poll(cx) {
+                    a_output = Some(output?);
+                }
+            }
+            if b_output.is_none() {
+                if let Poll::Ready(output) = b.as_mut().poll(cx) {
+                    b_output = Some(output?);
+                }
+            }
+            match (a_output.take(), b_output.take()) {
+                (Some(a), Some(b)) => Poll::Ready(Ok((a, b))),
+                (a, b) => {
+                    a_output = a;
+                    b_output = b;
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// The same as `try_join2` for any number of futures of the same type.
+    /// It hands back every value, in the order the futures came in, if they
+    /// all succeed, or the first error as soon as one of them fails, dropping
+    /// all of the others that haven't finished yet.
+    /// 和`try_join2`一样，用于任意多个同一类型的future。如果它们都成功了，就按future进来的顺序交还每一个值，
+    /// 或者只要其中一个失败了，就交还第一个错误，并丢弃其他所有还没有完成的future。
+    pub async fn try_join_all<I, T, E>(futures: I) -> Result<Vec<T>, E>
+    where
+        I: IntoIterator,
+        I::Item: Future<Output = Result<T, E>>,
+    {
+        let mut running = futures
+            .into_iter()
+            .enumerate()
+            .map(|(index, future)| async move { (index, future.await) })
+            .collect::<FuturesUnordered<_>>();
+        let mut values = Vec::with_capacity(running.len());
+        // Returning early drops `running`, and every future still in it.
+        // 提前返回会丢弃`running`，以及其中所有的future。
+        while let Some((index, output)) = running.next().await {
+            values.push((index, output?));
+        }
+        values.sort_by_key(|(index, _)| *index);
+        Ok(values.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// How long `retry` waits between attempts. Each wait is twice as long as
+    /// the one before, starting at `base` milliseconds and never going past
+    /// `max`. On top of that every wait is moved up or down by a random amount
+    /// of up to `jitter` milliseconds. Without that, a bunch of clients that
+    /// all failed at the same moment, say because a server went down, would
+    /// all retry at the same moments too, and keep knocking it over together.
+    /// The randomness comes from a `seed`, so with the same seed you always
+    /// get the same waits, which is what you want in a test.
+    /// `retry`在两次尝试之间等待多久。每次等待都是上一次的两倍长，从`base`毫秒开始，永远不会超过`max`。
+    /// 在此之上，每次等待都会被向上或向下移动一个最多`jitter`毫秒的随机量。没有它的话，一堆在同一时刻失败的客户端，
+    /// 比如因为一个服务器挂了，也会在同样的时刻重试，一起不停地把它再次撞倒。随机性来自一个`seed`，
+    /// 所以用同一个种子你总会得到同样的等待时间，这正是你在测试中想要的。
+    #[derive(Debug, Clone)]
+    pub struct Backoff {
+        base: u128,
+        max: u128,
+        jitter: u128,
+        attempt: u32,
+        rng: XorShift,
+    }
+
+    impl Backoff {
+        /// Waits starting at `base` milliseconds that double up to `max`,
+        /// without any jitter.
+        /// 从`base`毫秒开始、翻倍直到`max`的等待，没有任何抖动。
+        pub fn new(base: u128, max: u128) -> Self {
+            Self {
+                base,
+                max,
+                jitter: 0,
+                attempt: 0,
+                rng: XorShift::from_clock(),
+            }
+        }
+
+        /// Move every wait up or down by up to `jitter` milliseconds.
+        /// 把每次等待向上或向下移动最多`jitter`毫秒。
+        pub fn jitter(mut self, jitter: u128) -> Self {
+            self.jitter = jitter;
+            self
+        }
+
+        /// Where the randomness for the jitter comes from.
+        /// 抖动的随机性从哪里来。
+        pub fn seed(mut self, seed: u64) -> Self {
+            self.rng = XorShift::new(seed);
+            self
+        }
+
+        /// How long to wait before the next attempt.
+        /// 在下一次尝试之前要等待多久。
+        pub fn next_delay(&mut self) -> u128 {
+            // Past a point doubling would only overflow, and we'd be way past
+            // `max` long before then anyway.
+            // 过了某个点之后翻倍只会溢出，而且在那之前很久我们就已经远远超过`max`了。
+            let doubled = self.base.saturating_mul(1 << self.attempt.min(64));
+            self.attempt += 1;
+            let delay = doubled.min(self.max);
+            let jittered = if self.jitter == 0 {
+                delay
+            } else {
+                // A huge `jitter` would overflow here too, and a spread that
+                // gets cut short at the top is no worse for it.
+                // 一个巨大的`jitter`在这里也会溢出，而一个在顶端被截短了的分布也不会因此变差。
+                let spread = self.jitter.saturating_mul(2).saturating_add(1);
+                let offset = u128::from(self.rng.next()) % spread;
+                delay.saturating_add(offset).saturating_sub(self.jitter)
+            };
+            // The jitter mustn't take us past `max` either.
+            // 抖动也不能让我们超过`max`。
+            jittered.min(self.max)
+        }
+    }
+
+    /// A tiny random number generator. It's nowhere near good enough for
+    /// anything to do with security but it's plenty for spreading out
+    /// retries, and it means we don't need a whole crate for it.
+    /// 一个小小的随机数生成器。对于任何和安全有关的东西它都远远不够好，但是用来分散重试绰绰有余，而且这意味着我们不需要为此引入一整个crate。
+    #[derive(Debug, Clone)]
+    pub(crate) struct XorShift(u64);
+
+    impl XorShift {
+        pub(crate) fn new(seed: u64) -> Self {
+            // An all zero state would only ever hand back zeros.
+            // 一个全零的状态永远只会交出零。
+            Self(if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            })
+        }
+
+        /// Without a seed we just take whatever the clock says.
+        /// 没有种子的话我们就用时钟上的随便什么值。
+        pub(crate) fn from_clock() -> Self {
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64;
+            Self::new(nanos)
+        }
+
+        pub(crate) fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    /// Runs `op` until it succeeds, up to `attempts` times, sleeping for as
+    /// long as `backoff` says in between. If every attempt fails you get the
+    /// last error.
+    /// 运行`op`直到它成功，最多`attempts`次，在两次之间按照`backoff`所说的时间睡眠。如果每次尝试都失败了，你会得到最后一个错误。
+    pub async fn retry<T, E, F, Fut>(
+        attempts: usize,
+        mut backoff: Backoff,
+        mut op: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        assert!(attempts > 0, "retry needs at least one attempt");
+        let mut attempt = 1;
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(error) if attempt == attempts => return Err(error),
+                Err(_) => {
+                    Sleep::new(backoff.next_delay()).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Waits on a few futures at once and runs the handler of whichever one
+    /// finishes first, dropping the rest. If more than one is ready, the one
+    /// written first always wins, since we poll them strictly in the order
+    /// they're written in. That's what you want for something like checking
+    /// for a shutdown before picking up more work. The flip side is that a
+    /// branch that's always ready starves every branch after it, so put
+    /// anything that's ready very often last. The patterns have to be ones
+    /// that always match, like a name or `_`.
+    /// 同时等待几个future，运行最先完成的那个的处理代码，并丢弃其余的。如果有不止一个已经准备好了，
+    /// 写在最前面的那个总是会赢，因为我们严格按照它们被写下的顺序poll它们。这正是你在比如说取更多工作之前先检查是否要关闭时想要的。
+    /// 反过来说，一个总是准备好的分支会让它之后的每一个分支都饿死，所以把任何经常准备好的东西放在最后。
+    /// 模式必须是总能匹配的那种，比如一个名字或者`_`。
+    #[macro_export]
+    macro_rules! select_biased {
+        ($($pat:pat = $future:expr => $body:expr),+ $(,)?) => {
+            $crate::select_biased!(@branch [] $($pat = $future => $body,)+)
+        };
+        // Every branch gets its own pinned future and a slot for its output.
+        // They're all called the same thing but each one comes from a
+        // different expansion, so hygiene keeps them apart, and we pass the
+        // names along to the end where they all get used.
+        // 每个分支都有它自己被pin住的future和一个用于它输出的位置。它们都叫同一个名字，但每一个都来自不同的展开，
+        // 所以卫生性会把它们区分开，我们把这些名字一路传到最后，在那里它们全都会被用到。
+        (@branch [$($done:tt)*] $pat:pat = $future:expr => $body:expr, $($rest:tt)*) => {{
+            let mut future = ::std::pin::pin!($future);
+            let mut output = ::std::option::Option::None;
+            $crate::select_biased!(@branch [$($done)* (future, output, $pat, $body)] $($rest)*)
+        }};
+        (@branch [$(($future:ident, $output:ident, $pat:pat, $body:expr))+]) => {{
+            ::std::future::poll_fn(|cx| {
+                $(
+                    if let ::std::task::Poll::Ready(value) =
+                        ::std::future::Future::poll($future.as_mut(), cx)
+                    {
+                        $output = ::std::option::Option::Some(value);
+                        return ::std::task::Poll::Ready(());
+                    }
+                )+
+                ::std::task::Poll::Pending
+            })
+            .await;
+            $(
+                if let ::std::option::Option::Some($pat) = $output {
+                    $body
+                } else
+            )+
+            {
+                unreachable!()
+            }
+        }};
+    }
+
+    pub mod sync {
+        //! Tools for tasks to talk to and coordinate with each other. These are
+        //! the async cousins of what's in `std::sync`: rather than blocking the
+        //! thread while they wait, they hold on to the task's `Waker` and return
+        //! `Pending`, then wake the task up once it's worth polling again.
+        //! 用于任务之间通信和协调的工具。它们是`std::sync`中那些类型的异步版本：
+        //! 它们在等待时不会阻塞线程，而是持有任务的`Waker`并返回`Pending`，然后在值得再次poll时唤醒任务。
+        use std::{
+            future::poll_fn,
+            sync::{Arc, Mutex as StdMutex},
+            task::{Poll, Waker},
+        };
+
+        /// A latch that opens once it's been counted down `n` times. Unlike a
+        /// barrier, the ones counting down and the ones waiting aren't the same
+        /// tasks, nobody counting down has to wait, and once it's open it stays
+        /// open for good. A typical use is waiting until `n` subtasks have all
+        /// said that they're done.
+        /// 一个在被倒数`n`次之后打开的门闩。和屏障不同，倒数的和等待的不是同一批任务，倒数的人不需要等待，
+        /// 而且一旦打开它就永远保持打开。一个典型的用法是等待直到`n`个子任务都说它们已经完成了。
+        #[derive(Clone)]
+        pub struct CountDownLatch {
+            state: Arc<StdMutex<LatchState>>,
+        }
+
+        struct LatchState {
+            count: usize,
+            wakers: Vec<Waker>,
+        }
+
+        impl CountDownLatch {
+            /// Creates a latch that opens after `n` calls to `count_down`.
+            /// 创建一个在`count_down`被调用`n`次之后打开的门闩。
+            pub fn new(n: usize) -> Self {
+                Self {
+                    state: Arc::new(StdMutex::new(LatchState {
+                        count: n,
+                        wakers: Vec::new(),
+                    })),
+                }
+            }
+
+            /// Counts down by one, opening the latch and waking everyone up if
+            /// that was the last one. Counting down an open latch does nothing.
+            /// 倒数一次，如果这是最后一次，就打开门闩并唤醒所有人。对一个已经打开的门闩倒数什么都不会做。
+            pub fn count_down(&self) {
+                let wakers = {
+                    let mut state = self.state.lock().unwrap();
+                    if state.count == 0 {
+                        return;
+                    }
+                    state.count -= 1;
+                    if state.count > 0 {
+                        return;
+                    }
+                    std::mem::take(&mut state.wakers)
+                };
+                wakers.into_iter().for_each(Waker::wake);
+            }
+
+            /// How many more times the latch needs to be counted down.
+            /// 这个门闩还需要被倒数多少次。
+            pub fn count(&self) -> usize {
+                self.state.lock().unwrap().count
+            }
+
+            /// Waits for the latch to open.
+            /// 等待门闩打开。
+            pub async fn wait(&self) {
+                poll_fn(|cx| {
+                    let mut state = self.state.lock().unwrap();
+                    if state.count == 0 {
+                        return Poll::Ready(());
+                    }
+                    if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                        state.wakers.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                })
+                .await
+            }
+        }
+
+        pub use mutex::{Lock, Mutex, MutexGuard};
+
+        mod mutex {
+            use crate::runtime::{self, TaskId};
+            use std::{
+                cell::UnsafeCell,
+                collections::VecDeque,
+                future::Future,
+                marker::PhantomData,
+                ops::{Deref, DerefMut},
+                pin::Pin,
+                sync::Mutex as StdMutex,
+                task::{Context, Poll, Waker},
+            };
+
+            /// A `Mutex` whose `lock` you `await` rather than block on, so that
+            /// waiting for it doesn't hold up the thread the task is running on.
+            ///
+            /// There are two flavors. One made with `new` lets whoever shows up
+            /// first when the lock is free take it, even if others have been
+            /// waiting for ages. That's called barging and it's quick, since
+            /// the lock never sits idle waiting for the next waiter to get
+            /// polled, but an unlucky task can get barged in front of over and
+            /// over. One made with `new_fair` hands the lock straight to the
+            /// task that's been waiting the longest instead, so everyone gets
+            /// it in the order they asked for it and nobody starves. The price
+            /// is that the lock stays taken while that task makes its way back
+            /// through the queue, even if some other task could have used it
+            /// in the meantime.
+            /// 一个你`await`它的`lock`而不是阻塞在上面的`Mutex`，这样等待它就不会卡住任务所在的线程。
+            ///
+            /// 它有两种口味。用`new`创建的那种会让锁空闲时第一个出现的人拿走它，即使其他人已经等了很久。
+            /// 这叫做抢占（barging），它很快，因为锁永远不会空闲着等待下一个等待者被poll，但是一个不走运的任务可能会被一次又一次地插队。
+            /// 用`new_fair`创建的那种则会把锁直接交给等待时间最长的任务，所以每个人都按照他们请求的顺序得到它，没有人会饿死。
+            /// 代价是在那个任务排队回来的这段时间里锁一直被占着，即使其他任务本可以在此期间使用它。
+            pub struct Mutex<T> {
+                fair: bool,
+                state: StdMutex<State>,
+                value: UnsafeCell<T>,
+            }
+
+            struct State {
+                locked: bool,
+                /// The tasks waiting for the lock, longest waiting first.
+                /// 等待锁的任务，等待时间最长的排在最前面。
+                waiters: VecDeque<(u64, Waker)>,
+                /// The waiter that was woken up last time the lock was let go.
+                /// For a fair lock it owns the lock now, for a barging one it
+                /// just gets a shot at taking it.
+                /// 上一次锁被释放时被唤醒的等待者。对于公平锁来说，它现在拥有这把锁；对于抢占锁来说，它只是得到了一次拿锁的机会。
+                handoff: Option<u64>,
+                next_id: u64,
+            }
+
+            // Just like `std::sync::Mutex`, the lock makes sure only one task
+            // gets at the value at a time, so we only need it to be `Send`.
+            // 就像`std::sync::Mutex`一样，这把锁确保了同一时间只有一个任务能访问这个值，所以我们只需要它是`Send`的。
+            unsafe impl<T: Send> Send for Mutex<T> {}
+            unsafe impl<T: Send> Sync for Mutex<T> {}
+
+            impl<T> Mutex<T> {
+                /// Creates a lock where whoever shows up first when it's free
+                /// gets it.
+                /// 创建一把在空闲时谁先出现谁就能得到的锁。
+                pub fn new(value: T) -> Self {
+                    Self::with_fairness(value, false)
+                }
+
+                /// Creates a lock that's handed out in the order it was asked
+                /// for.
+                /// 创建一把按照请求顺序分配的锁。
+                pub fn new_fair(value: T) -> Self {
+                    Self::with_fairness(value, true)
+                }
+
+                fn with_fairness(value: T, fair: bool) -> Self {
+                    Self {
+                        fair,
+                        state: StdMutex::new(State {
+                            locked: false,
+                            waiters: VecDeque::new(),
+                            handoff: None,
+                            next_id: 0,
+                        }),
+                        value: UnsafeCell::new(value),
+                    }
+                }
+
+                /// Waits for the lock.
+                /// 等待这把锁。
+                pub fn lock(&self) -> Lock<'_, T> {
+                    Lock {
+                        mutex: self,
+                        id: None,
+                        tracked: None,
+                    }
+                }
+
+                /// Takes the lock if it's free right now, and hands back `None`
+                /// straight away if it isn't. We never get in line, so nobody
+                /// who is waiting gets moved around. A fair lock that's been
+                /// handed to a waiter counts as taken, even if that waiter
+                /// hasn't been polled yet.
+                /// 如果锁此刻是空闲的就拿走它，如果不是就马上返回`None`。我们永远不会去排队，所以没有哪个在等待的人会被挪动。
+                /// 一把已经交给了某个等待者的公平锁算作已经被占用了，即使那个等待者还没有被poll。
+                pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+                    let mut state = self.state.lock().unwrap();
+                    if state.locked {
+                        return None;
+                    }
+                    state.locked = true;
+                    Some(MutexGuard {
+                        mutex: self,
+                        tracked: false,
+                        _not_sync: PhantomData,
+                    })
+                }
+
+                /// Where we are, which is what the deadlock detector goes by.
+                /// 我们在哪里，这是死锁检测器用来区分锁的依据。
+                fn address(&self) -> usize {
+                    self as *const Self as usize
+                }
+
+                /// Lets go of the lock, or hands it to the next waiter if this
+                /// is a fair lock. Whoever needs waking up gets returned so it
+                /// can be woken once `state` is unlocked.
+                /// 释放这把锁，如果这是一把公平锁，就把它交给下一个等待者。需要被唤醒的那个会被返回，这样它就可以在`state`解锁后被唤醒。
+                fn release(&self, state: &mut State) -> Option<Waker> {
+                    match state.waiters.pop_front() {
+                        Some((id, waker)) => {
+                            state.handoff = Some(id);
+                            state.locked = self.fair;
+                            Some(waker)
+                        }
+                        None => {
+                            state.locked = false;
+                            None
+                        }
+                    }
+                }
+
+                fn unlock(&self) {
+                    let waker = self.release(&mut self.state.lock().unwrap());
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+
+            /// The future returned by `Mutex::lock`.
+            /// `Mutex::lock`返回的future。
+            pub struct Lock<'a, T> {
+                mutex: &'a Mutex<T>,
+                /// Our place in line, once we've had to get in it.
+                /// 我们在队伍中的位置，一旦我们不得不排队的话。
+                id: Option<u64>,
+                /// The task the deadlock detector knows is waiting on us.
+                /// 死锁检测器所知道的正在等待我们的那个任务。
+                tracked: Option<TaskId>,
+            }
+
+            impl<'a, T> Future for Lock<'a, T> {
+                type Output = MutexGuard<'a, T>;
+                fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                    let mutex = self.mutex;
+                    let tracking = runtime::deadlock_tracking(cx);
+                    let mut state = mutex.state.lock().unwrap();
+                    let handed_to_us = self.id.is_some() && state.handoff == self.id;
+                    if handed_to_us {
+                        state.handoff = None;
+                    }
+                    if (handed_to_us && mutex.fair) || !state.locked {
+                        state.locked = true;
+                        if let Some(id) = self.id.take() {
+                            state.waiters.retain(|(waiter, _)| *waiter != id);
+                        }
+                        self.tracked = None;
+                        if let Some(task) = tracking {
+                            runtime::lock_acquired(task, mutex.address());
+                        }
+                        return Poll::Ready(MutexGuard {
+                            mutex,
+                            tracked: tracking.is_some(),
+                            _not_sync: PhantomData,
+                        });
+                    }
+                    // Someone else has it, so we get in line, or if we're
+                    // already in line, make sure we're woken up the right way.
+                    // A barging waiter that got beaten to the lock has to go
+                    // to the back of the line again.
+                    // 别人拿着它，所以我们去排队；如果我们已经在排队了，就确保我们会被正确地唤醒。
+                    // 一个被抢先拿走锁的抢占式等待者必须重新排到队伍的最后。
+                    let waiting = self
+                        .id
+                        .and_then(|id| state.waiters.iter_mut().find(|(waiter, _)| *waiter == id));
+                    match waiting {
+                        Some((_, waker)) => waker.clone_from(cx.waker()),
+                        None => {
+                            let id = *self.id.get_or_insert(state.next_id);
+                            state.next_id += 1;
+                            state.waiters.push_back((id, cx.waker().clone()));
+                        }
+                    }
+                    drop(state);
+                    if let Some(task) = tracking {
+                        self.tracked = Some(task);
+                        runtime::lock_waiting(task, mutex.address());
+                    }
+                    Poll::Pending
+                }
+            }
+
+            /// If we stop waiting after the lock was already handed to us, we
+            /// have to pass it on, otherwise everyone behind us would be stuck.
+            /// 如果我们在锁已经交给我们之后才停止等待，我们就必须把它传下去，否则我们后面的所有人都会被卡住。
+            impl<T> Drop for Lock<'_, T> {
+                fn drop(&mut self) {
+                    if let Some(task) = self.tracked {
+                        runtime::lock_abandoned(task);
+                    }
+                    let Some(id) = self.id else { return };
+                    let waker = {
+                        let mut state = self.mutex.state.lock().unwrap();
+                        if state.handoff == Some(id) {
+                            state.handoff = None;
+                            if self.mutex.fair || !state.locked {
+                                self.mutex.release(&mut state)
+                            } else {
+                                None
+                            }
+                        } else {
+                            state.waiters.retain(|(waiter, _)| *waiter != id);
+                            None
+                        }
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+
+            /// Access to the value behind a `Mutex`. The lock is let go once
+            /// this gets dropped.
+            /// 对`Mutex`后面的值的访问权。一旦它被丢弃，锁就会被释放。
+            ///
+            /// A shared guard hands out `&T`, so it's only `Sync` when `T` is,
+            /// even though the `Mutex` itself only needs `T: Send`.
+            /// 一个共享的guard会交出`&T`，所以只有当`T`是`Sync`的时候它才是`Sync`的，即使`Mutex`本身只需要`T: Send`。
+            ///
+            /// ```compile_fail
+            /// use std::cell::Cell;
+            /// use whorl::futures::sync::MutexGuard;
+            ///
+            /// fn is_sync<T: Sync>() {}
+            /// is_sync::<MutexGuard<'static, Cell<u8>>>();
+            /// ```
+            pub struct MutexGuard<'a, T> {
+                mutex: &'a Mutex<T>,
+                /// Whether the deadlock detector knows we hold the lock.
+                /// 死锁检测器是否知道我们持有这把锁。
+                tracked: bool,
+                /// Keeps us from being `Sync` just because `Mutex<T>` is.
+                /// 让我们不会仅仅因为`Mutex<T>`是`Sync`的就也是`Sync`的。
+                _not_sync: PhantomData<*const ()>,
+            }
+
+            // Moving the guard to another thread moves the access to `T` with
+            // it, which the `Mutex` already allows for any `T: Send`. Sharing
+            // it shares `&T`, which needs `T: Sync`.
+            // 把guard移动到另一个线程会把对`T`的访问权一起移过去，而`Mutex`已经对任何`T: Send`允许这样做了。
+            // 共享它则会共享`&T`，这需要`T: Sync`。
+            unsafe impl<T: Send> Send for MutexGuard<'_, T> {}
+            unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
+
+            impl<T> Deref for MutexGuard<'_, T> {
+                type Target = T;
+                fn deref(&self) -> &T {
+                    // SAFETY: Holding the guard means we hold the lock.
+                    // SAFETY：持有guard就意味着我们持有锁。
+                    unsafe { &*self.mutex.value.get() }
+                }
+            }
+
+            impl<T> DerefMut for MutexGuard<'_, T> {
+                fn deref_mut(&mut self) -> &mut T {
+                    // SAFETY: Holding the guard means we hold the lock.
+                    // SAFETY：持有guard就意味着我们持有锁。
+                    unsafe { &mut *self.mutex.value.get() }
+                }
+            }
+
+            impl<T> Drop for MutexGuard<'_, T> {
+                fn drop(&mut self) {
+                    // This has to come first, or whoever we hand the lock to
+                    // could be marked as holding it before we take ourselves
+                    // off.
+                    // 这必须先做，否则我们把锁交给的那个人可能会在我们把自己移除之前就被标记为持有它。
+                    if self.tracked {
+                        runtime::lock_released(self.mutex.address());
+                    }
+                    self.mutex.unlock();
+                }
+            }
+
+            #[test]
+            /// A fair lock should be handed out in the order it was asked for,
+            /// even to a task that only shows up right as it's let go, while a
+            /// barging lock lets that task jump the line.
+            /// 一把公平锁应该按照请求的顺序被分配，即使是对一个恰好在锁被释放时才出现的任务；而一把抢占锁会让这个任务插队。
+            fn fair_mutex_hands_out_the_lock_in_order() {
+                use crate::runtime::{block_on_timeout, Builder};
+                use std::sync::Arc;
+
+                fn order(mutex: Mutex<Vec<usize>>) -> Vec<usize> {
+                    let runtime = Builder::new().worker_threads(0).build();
+                    let mutex = Arc::new(mutex);
+                    let guard = block_on_timeout(1000, mutex.lock()).unwrap();
+                    let spawn = |i| {
+                        let mutex = mutex.clone();
+                        runtime.spawn(async move { mutex.lock().await.push(i) });
+                    };
+                    (0..5).for_each(spawn);
+                    while runtime.tick() {}
+                    drop(guard);
+                    spawn(99);
+                    while runtime.tick() {}
+                    let order = block_on_timeout(1000, mutex.lock()).unwrap().clone();
+                    order
+                }
+
+                assert_eq!(order(Mutex::new_fair(Vec::new())), [0, 1, 2, 3, 4, 99]);
+                assert_eq!(order(Mutex::new(Vec::new())), [0, 99, 1, 2, 3, 4]);
+            }
+
+            #[test]
+            /// `try_lock` should take a free lock, give up right away on a held
+            /// one, and leave whoever is waiting in line alone.
+            /// `try_lock`应该拿走一把空闲的锁，对一把被持有的锁马上放弃，并且不去动正在排队的人。
+            fn try_lock_never_waits() {
+                use crate::runtime::Builder;
+                use std::sync::Arc;
+
+                let mutex = Arc::new(Mutex::new_fair(0));
+                let guard = mutex.try_lock().unwrap();
+                assert!(mutex.try_lock().is_none());
+
+                let runtime = Builder::new().worker_threads(0).build();
+                let waiter = runtime.spawn({
+                    let mutex = mutex.clone();
+                    async move { *mutex.lock().await += 1 }
+                });
+                while runtime.tick() {}
+                assert!(mutex.try_lock().is_none());
+                assert_eq!(mutex.state.lock().unwrap().waiters.len(), 1);
+                // Letting go hands it to the waiter, so it still isn't free.
+                // 放手会把它交给那个等待者，所以它仍然不是空闲的。
+                drop(guard);
+                assert!(mutex.try_lock().is_none());
+                while runtime.tick() {}
+                assert!(waiter.is_finished());
+                assert_eq!(*mutex.try_lock().unwrap(), 1);
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            /// Two tasks that take two locks in opposite orders should get
+            /// stuck waiting on each other, and the observer should be told.
+            /// 两个以相反顺序拿两把锁的任务应该会卡在互相等待上，而观察者应该被告知。
+            fn deadlocks_are_reported() {
+                use crate::{
+                    futures::Sleep,
+                    runtime::{Builder, Observer, TaskId},
+                };
+                use std::{
+                    sync::{mpsc, Arc, Mutex as StdMutex},
+                    time::Duration,
+                };
+
+                struct Cycles(StdMutex<mpsc::Sender<Vec<TaskId>>>);
+
+                impl Observer for Cycles {
+                    fn on_deadlock(&self, cycle: &[TaskId]) {
+                        let _ = self.0.lock().unwrap().send(cycle.to_vec());
+                    }
+                }
+
+                let (tx, rx) = mpsc::channel();
+                let runtime = Builder::new()
+                    .detect_deadlocks()
+                    .observer(Cycles(StdMutex::new(tx)))
+                    .build();
+                let a = Arc::new(Mutex::new(()));
+                let b = Arc::new(Mutex::new(()));
+                let lock_both = |first: Arc<Mutex<()>>, second: Arc<Mutex<()>>| async move {
+                    let _first = first.lock().await;
+                    Sleep::new(20).await;
+                    let _second = second.lock().await;
+                };
+                let one = runtime.spawn(lock_both(a.clone(), b.clone()));
+                let two = runtime.spawn(lock_both(b.clone(), a.clone()));
+                let cycle = rx.recv_timeout(Duration::from_secs(30)).unwrap();
+                assert_eq!(cycle.len(), 2);
+                assert_ne!(cycle[0], cycle[1]);
+                assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+                one.abort();
+                two.abort();
+                assert!(a.try_lock().is_some() && b.try_lock().is_some());
+            }
+        }
+
+        pub mod mpsc {
+            //! A multi-producer, single-consumer channel. Any number of
+            //! [`Sender`]s can push values in and a single [`Receiver`] takes
+            //! them out in the order they were sent. The channel is unbounded so
+            //! sending never has to wait.
+            //! 一个多生产者、单消费者的通道。任意数量的[`Sender`]都可以推入值，而唯一的[`Receiver`]按照发送的顺序取出它们。
+            //! 这个通道是无界的，所以发送永远不需要等待。
+            use crate::futures::Sleep;
+            use std::{
+                collections::VecDeque,
+                error::Error,
+                fmt,
+                future::{poll_fn, Future, IntoFuture},
+                pin::Pin,
+                sync::{Arc, Mutex},
+                task::{Context, Poll, Waker},
+            };
+
+            /// The state shared between both halves of the channel.
+            /// 通道两端共享的状态。
+            struct Chan<T> {
+                /// Values that were sent but not received yet.
+                /// 已经发送但还没有被接收的值。
+                queue: VecDeque<T>,
+                /// How many `Sender`s are still around. Once this hits zero
+                /// nothing new can ever show up.
+                /// 还有多少个`Sender`存在。一旦它变成零，就再也不会有新的值出现了。
+                senders: usize,
+                /// Whether the `Receiver` is still around to take values out.
+                /// `Receiver`是否还在，可以取出值。
+                receiver: bool,
+                /// The `Waker` of the task parked on `recv`, if there is one.
+                /// 挂起在`recv`上的任务的`Waker`，如果有的话。
+                waker: Option<Waker>,
+            }
+
+            /// The sending half of the channel. Clone it to have more producers.
+            /// 通道的发送端。克隆它就可以有更多的生产者。
+            pub struct Sender<T> {
+                chan: Arc<Mutex<Chan<T>>>,
+            }
+
+            /// The receiving half of the channel.
+            /// 通道的接收端。
+            pub struct Receiver<T> {
+                chan: Arc<Mutex<Chan<T>>>,
+            }
+
+            /// Returned by [`Sender::send`] when the `Receiver` is gone. It
+            /// gives you back the value that couldn't be sent.
+            /// 当`Receiver`已经不存在时由[`Sender::send`]返回。它会把没能发送出去的值还给你。
+            #[derive(Debug, PartialEq, Eq)]
+            pub struct SendError<T>(pub T);
+
+            impl<T> fmt::Display for SendError<T> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "sending on a channel whose receiver was dropped")
+                }
+            }
+
+            impl<T: fmt::Debug> Error for SendError<T> {}
+
+            /// Creates a new channel, handing back both of its halves.
+            /// 创建一个新的通道，并返回它的两端。
+            pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+                let chan = Arc::new(Mutex::new(Chan {
+                    queue: VecDeque::new(),
+                    senders: 1,
+                    receiver: true,
+                    waker: None,
+                }));
+                (Sender { chan: chan.clone() }, Receiver { chan })
+            }
+
+            impl<T> Sender<T> {
+                /// Sends a value down the channel, waking the `Receiver` if it
+                /// was waiting for one.
+                /// 向通道发送一个值，如果`Receiver`正在等待，就唤醒它。
+                pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+                    let waker = {
+                        let mut chan = self.chan.lock().unwrap();
+                        if !chan.receiver {
+                            return Err(SendError(value));
+                        }
+                        chan.queue.push_back(value);
+                        chan.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                    Ok(())
+                }
+            }
+
+            impl<T> Clone for Sender<T> {
+                fn clone(&self) -> Self {
+                    self.chan.lock().unwrap().senders += 1;
+                    Self {
+                        chan: self.chan.clone(),
+                    }
+                }
+            }
+
+            /// The last `Sender` going away is just as important to the
+            /// `Receiver` as a new value showing up: it means `recv` can
+            /// finally return `None`. If the `Receiver` is parked we have to
+            /// wake it up or it would sit there waiting forever for a value
+            /// that can never come. Since `recv` checks the sender count and
+            /// stores its `Waker` while holding the same lock that we take
+            /// here, there's no window where it can miss this wake up.
+            /// 最后一个`Sender`的离开对`Receiver`来说和一个新值的出现一样重要：它意味着`recv`终于可以返回`None`了。
+            /// 如果`Receiver`处于挂起状态，我们必须唤醒它，否则它会一直等待一个永远不会到来的值。
+            /// 因为`recv`是在持有我们这里获取的同一把锁的情况下检查发送者数量并保存它的`Waker`的，所以不存在它会错过这次唤醒的窗口。
+            impl<T> Drop for Sender<T> {
+                fn drop(&mut self) {
+                    let waker = {
+                        let mut chan = self.chan.lock().unwrap();
+                        chan.senders -= 1;
+                        if chan.senders == 0 {
+                            chan.waker.take()
+                        } else {
+                            None
+                        }
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+
+            impl<T> Receiver<T> {
+                /// Waits for the next value. Resolves to `None` once every
+                /// `Sender` has been dropped and nothing is left in the channel.
+                /// 等待下一个值。一旦所有的`Sender`都被丢弃并且通道中什么都不剩了，就返回`None`。
+                pub fn recv(&mut self) -> Recv<'_, T> {
+                    Recv { receiver: self }
+                }
+
+                /// The poll function behind `recv`, for when you're writing a
+                /// `Future` by hand and want to receive from inside of it.
+                /// `recv`背后的poll函数，用于手写`Future`并想要在其中接收值的情况。
+                pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+                    let mut chan = self.chan.lock().unwrap();
+                    if let Some(value) = chan.queue.pop_front() {
+                        Poll::Ready(Some(value))
+                    } else if chan.senders == 0 {
+                        Poll::Ready(None)
+                    } else {
+                        chan.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+
+                /// Collects up to `max` values, waiting at most `ms`
+                /// milliseconds for them, which is handy for handling things
+                /// in batches. Whatever showed up by the time we run out of
+                /// time is what you get, so the batch might be smaller than
+                /// `max` or even empty. We also stop early if every `Sender`
+                /// is gone, since nothing else is coming then.
+                /// 收集最多`max`个值，最多为它们等待`ms`毫秒，这对于批量处理东西很方便。时间用完的时候出现了什么你就得到什么，
+                /// 所以这一批可能比`max`少，甚至是空的。如果所有的`Sender`都没了，我们也会提前停下，因为那时不会再有别的东西来了。
+                pub async fn recv_many_timeout(&mut self, max: usize, ms: u128) -> Vec<T> {
+                    let mut sleep = Sleep::new(ms);
+                    let mut values = Vec::new();
+                    poll_fn(|cx| {
+                        while values.len() < max {
+                            match self.poll_recv(cx) {
+                                Poll::Ready(Some(value)) => values.push(value),
+                                Poll::Ready(None) => return Poll::Ready(()),
+                                Poll::Pending => break,
+                            }
+                        }
+                        if values.len() == max {
+                            return Poll::Ready(());
+                        }
+                        Pin::new(&mut sleep).poll(cx)
+                    })
+                    .await;
+                    values
+                }
+            }
+
+            /// Once the `Receiver` is gone nobody can take anything out, so we
+            /// let `send` know and drop whatever was left over. We drop those
+            /// values after letting go of the lock in case dropping one of them
+            /// tries to use the channel.
+            /// 一旦`Receiver`不存在了，就没有人能取出任何东西，所以我们通知`send`并丢弃剩下的值。
+            /// 我们在释放锁之后才丢弃这些值，以防丢弃其中某个值时会尝试使用这个通道。
+            impl<T> Drop for Receiver<T> {
+                fn drop(&mut self) {
+                    let leftover = {
+                        let mut chan = self.chan.lock().unwrap();
+                        chan.receiver = false;
+                        std::mem::take(&mut chan.queue)
+                    };
+                    drop(leftover);
+                }
+            }
+
+            /// The future returned by [`Receiver::recv`].
+            /// [`Receiver::recv`]返回的future。
+            pub struct Recv<'a, T> {
+                receiver: &'a mut Receiver<T>,
+            }
+
+            impl<T> Future for Recv<'_, T> {
+                type Output = Option<T>;
+                fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                    self.receiver.poll_recv(cx)
+                }
+            }
+
+            /// Awaiting `&mut rx` receives the next value, the same as `recv`.
+            /// await `&mut rx`会接收下一个值，和`recv`一样。
+            impl<'a, T> IntoFuture for &'a mut Receiver<T> {
+                type Output = Option<T>;
+                type IntoFuture = Recv<'a, T>;
+                fn into_future(self) -> Recv<'a, T> {
+                    self.recv()
+                }
+            }
+
+            #[test]
+            /// Dropping the only `Sender` while the `Receiver` is parked in
+            /// `recv` has to wake it up with `None`.
+            /// 在`Receiver`挂起在`recv`中时丢弃唯一的`Sender`，必须用`None`唤醒它。
+            fn recv_sees_none_when_senders_drop() {
+                use std::{
+                    pin::pin,
+                    sync::{
+                        atomic::{AtomicUsize, Ordering},
+                        Arc,
+                    },
+                    task::{Context, Poll, Wake, Waker},
+                };
+
+                struct Wakes(AtomicUsize);
+
+                impl Wake for Wakes {
+                    fn wake(self: Arc<Self>) {
+                        self.0.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+
+                let wakes = Arc::new(Wakes(AtomicUsize::new(0)));
+                let waker = Waker::from(wakes.clone());
+                let mut cx = Context::from_waker(&waker);
+                let (tx, mut rx) = channel();
+                tx.send(1).unwrap();
+                assert_eq!(pin!(rx.recv()).poll(&mut cx), Poll::Ready(Some(1)));
+                // Polling once with nothing to receive is what parks us, so
+                // there's no guessing about whether it has happened yet.
+                // 在没有东西可以接收的时候poll一次就是让我们挂起的方式，所以不用去猜它是否已经发生了。
+                let mut recv = pin!(rx.recv());
+                assert!(recv.as_mut().poll(&mut cx).is_pending());
+                assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+                drop(tx);
+                assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+                assert_eq!(recv.poll(&mut cx), Poll::Ready(None));
+            }
+
+            #[test]
+            /// A oneshot `Receiver` should be awaitable as it is and after
+            /// `into_future`, and awaiting `&mut` an mpsc `Receiver` should
+            /// receive from it.
+            /// 一个oneshot `Receiver`应该既可以原样被await，也可以在`into_future`之后被await，而await一个mpsc `Receiver`的`&mut`应该从它接收值。
+            fn receivers_can_be_awaited_directly() {
+                use crate::futures::{sync::oneshot, IntoFuture};
+                use crate::runtime::Builder;
+
+                let runtime = Builder::new().build();
+                let received = runtime.block_on(async {
+                    let (direct_tx, direct_rx) = oneshot::channel();
+                    let (converted_tx, converted_rx) = oneshot::channel();
+                    let (tx, mut rx) = channel();
+                    direct_tx.send(1).unwrap();
+                    converted_tx.send(2).unwrap();
+                    tx.send(3).unwrap();
+                    drop(tx);
+                    (
+                        direct_rx.await,
+                        converted_rx.into_future().await,
+                        (&mut rx).await,
+                        (&mut rx).await,
+                    )
+                });
+                assert_eq!(received, (Ok(1), Ok(2), Some(3), None));
+            }
+
+            #[test]
+            /// `recv_many_timeout` should stop at `max` values when they're
+            /// all there already, and otherwise hand back however many showed
+            /// up before the time ran out.
+            /// 当值已经全部在那里时，`recv_many_timeout`应该在`max`个值处停下，否则交还在时间用完之前出现的那么多值。
+            fn recv_many_timeout_returns_a_partial_batch() {
+                use crate::runtime;
+                use std::{
+                    thread,
+                    time::{Duration, Instant},
+                };
+
+                let (tx, mut rx) = channel();
+                for i in 0..5 {
+                    tx.send(i).unwrap();
+                }
+                let start = Instant::now();
+                let batch = runtime::block_on(rx.recv_many_timeout(3, 5000));
+                assert_eq!(batch, [0, 1, 2]);
+                assert!(start.elapsed() < Duration::from_secs(1));
+
+                let (tx, mut rx) = channel();
+                thread::spawn(move || {
+                    for i in 0..10 {
+                        if tx.send(i).is_err() {
+                            break;
+                        }
+                        thread::sleep(Duration::from_millis(40));
+                    }
+                });
+                let batch = runtime::block_on(rx.recv_many_timeout(10, 100));
+                assert!(!batch.is_empty() && batch.len() < 10, "{batch:?}");
+                assert_eq!(batch, (0..batch.len()).collect::<Vec<_>>());
+            }
+        }
+
+        pub mod oneshot {
+            //! A channel for sending a single value, once. It's the smallest
+            //! way to hand a result from one place, like another thread, over
+            //! to a task that's waiting on it.
+            //! 一个只发送一个值、只发送一次的通道。它是把一个结果从一个地方，比如另一个线程，交给正在等待它的任务的最小方式。
+            use std::{
+                error::Error,
+                fmt,
+                future::Future,
+                pin::Pin,
+                sync::{Arc, Mutex},
+                task::{Context, Poll, Waker},
+            };
+
+            struct Inner<T> {
+                value: Option<T>,
+                /// Set once the `Sender` is gone, whether it sent something or
+                /// not.
+                /// 一旦`Sender`消失就会被设置，无论它是否发送过东西。
+                closed: bool,
+                waker: Option<Waker>,
+            }
+
+            /// Sends the value.
+            /// 发送值的一端。
+            pub struct Sender<T> {
+                inner: Arc<Mutex<Inner<T>>>,
+            }
+
+            /// Awaiting this gives you the value, or a `RecvError` if the
+            /// `Sender` was dropped without sending one.
+            /// await它会给你那个值，或者如果`Sender`没有发送就被丢弃了，会给你一个`RecvError`。
+            pub struct Receiver<T> {
+                inner: Arc<Mutex<Inner<T>>>,
+            }
+
+            /// The `Sender` went away without sending anything.
+            /// `Sender`没有发送任何东西就消失了。
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct RecvError;
+
+            impl fmt::Display for RecvError {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "sender dropped without sending a value")
+                }
+            }
+
+            impl Error for RecvError {}
+
+            /// Creates a new oneshot channel.
+            /// 创建一个新的oneshot通道。
+            pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+                let inner = Arc::new(Mutex::new(Inner {
+                    value: None,
+                    closed: false,
+                    waker: None,
+                }));
+                (
+                    Sender {
+                        inner: inner.clone(),
+                    },
+                    Receiver { inner },
+                )
+            }
+
+            impl<T> Sender<T> {
+                /// Sends `value` over. If the `Receiver` is already gone you
+                /// get the value back.
+                /// 把`value`发送过去。如果`Receiver`已经不在了，你会拿回这个值。
+                pub fn send(self, value: T) -> Result<(), T> {
+                    if Arc::strong_count(&self.inner) == 1 {
+                        return Err(value);
+                    }
+                    self.inner.lock().unwrap().value = Some(value);
+                    Ok(())
+                }
+            }
+
+            /// The `Receiver` gets woken up once the `Sender` is gone, which also
+            /// covers the `send` case since that consumes the `Sender`.
+            /// 一旦`Sender`消失，`Receiver`就会被唤醒，这也包括了`send`的情况，因为它会消耗掉`Sender`。
+            impl<T> Drop for Sender<T> {
+                fn drop(&mut self) {
+                    let waker = {
+                        let mut inner = self.inner.lock().unwrap();
+                        inner.closed = true;
+                        inner.waker.take()
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            }
+
+            impl<T> Future for Receiver<T> {
+                type Output = Result<T, RecvError>;
+                fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                    let mut inner = self.inner.lock().unwrap();
+                    if let Some(value) = inner.value.take() {
+                        Poll::Ready(Ok(value))
+                    } else if inner.closed {
+                        Poll::Ready(Err(RecvError))
+                    } else {
+                        inner.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+
+        #[test]
+        /// The latch should let its waiter go once, and only after the last
+        /// `count_down`.
+        /// 门闩应该只放行等待者一次，而且只在最后一次`count_down`之后。
+        fn latch_opens_after_the_last_count_down() {
+            use crate::runtime;
+            use std::{sync::mpsc, thread, time::Duration};
+
+            let runtime = runtime::Builder::new().build();
+            let latch = CountDownLatch::new(3);
+            let (tx, rx) = mpsc::channel();
+            let waiting = latch.clone();
+            runtime.spawn(async move {
+                waiting.wait().await;
+                tx.send(()).unwrap();
+            });
+            for _ in 0..2 {
+                latch.count_down();
+                thread::sleep(Duration::from_millis(20));
+                assert!(rx.try_recv().is_err());
+            }
+            latch.count_down();
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+            latch.count_down();
+            assert_eq!(latch.count(), 0);
+            assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+        }
+    }
+
+    pub mod flag {
+        //! Waiting on a plain `AtomicBool`. Synchronous code, like another
+        //! thread or a signal handler, can't easily get at a `Waker`, but it
+        //! can always flip a bool, so this is about the smallest bridge there
+        //! is between the two worlds.
+        //! 等待一个普通的`AtomicBool`。同步代码，比如另一个线程或者信号处理函数，不容易拿到`Waker`，
+        //! 但它总是可以翻转一个bool，所以这大概是两个世界之间最小的桥梁了。
+        use crate::runtime;
+        use std::{
+            future::Future,
+            pin::Pin,
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+            task::{Context, Poll},
+            time::{Duration, Instant},
+        };
+
+        /// How long we wait before checking the flag again.
+        /// 我们在再次检查标志之前等待多久。
+        const RECHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+        /// A future that completes once the `AtomicBool` it was given becomes
+        /// `true`. Whoever flips the bool has no idea we're waiting on it, so
+        /// there's nobody to call `wake` for us. Instead, every time we find
+        /// the flag still unset, we ask the runtime's timer to wake us up again
+        /// shortly and take another look.
+        /// 一个在给定的`AtomicBool`变为`true`时完成的future。翻转这个bool的人并不知道我们在等待它，
+        /// 所以没有人会替我们调用`wake`。所以每次我们发现标志还没有被设置时，就让运行时的定时器稍后再唤醒我们，再看一眼。
+        pub struct WaitFor {
+            flag: Arc<AtomicBool>,
+            /// The timer that will wake us up to check the flag again.
+            /// 将会唤醒我们再次检查标志的定时器。
+            timer: Option<runtime::TimerEntry>,
+        }
+
+        impl WaitFor {
+            /// Waits for `flag` to become `true`.
+            /// 等待`flag`变为`true`。
+            pub fn new(flag: Arc<AtomicBool>) -> Self {
+                Self { flag, timer: None }
+            }
+        }
+
+        impl Future for WaitFor {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                if self.flag.load(Ordering::Acquire) {
+                    self.timer = None;
+                    return Poll::Ready(());
+                }
+                // We set up the wake up call first and only then look at the
+                // flag again. If it got flipped right after the check above
+                // we'll see it here, and if it gets flipped after this one the
+                // timer is already on its way to wake us. Either way we can't
+                // miss it and sleep forever.
+                // 我们先安排好唤醒，然后才再看一次标志。如果它恰好在上面的检查之后被翻转了，我们会在这里看到它；
+                // 如果它在这次检查之后才被翻转，定时器已经在唤醒我们的路上了。无论哪种情况，我们都不会错过它而永远睡下去。
+                let deadline = Instant::now() + RECHECK_INTERVAL;
+                self.timer = Some(runtime::register_timer(deadline, cx.waker().clone()));
+                if self.flag.load(Ordering::Acquire) {
+                    self.timer = None;
+                    return Poll::Ready(());
+                }
+                Poll::Pending
+            }
+        }
+
+        #[test]
+        /// Flipping the flag from another thread has to wake up the task
+        /// that's waiting on it.
+        /// 从另一个线程翻转标志必须唤醒正在等待它的任务。
+        fn flipping_the_flag_wakes_the_task() {
+            use std::{sync::mpsc, thread};
+
+            let runtime = runtime::Builder::new().build();
+            let flag = Arc::new(AtomicBool::new(false));
+            let (tx, rx) = mpsc::channel();
+            let wait = WaitFor::new(flag.clone());
+            runtime.spawn(async move {
+                wait.await;
+                tx.send(()).unwrap();
+            });
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                flag.store(true, Ordering::Release);
+            });
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        }
+    }
+
+    pub mod close {
+        //! Rust doesn't have an async `Drop`. When a value goes away, `drop`
+        //! runs right then and there and it can't `await` anything, so there's
+        //! no way to, say, flush a buffer over the network or tell the other
+        //! side of a connection goodbye. The usual way around it is a plain old
+        //! method: you call `close().await` yourself before letting go of the
+        //! value, and `drop` only cleans up whatever it can without waiting.
+        //!
+        //! The catch is that nothing makes you call it. So resources can hold
+        //! on to a [`CloseGuard`] from [`defer_close`], tell it when they've
+        //! been closed, and if they get dropped without that, the guard lets
+        //! the runtime's `Observer` know so that the mistake doesn't go silent.
+        //! Rust没有异步的`Drop`。当一个值消失时，`drop`会立刻就地运行，它不能`await`任何东西，
+        //! 所以没有办法，比如说，通过网络刷新一个缓冲区，或者跟连接的另一端说再见。通常的解决办法是一个普通的方法：
+        //! 你在放开这个值之前自己调用`close().await`，而`drop`只清理那些不需要等待就能清理的东西。
+        //!
+        //! 问题在于没有什么会强迫你调用它。所以资源可以持有一个来自[`defer_close`]的[`CloseGuard`]，
+        //! 在它们被关闭时告诉它，如果它们没有被关闭就被丢弃了，这个guard就会让运行时的`Observer`知道，这样这个错误就不会被悄无声息地忽略。
+        use crate::runtime;
+        use std::future::Future;
+
+        /// Something that needs to do some async work before it goes away.
+        /// 在消失之前需要做一些异步工作的东西。
+        pub trait AsyncClose {
+            /// Does the cleanup, consuming the resource.
+            /// 进行清理，消耗掉这个资源。
+            fn close(self) -> impl Future<Output = ()> + Send;
+        }
+
+        /// Watches over a resource and complains to the `Observer` if it's
+        /// dropped before `close` gets called on the guard.
+        /// 看守一个资源，如果它在guard上的`close`被调用之前就被丢弃了，就向`Observer`抱怨。
+        #[derive(Debug)]
+        pub struct CloseGuard {
+            resource: &'static str,
+            closed: bool,
+        }
+
+        /// Creates a `CloseGuard` for a resource called `resource`.
+        /// 为一个名叫`resource`的资源创建一个`CloseGuard`。
+        pub fn defer_close(resource: &'static str) -> CloseGuard {
+            CloseGuard {
+                resource,
+                closed: false,
+            }
+        }
+
+        impl CloseGuard {
+            /// Marks the resource as properly closed.
+            /// 把资源标记为已经被正确关闭。
+            pub fn close(mut self) {
+                self.closed = true;
+            }
+        }
+
+        impl Drop for CloseGuard {
+            fn drop(&mut self) {
+                if !self.closed {
+                    runtime::observe(|observer| observer.on_unclosed(self.resource));
+                }
+            }
+        }
+
+        #[test]
+        /// Dropping a resource without closing it should be reported, and
+        /// closing it first shouldn't be.
+        /// 不关闭就丢弃一个资源应该被报告，而先关闭它就不应该被报告。
+        fn unclosed_resources_are_reported() {
+            use crate::runtime::Observer;
+            use std::{
+                sync::{mpsc, Arc, Mutex},
+                time::Duration,
+            };
+
+            struct Connection {
+                guard: CloseGuard,
+            }
+
+            impl AsyncClose for Connection {
+                async fn close(self) {
+                    crate::futures::Sleep::new(1).await;
+                    self.guard.close();
+                }
+            }
+
+            struct Unclosed(Arc<Mutex<Vec<String>>>);
+
+            impl Observer for Unclosed {
+                fn on_unclosed(&self, resource: &str) {
+                    self.0.lock().unwrap().push(resource.to_owned());
+                }
+            }
+
+            let unclosed = Arc::new(Mutex::new(Vec::new()));
+            let runtime = runtime::Builder::new()
+                .observer(Unclosed(unclosed.clone()))
+                .build();
+            let (tx, rx) = mpsc::channel();
+            runtime.spawn(async move {
+                let closed = Connection {
+                    guard: defer_close("closed"),
+                };
+                let dropped = Connection {
+                    guard: defer_close("dropped"),
+                };
+                closed.close().await;
+                drop(dropped);
+                tx.send(()).unwrap();
+            });
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+            assert_eq!(*unclosed.lock().unwrap(), ["dropped"]);
+        }
+    }
+
+    pub mod stream {
+        //! Streams are the async version of iterators. An `Iterator` hands
+        //! back its next item right away, but a `Stream` might not have one
+        //! yet, so just like a `Future` it can say `Pending` and wake us up
+        //! once it does.
+        //! 流是迭代器的异步版本。`Iterator`会立刻交出它的下一个元素，但是`Stream`可能还没有，
+        //! 所以就像`Future`一样，它可以返回`Pending`，并在有了之后唤醒我们。
+        use super::{Elapsed, Sleep};
+        use crate::runtime;
+        use std::{
+            collections::VecDeque,
+            future::Future,
+            ops::DerefMut,
+            pin::Pin,
+            sync::{
+                mpsc::{Receiver, TryRecvError},
+                Mutex,
+            },
+            task::{ready, Context, Poll},
+            time::{Duration, Instant},
+        };
+
+        /// How long we wait before trying a `std` receiver again.
+        /// 我们在再次尝试`std`接收端之前等待多久。
+        const RETRY_INTERVAL: Duration = Duration::from_millis(1);
+
+        /// A series of values that show up over time.
+        /// 一系列随着时间出现的值。
+        pub trait Stream {
+            /// The type of the values the stream hands back.
+            /// 流交出的值的类型。
+            type Item;
+
+            /// Tries to get the next value. `Ready(None)` means the stream is
+            /// done and there's nothing more coming.
+            /// 尝试获取下一个值。`Ready(None)`意味着这个流结束了，不会再有东西了。
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+
+            /// A `Future` for the next value in the stream.
+            /// 流中下一个值的`Future`。
+            fn next(&mut self) -> Next<'_, Self>
+            where
+                Self: Unpin + Sized,
+            {
+                Next { stream: self }
+            }
+
+            /// Gives up on the stream if it goes quiet for longer than `idle`.
+            /// Every item that does show up in time comes out as `Ok` and
+            /// starts the wait over. If the wait runs out first we hand back a
+            /// single `Err(Elapsed)` and then end the stream, so that a stream
+            /// that ended on its own, which just ends, is easy to tell apart
+            /// from one we gave up on. This is what a server wants for an idle
+            /// connection.
+            /// 如果流安静的时间超过了`idle`，就放弃它。每个及时出现的元素都会以`Ok`的形式出来，并让等待重新开始。
+            /// 如果等待先用完了，我们就交出一个`Err(Elapsed)`然后结束这个流，这样自己结束的流（它只是结束了）
+            /// 和我们放弃了的流就很容易区分开。这正是服务器对一个空闲连接想要的。
+            fn timeout_idle(self, idle: Duration) -> TimeoutIdle<Self>
+            where
+                Self: Sized,
+            {
+                TimeoutIdle {
+                    stream: self,
+                    idle,
+                    sleep: Sleep::new(idle.as_millis()),
+                    timed_out: false,
+                }
+            }
+
+            /// Gives every item `ms` milliseconds to show up, counting from
+            /// when we start waiting for it. Items that make it come out as
+            /// `Ok`. When one doesn't we hand back an `Err(Elapsed)` in its
+            /// place and start the wait over, but unlike `timeout_idle` the
+            /// stream keeps going, so one slow item doesn't cost you the rest.
+            /// 给每个元素`ms`毫秒的时间出现，从我们开始等它的时候算起。赶上了的元素会以`Ok`的形式出来。
+            /// 当有一个没赶上时，我们就在它的位置交出一个`Err(Elapsed)`，然后重新开始等待。但是和`timeout_idle`不同，流会继续下去，
+            /// 所以一个慢的元素不会让你失去剩下的那些。
+            fn timeout(self, ms: u128) -> Timeout<Self>
+            where
+                Self: Sized,
+            {
+                Timeout {
+                    stream: self,
+                    ms,
+                    sleep: None,
+                }
+            }
+
+            /// Maps every item through `f` and runs up to `n` of the futures
+            /// it hands back at the same time, but still hands back their
+            /// outputs in the same order the items came in. A future that
+            /// finishes early just waits in line until everything before it
+            /// is done. This is what you want for, say, fetching a bunch of
+            /// URLs at once while still printing them in order.
+            /// 把每个元素都通过`f`映射，并且同时运行最多`n`个它返回的future，但是仍然按照元素进来的顺序交出它们的输出。
+            /// 一个提前完成的future只会排队等待，直到它前面的所有东西都完成了。比如说，当你想一次获取一堆URL，
+            /// 但仍然按顺序打印它们的时候，这就是你想要的。
+            fn buffered<F, Fut>(self, n: usize, f: F) -> Buffered<Self, F, Fut>
+            where
+                Self: Sized,
+                F: FnMut(Self::Item) -> Fut,
+                Fut: Future,
+            {
+                assert!(n > 0, "buffered needs room for at least one future");
+                Buffered {
+                    stream: Some(self),
+                    f,
+                    n,
+                    in_flight: VecDeque::new(),
+                }
+            }
+
+            /// Hands back at most the first `n` items and then ends.
+            /// 最多交出前`n`个元素，然后结束。
+            fn take(self, n: usize) -> Take<Self>
+            where
+                Self: Sized,
+            {
+                Take {
+                    stream: self,
+                    left: n,
+                }
+            }
+
+            /// Throws away the first `n` items and hands back the rest.
+            /// 丢掉前`n`个元素，交出剩下的。
+            fn skip(self, n: usize) -> Skip<Self>
+            where
+                Self: Sized,
+            {
+                Skip {
+                    stream: self,
+                    left: n,
+                }
+            }
+
+            /// Hands back items for as long as `predicate` says yes, and ends
+            /// at the first one it says no to. That one is dropped.
+            /// 只要`predicate`说是，就交出元素，在它第一次说不的那个元素处结束。那个元素会被丢弃。
+            fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P>
+            where
+                Self: Sized,
+                P: FnMut(&Self::Item) -> bool,
+            {
+                TakeWhile {
+                    stream: self,
+                    predicate,
+                    done: false,
+                }
+            }
+
+            /// Gathers items up into batches of `n`, which is handy for writing
+            /// them out all at once. Whatever's left over when the stream ends
+            /// comes out as one last, smaller batch. A stream that never had
+            /// anything doesn't hand back an empty batch, it just ends.
+            /// 把元素收集成`n`个一批，这对于一次把它们全部写出去很方便。流结束时剩下的任何东西会作为最后一个更小的批次出来。
+            /// 一个从来没有任何东西的流不会交出一个空的批次，它只是结束了。
+            fn chunks(self, n: usize) -> Chunks<Self>
+            where
+                Self: Sized,
+            {
+                assert!(n > 0, "chunks have to hold at least one item");
+                Chunks {
+                    stream: self,
+                    n,
+                    chunk: Vec::new(),
+                    done: false,
+                }
+            }
+
+            /// Runs every item through `f` together with everything so far,
+            /// starting from `init`, and hands back where it ended up once
+            /// the stream is done. It's `Iterator::fold`, except that `f`
+            /// hands back a future, so each step gets to await things. We only
+            /// ever run one step at a time, since each one needs the last
+            /// one's result.
+            /// 从`init`开始，把每个元素和到目前为止的所有东西一起交给`f`，在流结束后交出最终的结果。
+            /// 它就是`Iterator::fold`，只不过`f`返回的是一个future，所以每一步都可以await东西。我们每次只运行一步，因为每一步都需要上一步的结果。
+            fn fold<Acc, F, Fut>(self, init: Acc, f: F) -> Fold<Self, F, Fut, Acc>
+            where
+                Self: Sized,
+                F: FnMut(Acc, Self::Item) -> Fut,
+                Fut: Future<Output = Acc>,
+            {
+                Fold {
+                    stream: self,
+                    f,
+                    acc: Some(init),
+                    step: None,
+                }
+            }
+
+            /// Turns a stream of streams into one stream, by handing back
+            /// everything from the first inner stream, then everything from
+            /// the next one, and so on. It ends once the outer stream does.
+            /// 把一个由流组成的流变成一个流，先交出第一个内部流的所有东西，然后是下一个的所有东西，以此类推。它在外层流结束时结束。
+            fn flatten(self) -> Flatten<Self>
+            where
+                Self: Sized,
+                Self::Item: Stream,
+            {
+                Flatten {
+                    stream: self,
+                    inner: None,
+                }
+            }
+
+            /// Hands every item to `f` along with some state that sticks
+            /// around between items, starting at `init`, and hands back
+            /// whatever `f` does. That's a `map` that remembers things, like
+            /// a running total. Once `f` hands back `None` the stream ends,
+            /// even if there were items left.
+            /// 把每个元素和一些在元素之间保留下来的状态（从`init`开始）一起交给`f`，并交出`f`返回的东西。
+            /// 这就是一个能记住东西的`map`，比如一个累计的总数。一旦`f`返回`None`，流就结束了，即使还剩下有元素。
+            fn scan<State, B, F>(self, init: State, f: F) -> Scan<Self, State, F>
+            where
+                Self: Sized,
+                F: FnMut(&mut State, Self::Item) -> Option<B>,
+            {
+                Scan {
+                    stream: self,
+                    state: init,
+                    f,
+                    done: false,
+                }
+            }
+
+            /// Lets you look at the next item with `Peekable::peek` before
+            /// deciding whether to take it, which is the lookahead a parser
+            /// needs.
+            /// 让你可以在决定是否拿走下一个元素之前先用`Peekable::peek`看看它，这就是一个解析器所需要的向前看。
+            fn peekable(self) -> Peekable<Self>
+            where
+                Self: Sized,
+            {
+                Peekable {
+                    stream: self,
+                    peeked: None,
+                }
+            }
+        }
+
+        /// A stream of everything in an iterator, each item ready right away.
+        /// 一个由迭代器中的所有东西组成的流，每个元素都是立刻就绪的。
+        pub fn iter<I: IntoIterator>(iter: I) -> Iter<I::IntoIter> {
+            Iter {
+                iter: iter.into_iter(),
+            }
+        }
+
+        /// The `Stream` returned by `iter`.
+        /// `iter`返回的`Stream`。
+        pub struct Iter<I> {
+            iter: I,
+        }
+
+        impl<I: Iterator + Unpin> Stream for Iter<I> {
+            type Item = I::Item;
+            fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+                Poll::Ready(self.iter.next())
+            }
+        }
+
+        /// Hands back items from both `a` and `b` as soon as either has one,
+        /// and ends once both of them have. We take turns asking which one
+        /// goes first, so a stream that always has something ready can't
+        /// starve the other one.
+        /// 只要`a`和`b`中任何一个有元素就交出来，当它们两个都结束了才结束。我们轮流决定先问哪一个，
+        /// 这样一个总是有东西就绪的流就不会饿死另一个。
+        pub fn merge<A, B>(a: A, b: B) -> Merge<A, B>
+        where
+            A: Stream,
+            B: Stream<Item = A::Item>,
+        {
+            Merge {
+                a: Some(a),
+                b: Some(b),
+                b_first: false,
+            }
+        }
+
+        /// The `Stream` returned by `merge`.
+        /// `merge`返回的`Stream`。
+        pub struct Merge<A, B> {
+            /// `None` once that side has ended.
+            /// 一旦那一边结束了就是`None`。
+            a: Option<A>,
+            b: Option<B>,
+            b_first: bool,
+        }
+
+        /// Asks one side of a `Merge` for an item, forgetting about it if it
+        /// turns out to be done.
+        /// 向`Merge`的一边要一个元素，如果它已经结束了就把它忘掉。
+        fn poll_side<S: Stream + Unpin>(
+            side: &mut Option<S>,
+            cx: &mut Context<'_>,
+        ) -> Option<S::Item> {
+            let stream = side.as_mut()?;
+            match Pin::new(stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => Some(item),
+                Poll::Ready(None) => {
+                    *side = None;
+                    None
+                }
+                Poll::Pending => None,
+            }
+        }
+
+        impl<A, B> Stream for Merge<A, B>
+        where
+            A: Stream + Unpin,
+            B: Stream<Item = A::Item> + Unpin,
+        {
+            type Item = A::Item;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A::Item>> {
+                let this = &mut *self;
+                this.b_first = !this.b_first;
+                let item = if this.b_first {
+                    poll_side(&mut this.b, cx).or_else(|| poll_side(&mut this.a, cx))
+                } else {
+                    poll_side(&mut this.a, cx).or_else(|| poll_side(&mut this.b, cx))
+                };
+                match item {
+                    Some(item) => Poll::Ready(Some(item)),
+                    // Both sides got asked, so whichever are still around
+                    // have our waker and will call us back.
+                    // 两边都被问过了，所以仍然还在的那些都有我们的waker，会回头叫我们。
+                    None if this.a.is_none() && this.b.is_none() => Poll::Ready(None),
+                    None => Poll::Pending,
+                }
+            }
+        }
+
+        /// Pairs up items from `a` and `b`, first with first, second with
+        /// second and so on, and ends as soon as either one does. Whichever
+        /// side has its item ready first holds on to it while we wait for
+        /// the other side's.
+        /// 把`a`和`b`的元素配成对，第一个和第一个，第二个和第二个，以此类推，只要其中任何一个结束了就结束。
+        /// 哪一边先准备好它的元素，就在我们等待另一边的元素时先拿着它。
+        pub fn zip<A: Stream, B: Stream>(a: A, b: B) -> Zip<A, B> {
+            Zip {
+                a,
+                b,
+                a_item: None,
+                b_item: None,
+            }
+        }
+
+        /// The `Stream` returned by `zip`.
+        /// `zip`返回的`Stream`。
+        pub struct Zip<A: Stream, B: Stream> {
+            a: A,
+            b: B,
+            /// An item that showed up before its partner did.
+            /// 一个在它的搭档之前就出现了的元素。
+            a_item: Option<A::Item>,
+            b_item: Option<B::Item>,
+        }
+
+        // The items we hold on to only ever get moved around by value, never
+        // pinned, so they don't need to be `Unpin` for us to be.
+        // 我们拿着的元素只会被按值移来移去，从来不会被pin住，所以我们要是`Unpin`的并不需要它们是`Unpin`的。
+        impl<A: Stream + Unpin, B: Stream + Unpin> Unpin for Zip<A, B> {}
+
+        impl<A, B> Stream for Zip<A, B>
+        where
+            A: Stream + Unpin,
+            B: Stream + Unpin,
+        {
+            type Item = (A::Item, B::Item);
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let this = &mut *self;
+                if this.a_item.is_none() {
+                    match Pin::new(&mut this.a).poll_next(cx) {
+                        Poll::Ready(Some(item)) => this.a_item = Some(item),
+                        Poll::Ready(None) => return Poll::Ready(None),
+                        Poll::Pending => {}
+                    }
+                }
+                if this.b_item.is_none() {
+                    match Pin::new(&mut this.b).poll_next(cx) {
+                        Poll::Ready(Some(item)) => this.b_item = Some(item),
+                        Poll::Ready(None) => return Poll::Ready(None),
+                        Poll::Pending => {}
+                    }
+                }
+                match (this.a_item.take(), this.b_item.take()) {
+                    (Some(a), Some(b)) => Poll::Ready(Some((a, b))),
+                    // Whatever we did get stays put for next time.
+                    // 我们拿到了的东西会留到下一次。
+                    (a, b) => {
+                        this.a_item = a;
+                        this.b_item = b;
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::take`.
+        /// `Stream::take`返回的`Stream`。
+        pub struct Take<S> {
+            stream: S,
+            left: usize,
+        }
+
+        impl<S: Stream + Unpin> Stream for Take<S> {
+            type Item = S::Item;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+                // Once we've handed back all `n` we don't even ask the stream
+                // again, it might not have anything else for a long time.
+                // 一旦我们交出了全部`n`个，我们就不会再去问这个流了，它可能很长时间都不会再有别的东西。
+                if self.left == 0 {
+                    return Poll::Ready(None);
+                }
+                let item = ready!(Pin::new(&mut self.stream).poll_next(cx));
+                if item.is_some() {
+                    self.left -= 1;
+                }
+                Poll::Ready(item)
+            }
+        }
+
+        /// The `Stream` returned by `Stream::skip`.
+        /// `Stream::skip`返回的`Stream`。
+        pub struct Skip<S> {
+            stream: S,
+            left: usize,
+        }
+
+        impl<S: Stream + Unpin> Stream for Skip<S> {
+            type Item = S::Item;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+                loop {
+                    let item = ready!(Pin::new(&mut self.stream).poll_next(cx));
+                    if self.left == 0 || item.is_none() {
+                        return Poll::Ready(item);
+                    }
+                    self.left -= 1;
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::chunks`.
+        /// `Stream::chunks`返回的`Stream`。
+        pub struct Chunks<S: Stream> {
+            stream: S,
+            n: usize,
+            /// The batch we're filling up right now. It sticks around between
+            /// polls when the stream makes us wait part way through.
+            /// 我们此刻正在填充的批次。当流让我们在中途等待时，它会在两次poll之间保留下来。
+            chunk: Vec<S::Item>,
+            done: bool,
+        }
+
+        // Like with `Zip`, the items in the batch only ever get moved by
+        // value, so they don't need to be `Unpin` for us to be.
+        // 和`Zip`一样，批次中的元素只会被按值移动，所以我们要是`Unpin`的并不需要它们是`Unpin`的。
+        impl<S: Stream + Unpin> Unpin for Chunks<S> {}
+
+        impl<S: Stream + Unpin> Stream for Chunks<S> {
+            type Item = Vec<S::Item>;
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                if self.done {
+                    return Poll::Ready(None);
+                }
+                loop {
+                    match ready!(Pin::new(&mut self.stream).poll_next(cx)) {
+                        Some(item) => {
+                            self.chunk.push(item);
+                            if self.chunk.len() == self.n {
+                                let n = self.n;
+                                let chunk =
+                                    std::mem::replace(&mut self.chunk, Vec::with_capacity(n));
+                                return Poll::Ready(Some(chunk));
+                            }
+                        }
+                        None => {
+                            self.done = true;
+                            let chunk = std::mem::take(&mut self.chunk);
+                            return Poll::Ready((!chunk.is_empty()).then_some(chunk));
+                        }
+                    }
+                }
+            }
+        }
+
+        /// The `Future` returned by `Stream::fold`.
+        /// `Stream::fold`返回的`Future`。
+        pub struct Fold<S, F, Fut, Acc> {
+            stream: S,
+            f: F,
+            /// Where we're at, whenever there isn't a step running with it.
+            /// 我们当前的结果，只要没有一步正拿着它在运行。
+            acc: Option<Acc>,
+            step: Option<Pin<Box<Fut>>>,
+        }
+
+        // The step is boxed and nothing else is ever pinned.
+        // 那一步是装箱了的，其他任何东西都不会被pin住。
+        impl<S: Unpin, F, Fut, Acc> Unpin for Fold<S, F, Fut, Acc> {}
+
+        impl<S, F, Fut, Acc> Future for Fold<S, F, Fut, Acc>
+        where
+            S: Stream + Unpin,
+            F: FnMut(Acc, S::Item) -> Fut,
+            Fut: Future<Output = Acc>,
+        {
+            type Output = Acc;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Acc> {
+                let this = self.get_mut();
+                loop {
+                    if let Some(step) = this.step.as_mut() {
+                        this.acc = Some(ready!(step.as_mut().poll(cx)));
+                        this.step = None;
+                    }
+                    // The accumulator has to stay put while the stream
+                    // makes us wait, so we only take it once there's an item.
+                    // 在流让我们等待的时候累加值必须留在原地，所以我们只在有了元素之后才把它拿走。
+                    let item = ready!(Pin::new(&mut this.stream).poll_next(cx));
+                    let acc = this.acc.take().expect("fold polled after it finished");
+                    match item {
+                        Some(item) => this.step = Some(Box::pin((this.f)(acc, item))),
+                        None => return Poll::Ready(acc),
+                    }
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::flatten`.
+        /// `Stream::flatten`返回的`Stream`。
+        pub struct Flatten<S: Stream> {
+            stream: S,
+            /// The inner stream we're draining right now, if we've got one.
+            /// 我们此刻正在取空的内部流，如果有的话。
+            inner: Option<S::Item>,
+        }
+
+        impl<S> Stream for Flatten<S>
+        where
+            S: Stream + Unpin,
+            S::Item: Stream + Unpin,
+        {
+            type Item = <S::Item as Stream>::Item;
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                loop {
+                    if let Some(inner) = self.inner.as_mut() {
+                        match ready!(Pin::new(inner).poll_next(cx)) {
+                            Some(item) => return Poll::Ready(Some(item)),
+                            // That one's empty now, so on to the next.
+                            // 那一个现在空了，所以换下一个。
+                            None => self.inner = None,
+                        }
+                    }
+                    match ready!(Pin::new(&mut self.stream).poll_next(cx)) {
+                        Some(inner) => self.inner = Some(inner),
+                        None => return Poll::Ready(None),
+                    }
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::scan`.
+        /// `Stream::scan`返回的`Stream`。
+        pub struct Scan<S, State, F> {
+            stream: S,
+            state: State,
+            f: F,
+            done: bool,
+        }
+
+        impl<S, State, B, F> Stream for Scan<S, State, F>
+        where
+            S: Stream + Unpin,
+            State: Unpin,
+            F: FnMut(&mut State, S::Item) -> Option<B> + Unpin,
+        {
+            type Item = B;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<B>> {
+                if self.done {
+                    return Poll::Ready(None);
+                }
+                let this = &mut *self;
+                let output = ready!(Pin::new(&mut this.stream).poll_next(cx))
+                    .and_then(|item| (this.f)(&mut this.state, item));
+                this.done = output.is_none();
+                Poll::Ready(output)
+            }
+        }
+
+        /// The `Stream` returned by `Stream::peekable`.
+        /// `Stream::peekable`返回的`Stream`。
+        pub struct Peekable<S: Stream> {
+            stream: S,
+            /// What `peek` got out of the stream, until it's taken. It's
+            /// `Some(None)` if what we found was the end.
+            /// `peek`从流中拿到的东西，直到它被拿走。如果我们找到的是结尾，它就是`Some(None)`。
+            peeked: Option<Option<S::Item>>,
+        }
+
+        impl<S: Stream + Unpin> Peekable<S> {
+            /// Waits for the next item and lets you look at it without taking
+            /// it. We hold on to it, so the next `poll_next` hands back this
+            /// same item, and peeking again doesn't move the stream along.
+            /// 等待下一个元素，让你看看它但不拿走它。我们会保留着它，所以下一次`poll_next`会交出同一个元素，而再次peek也不会让流往前走。
+            pub async fn peek(&mut self) -> Option<&S::Item> {
+                std::future::poll_fn(|cx| {
+                    if self.peeked.is_none() {
+                        let item = ready!(Pin::new(&mut self.stream).poll_next(cx));
+                        self.peeked = Some(item);
+                    }
+                    Poll::Ready(())
+                })
+                .await;
+                self.peeked.as_ref().and_then(Option::as_ref)
+            }
+        }
+
+        impl<S: Stream + Unpin> Stream for Peekable<S>
+        where
+            S::Item: Unpin,
+        {
+            type Item = S::Item;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+                match self.peeked.take() {
+                    Some(item) => Poll::Ready(item),
+                    None => Pin::new(&mut self.stream).poll_next(cx),
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::take_while`.
+        /// `Stream::take_while`返回的`Stream`。
+        pub struct TakeWhile<S, P> {
+            stream: S,
+            predicate: P,
+            done: bool,
+        }
+
+        impl<S, P> Stream for TakeWhile<S, P>
+        where
+            S: Stream + Unpin,
+            P: FnMut(&S::Item) -> bool + Unpin,
+        {
+            type Item = S::Item;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+                if self.done {
+                    return Poll::Ready(None);
+                }
+                let item = ready!(Pin::new(&mut self.stream).poll_next(cx));
+                match item {
+                    Some(item) if (self.predicate)(&item) => Poll::Ready(Some(item)),
+                    _ => {
+                        self.done = true;
+                        Poll::Ready(None)
+                    }
+                }
+            }
+        }
+
+        /// A pinned stream is a stream too. This is what lets us `Box::pin` a
+        /// stream that isn't `Unpin` and still call `next` on it.
+        /// 一个被pin住的流也是一个流。这让我们可以`Box::pin`一个不是`Unpin`的流，并且仍然可以对它调用`next`。
+        impl<P> Stream for Pin<P>
+        where
+            P: DerefMut + Unpin,
+            P::Target: Stream,
+        {
+            type Item = <P::Target as Stream>::Item;
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                self.get_mut().as_mut().poll_next(cx)
+            }
+        }
+
+        /// The `Future` returned by `Stream::next`.
+        /// `Stream::next`返回的`Future`。
+        pub struct Next<'a, S> {
+            stream: &'a mut S,
+        }
+
+        impl<S: Stream + Unpin> Future for Next<'_, S> {
+            type Output = Option<S::Item>;
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                Pin::new(&mut *self.stream).poll_next(cx)
+            }
+        }
+
+        /// The `Stream` returned by `Stream::timeout_idle`.
+        /// `Stream::timeout_idle`返回的`Stream`。
+        pub struct TimeoutIdle<S> {
+            stream: S,
+            idle: Duration,
+            /// Runs out once the stream has been quiet for too long.
+            /// 一旦流安静了太久，它就会到期。
+            sleep: Sleep,
+            timed_out: bool,
+        }
+
+        impl<S: Stream + Unpin> Stream for TimeoutIdle<S> {
+            type Item = Result<S::Item, Elapsed>;
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                if self.timed_out {
+                    return Poll::Ready(None);
+                }
+                match Pin::new(&mut self.stream).poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        let idle = self.idle.as_millis();
+                        self.sleep.reset(idle);
+                        Poll::Ready(Some(Ok(item)))
+                    }
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => match Pin::new(&mut self.sleep).poll(cx) {
+                        Poll::Ready(()) => {
+                            self.timed_out = true;
+                            Poll::Ready(Some(Err(Elapsed)))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    },
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::timeout`.
+        /// `Stream::timeout`返回的`Stream`。
+        pub struct Timeout<S> {
+            stream: S,
+            ms: u128,
+            /// The wait for the next item, once we've started waiting for it.
+            /// 对下一个元素的等待，一旦我们开始等它了的话。
+            sleep: Option<Sleep>,
+        }
+
+        impl<S: Stream + Unpin> Stream for Timeout<S> {
+            type Item = Result<S::Item, Elapsed>;
+            fn poll_next(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                if self.sleep.is_none() {
+                    self.sleep = Some(Sleep::new(self.ms));
+                }
+                match Pin::new(&mut self.stream).poll_next(cx) {
+                    Poll::Ready(item) => {
+                        self.sleep = None;
+                        Poll::Ready(item.map(Ok))
+                    }
+                    Poll::Pending => {
+                        let sleep = self.sleep.as_mut().unwrap();
+                        ready!(Pin::new(sleep).poll(cx));
+                        self.sleep = None;
+                        Poll::Ready(Some(Err(Elapsed)))
+                    }
+                }
+            }
+        }
+
+        /// The `Stream` returned by `Stream::buffered`.
+        /// `Stream::buffered`返回的`Stream`。
+        pub struct Buffered<S, F, Fut: Future> {
+            /// `None` once the stream has ended.
+            /// 一旦流结束了就是`None`。
+            stream: Option<S>,
+            f: F,
+            n: usize,
+            /// Everything we're still working on or that's waiting for its
+            /// turn, in the order the items came in. The front is always the
+            /// next one we hand back.
+            /// 所有我们还在处理的或者在等待轮到它的东西，按照元素进来的顺序排列。最前面的总是我们下一个要交出的。
+            in_flight: VecDeque<Slot<Fut>>,
+        }
+
+        /// One spot in line for `Buffered`.
+        /// `Buffered`中排队的一个位置。
+        enum Slot<Fut: Future> {
+            Running(Pin<Box<Fut>>),
+            Done(Fut::Output),
+        }
+
+        /// Every future gets boxed, so nothing in here is ever pinned in
+        /// place and it's fine to move it around.
+        /// 每个future都被装箱了，所以这里面没有任何东西被pin在原地，移动它是没问题的。
+        impl<S: Unpin, F, Fut: Future> Unpin for Buffered<S, F, Fut> {}
+
+        impl<S, F, Fut> Stream for Buffered<S, F, Fut>
+        where
+            S: Stream + Unpin,
+            F: FnMut(S::Item) -> Fut,
+            Fut: Future,
+        {
+            type Item = Fut::Output;
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                let this = self.get_mut();
+                // Take in new items until we're full or the stream runs dry.
+                // 接收新的元素，直到我们满了或者流暂时没有东西了。
+                while this.in_flight.len() < this.n {
+                    let Some(stream) = this.stream.as_mut() else {
+                        break;
+                    };
+                    match Pin::new(stream).poll_next(cx) {
+                        Poll::Ready(Some(item)) => {
+                            let future = Box::pin((this.f)(item));
+                            this.in_flight.push_back(Slot::Running(future));
+                        }
+                        Poll::Ready(None) => this.stream = None,
+                        Poll::Pending => break,
+                    }
+                }
+                for slot in this.in_flight.iter_mut() {
+                    if let Slot::Running(future) = slot {
+                        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                            *slot = Slot::Done(output);
+                        }
+                    }
+                }
+                match this.in_flight.front() {
+                    Some(Slot::Done(_)) => match this.in_flight.pop_front() {
+                        Some(Slot::Done(output)) => {
+                            // There's room for another one now, so make sure
+                            // we get polled again to take it in.
+                            // 现在有空间容纳另一个了，所以确保我们会被再次poll来接收它。
+                            cx.waker().wake_by_ref();
+                            Poll::Ready(Some(output))
+                        }
+                        _ => unreachable!(),
+                    },
+                    Some(Slot::Running(_)) => Poll::Pending,
+                    None if this.stream.is_none() => Poll::Ready(None),
+                    None => Poll::Pending,
+                }
+            }
+        }
+
+        /// Turns a `std::sync::mpsc::Receiver` into a `Stream`, so values sent
+        /// from plain sync code can be awaited. Just like with
+        /// `futures::flag::WaitFor`, the sending side doesn't know anything
+        /// about wakers, so when the channel is empty we ask the timer to wake
+        /// us up shortly and try again. The stream ends once every `Sender` is
+        /// gone and the channel has been drained.
+        /// 把`std::sync::mpsc::Receiver`变成一个`Stream`，这样从普通同步代码发送的值就可以被await了。
+        /// 就像`futures::flag::WaitFor`一样，发送端对waker一无所知，所以当通道为空时，我们让定时器稍后唤醒我们再试一次。
+        /// 一旦所有的`Sender`都没了并且通道被取空，这个流就结束了。
+        pub fn from_std_receiver<T>(rx: Receiver<T>) -> impl Stream<Item = T> {
+            StdReceiver {
+                rx: Mutex::new(rx),
+                timer: None,
+            }
+        }
+
+        struct StdReceiver<T> {
+            /// Futures we spawn have to be `Sync` and a `Receiver` isn't, so
+            /// it goes in a `Mutex`. We only ever get at it through `&mut`
+            /// though, so the lock is never actually taken.
+            /// 我们生成的future必须是`Sync`的，而`Receiver`不是，所以它被放进了一个`Mutex`里。
+            /// 不过我们只会通过`&mut`访问它，所以这个锁实际上从来不会被获取。
+            rx: Mutex<Receiver<T>>,
+            /// The timer that will wake us up to try the channel again.
+            /// 将会唤醒我们再次尝试通道的定时器。
+            timer: Option<runtime::TimerEntry>,
+        }
+
+        impl<T> Stream for StdReceiver<T> {
+            type Item = T;
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+                // Same order as `WaitFor`: set up the wake up call before the
+                // last look so a value sent in between can't get missed.
+                // 和`WaitFor`一样的顺序：在最后一次查看之前安排好唤醒，这样在中间发送的值就不会被错过。
+                for attempt in 0..2 {
+                    match self.rx.get_mut().unwrap().try_recv() {
+                        Ok(value) => {
+                            self.timer = None;
+                            return Poll::Ready(Some(value));
+                        }
+                        Err(TryRecvError::Disconnected) => {
+                            self.timer = None;
+                            return Poll::Ready(None);
+                        }
+                        Err(TryRecvError::Empty) if attempt == 0 => {
+                            let deadline = Instant::now() + RETRY_INTERVAL;
+                            self.timer =
+                                Some(runtime::register_timer(deadline, cx.waker().clone()));
+                        }
+                        Err(TryRecvError::Empty) => {}
+                    }
+                }
+                Poll::Pending
+            }
+        }
+
+        #[test]
+        /// Values sent on a `std` channel from another thread should come out
+        /// of the stream, and the stream should end when the sender is gone.
+        /// 从另一个线程在`std`通道上发送的值应该从流中出来，当发送端没了的时候流应该结束。
+        fn std_receiver_streams_what_a_thread_sends() {
+            use std::{sync::mpsc, thread};
+
+            let (values_tx, values_rx) = mpsc::channel();
+            let (tx, rx) = mpsc::channel();
+            let runtime = runtime::Builder::new().build();
+            let mut stream = Box::pin(from_std_receiver(values_rx));
+            runtime.spawn(async move {
+                let mut values = Vec::new();
+                while let Some(value) = stream.next().await {
+                    values.push(value);
+                }
+                tx.send(values).unwrap();
+            });
+            thread::spawn(move || {
+                for i in 0..3 {
+                    thread::sleep(Duration::from_millis(10));
+                    values_tx.send(i).unwrap();
+                }
+            });
+            let values = rx.recv_timeout(Duration::from_secs(30)).unwrap();
+            assert_eq!(values, [0, 1, 2]);
+        }
+
+        #[test]
+        /// A stream that stalls for longer than the idle timeout should get
+        /// an error and end, while one that keeps going should not.
+        /// 一个停顿时间超过空闲超时的流应该得到一个错误并结束，而一个一直在继续的流则不应该。
+        fn idle_streams_time_out() {
+            use std::{sync::mpsc, thread};
+
+            let collect = |pause: Duration| {
+                let (values_tx, values_rx) = mpsc::channel();
+                let mut stream =
+                    Box::pin(from_std_receiver(values_rx)).timeout_idle(Duration::from_millis(100));
+                thread::spawn(move || {
+                    for i in 0..3 {
+                        values_tx.send(i).unwrap();
+                        thread::sleep(pause);
+                    }
+                });
+                runtime::block_on(async move {
+                    let mut items = Vec::new();
+                    while let Some(item) = stream.next().await {
+                        items.push(item);
+                    }
+                    items
+                })
+            };
+            assert_eq!(collect(Duration::from_millis(10)), [Ok(0), Ok(1), Ok(2)]);
+            assert_eq!(collect(Duration::from_millis(300)), [Ok(0), Err(Elapsed)]);
+        }
+
+        #[test]
+        /// A stream that stalls once should hand back an `Err` for the item
+        /// that was late, and then carry on with `Ok`s.
+        /// 一个卡住过一次的流应该为迟到的那个元素交出一个`Err`，然后继续交出`Ok`。
+        fn timeout_errors_on_a_slow_item_and_carries_on() {
+            use std::{sync::mpsc, thread};
+
+            let (values_tx, values_rx) = mpsc::channel();
+            let mut stream = Box::pin(from_std_receiver(values_rx)).timeout(100);
+            thread::spawn(move || {
+                for i in 0..4 {
+                    if i == 2 {
+                        thread::sleep(Duration::from_millis(150));
+                    }
+                    values_tx.send(i).unwrap();
+                }
+            });
+            let items = runtime::block_on(async move {
+                let mut items = Vec::new();
+                while let Some(item) = stream.next().await {
+                    items.push(item);
+                }
+                items
+            });
+            assert_eq!(items, [Ok(0), Ok(1), Err(Elapsed), Ok(2), Ok(3)]);
+        }
+
+        #[test]
+        /// `buffered` should run up to `n` futures at once but still hand
+        /// their outputs back in the order the items came in, even when later
+        /// ones finish first.
+        /// `buffered`应该同时运行最多`n`个future，但仍然按照元素进来的顺序交出它们的输出，即使后面的先完成了。
+        fn buffered_keeps_input_order() {
+            use std::sync::{
+                atomic::{AtomicUsize, Ordering},
+                mpsc, Arc,
+            };
+
+            let (tx, rx) = mpsc::channel();
+            for delay in [50, 10, 30, 0, 20, 40] {
+                tx.send(delay).unwrap();
+            }
+            drop(tx);
+            let running = Arc::new(AtomicUsize::new(0));
+            let most = Arc::new(AtomicUsize::new(0));
+            let mut stream = Box::pin(from_std_receiver(rx)).buffered(3, |delay: u128| {
+                let running = running.clone();
+                let most = most.clone();
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    most.fetch_max(now, Ordering::SeqCst);
+                    Sleep::new(delay).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    delay
+                }
+            });
+            let outputs = runtime::block_on(async move {
+                let mut outputs = Vec::new();
+                while let Some(output) = stream.next().await {
+                    outputs.push(output);
+                }
+                outputs
+            });
+            assert_eq!(outputs, [50, 10, 30, 0, 20, 40]);
+            assert_eq!(most.load(Ordering::SeqCst), 3);
+        }
+
+        #[test]
+        /// `take`, `skip` and `take_while` should pick out the same items as
+        /// their `Iterator` namesakes do.
+        /// `take`、`skip`和`take_while`挑出的元素应该和它们在`Iterator`上的同名方法一样。
+        fn take_and_skip_bound_a_stream() {
+            let collect = |mut stream: Pin<Box<dyn Stream<Item = i32> + Send + Sync>>| {
+                runtime::block_on(async move {
+                    let mut items = Vec::new();
+                    while let Some(item) = stream.next().await {
+                        items.push(item);
+                    }
+                    items
+                })
+            };
+            assert_eq!(collect(Box::pin(iter(0..10).take(3))), [0, 1, 2]);
+            assert_eq!(collect(Box::pin(iter(0..10).skip(8))), [8, 9]);
+            assert_eq!(collect(Box::pin(iter(0..10).skip(20))), []);
+            assert_eq!(
+                collect(Box::pin(iter(0..10).take_while(|&i| i < 4))),
+                [0, 1, 2, 3]
+            );
+            assert_eq!(collect(Box::pin(iter(0..10).skip(2).take(2))), [2, 3]);
+        }
+
+        #[test]
+        /// `chunks` should hand back full batches, then whatever is left over,
+        /// and nothing at all for an empty stream.
+        /// `chunks`应该交出完整的批次，然后是剩下的东西，而对于一个空的流什么都不交出。
+        fn chunks_batch_up_items() {
+            let collect = |mut stream: Chunks<Iter<std::ops::Range<i32>>>| {
+                runtime::block_on(async move {
+                    let mut chunks = Vec::new();
+                    while let Some(chunk) = stream.next().await {
+                        chunks.push(chunk);
+                    }
+                    chunks
+                })
+            };
+            assert_eq!(
+                collect(iter(0..7).chunks(3)),
+                [vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+            );
+            assert_eq!(
+                collect(iter(0..6).chunks(3)),
+                [vec![0, 1, 2], vec![3, 4, 5]]
+            );
+            assert!(collect(iter(0..0).chunks(3)).is_empty());
+        }
+
+        #[test]
+        /// `merge` should keep handing back items from the slow stream after
+        /// the quick one has run out, and only end once both have.
+        /// `merge`应该在快的流用完之后继续交出慢的流的元素，并且只在两个都结束之后才结束。
+        fn merge_drains_both_streams() {
+            use std::{sync::mpsc, thread};
+
+            let (values_tx, values_rx) = mpsc::channel();
+            thread::spawn(move || {
+                for i in 10..12 {
+                    thread::sleep(Duration::from_millis(20));
+                    values_tx.send(i).unwrap();
+                }
+            });
+            let slow = Box::pin(from_std_receiver(values_rx));
+            let mut merged = merge(iter(0..3), slow);
+            let mut items = runtime::block_on(async move {
+                let mut items = Vec::new();
+                while let Some(item) = merged.next().await {
+                    items.push(item);
+                }
+                items
+            });
+            // The quick ones are all ready before the first slow one shows up.
+            // 快的那些在第一个慢的出现之前就全都就绪了。
+            assert_eq!(items[..3], [0, 1, 2]);
+            items.sort();
+            assert_eq!(items, [0, 1, 2, 10, 11]);
+        }
+
+        #[test]
+        /// `zip` should pair items up in order and stop at the end of the
+        /// shorter stream, even when one side is slower than the other.
+        /// `zip`应该按顺序把元素配成对，并在较短的流结束时停下来，即使其中一边比另一边慢。
+        fn zip_stops_at_the_shorter_stream() {
+            let collect = |mut stream: Pin<Box<dyn Stream<Item = (i32, char)> + Send + Sync>>| {
+                runtime::block_on(async move {
+                    let mut pairs = Vec::new();
+                    while let Some(pair) = stream.next().await {
+                        pairs.push(pair);
+                    }
+                    pairs
+                })
+            };
+            let pairs = collect(Box::pin(zip(iter(0..3), iter(['a', 'b', 'c', 'd']))));
+            assert_eq!(pairs, [(0, 'a'), (1, 'b'), (2, 'c')]);
+
+            let (tx, rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                for c in ['x', 'y'] {
+                    std::thread::sleep(Duration::from_millis(20));
+                    tx.send(c).unwrap();
+                }
+            });
+            let slow = Box::pin(from_std_receiver(rx));
+            let pairs = collect(Box::pin(zip(iter(0..5), slow)));
+            assert_eq!(pairs, [(0, 'x'), (1, 'y')]);
+        }
+
+        #[test]
+        /// `zip` and `chunks` only ever move items by value, so items that
+        /// aren't `Unpin` should go through them just fine.
+        /// `zip`和`chunks`只会按值移动元素，所以不是`Unpin`的元素应该可以正常地通过它们。
+        fn zip_and_chunks_take_items_that_are_not_unpin() {
+            use std::marker::PhantomPinned;
+
+            let pinned = |n| iter((0..n).map(|_| PhantomPinned));
+            let (pairs, chunks) = runtime::block_on(async move {
+                let mut zipped = zip(pinned(3), pinned(5));
+                let mut pairs = 0;
+                while zipped.next().await.is_some() {
+                    pairs += 1;
+                }
+                let mut chunked = pinned(5).chunks(2);
+                let mut chunks = Vec::new();
+                while let Some(chunk) = chunked.next().await {
+                    chunks.push(chunk.len());
+                }
+                (pairs, chunks)
+            });
+            assert_eq!(pairs, 3);
+            assert_eq!(chunks, [2, 2, 1]);
+        }
+
+        #[test]
+        /// `fold` should run every step in order and hand back the total,
+        /// even when each step has to wait on something.
+        /// `fold`应该按顺序运行每一步并交出总数，即使每一步都要等待某个东西。
+        fn fold_adds_up_a_stream() {
+            let total = runtime::block_on(iter(1..=4).fold(0, |total, i| async move {
+                Sleep::new(1).await;
+                total + i
+            }));
+            assert_eq!(total, 10);
+            assert_eq!(
+                runtime::block_on(iter(0..0).fold(7, |total, i| async move { total + i })),
+                7
+            );
+        }
+
+        #[test]
+        /// `flatten` should drain each inner stream in turn, skip over empty
+        /// ones, and end along with the outer stream.
+        /// `flatten`应该依次取空每一个内部流，跳过空的那些，并和外层流一起结束。
+        fn flatten_joins_up_inner_streams() {
+            let streams = iter([
+                iter(vec![1, 2]),
+                iter(vec![]),
+                iter(vec![3]),
+                iter(vec![4, 5]),
+            ]);
+            let items = runtime::block_on(streams.flatten().fold(Vec::new(), |mut items, i| {
+                items.push(i);
+                async move { items }
+            }));
+            assert_eq!(items, [1, 2, 3, 4, 5]);
+        }
+
+        #[test]
+        /// `scan` should carry its state from item to item, and stop for good
+        /// the first time `f` hands back `None`.
+        /// `scan`应该把它的状态从一个元素带到下一个元素，并在`f`第一次返回`None`时彻底停下来。
+        fn scan_keeps_a_running_total() {
+            fn collect(stream: impl Stream<Item = i32> + Unpin) -> Vec<i32> {
+                runtime::block_on(stream.fold(Vec::new(), |mut items, i| {
+                    items.push(i);
+                    async move { items }
+                }))
+            }
+
+            let sums = iter(1..=4).scan(0, |total, i| {
+                *total += i;
+                Some(*total)
+            });
+            assert_eq!(collect(sums), [1, 3, 6, 10]);
+            let capped = iter(1..=4).scan(0, |total, i| {
+                *total += i;
+                (*total < 5).then_some(*total)
+            });
+            assert_eq!(collect(capped), [1, 3]);
+        }
+
+        #[test]
+        /// `peek` should show the next item without taking it, so `next`
+        /// hands back that same item afterwards.
+        /// `peek`应该展示下一个元素但不拿走它，所以之后`next`会交出同一个元素。
+        fn peek_leaves_the_item_for_next() {
+            let (first, again, next, rest, end) = runtime::block_on(async {
+                let mut stream = iter(['a', 'b']).peekable();
+                let first = stream.peek().await.copied();
+                let again = stream.peek().await.copied();
+                let next = stream.next().await;
+                let rest = stream.next().await;
+                let end = stream.peek().await.copied();
+                (first, again, next, rest, end)
+            });
+            assert_eq!((first, again, next), (Some('a'), Some('a'), Some('a')));
+            assert_eq!((rest, end), (Some('b'), None));
+        }
+    }
+
+    #[test]
+    /// `collect` should hand back every output no matter what order they
+    /// finish in, and `collect_ordered` should put them back in order.
+    /// `collect`应该交还每一个输出，无论它们以什么顺序完成，而`collect_ordered`应该把它们按顺序放回去。
+    fn futures_unordered_collects_every_output() {
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = runtime::Builder::new().build();
+        let sleepy = |i: usize| async move {
+            Sleep::new(10 * (5 - i as u128)).await;
+            (i, i * 10)
+        };
+        let unordered: FuturesUnordered<_> = (0..5).map(sleepy).collect();
+        let ordered: FuturesUnordered<_> = (0..5).map(sleepy).collect();
+        let (tx, rx) = mpsc::channel();
+        runtime.spawn(async move {
+            let mut outputs = unordered.collect().await;
+            let ordered = ordered.collect_ordered().await;
+            outputs.sort();
+            tx.send((outputs, ordered)).unwrap();
+        });
+        let (outputs, ordered) = rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        assert_eq!(outputs, [(0, 0), (1, 10), (2, 20), (3, 30), (4, 40)]);
+        assert_eq!(ordered, [0, 10, 20, 30, 40]);
+    }
+
+    #[cfg(test)]
+    /// Counts how many times it gets dropped, for the tests that check a
+    /// future was dropped rather than left to run to the end.
+    /// 计算它被丢弃了多少次，给那些检查一个future是被丢弃了、而不是被留着运行到最后的测试用。
+    struct Dropped(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    #[cfg(test)]
+    impl Drop for Dropped {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    /// `try_join2` should hand back the first error it sees and drop the
+    /// other future without waiting for it to finish.
+    /// `try_join2`应该交还它看到的第一个错误，并且丢弃另一个future而不等它完成。
+    fn try_join2_stops_at_the_first_error() {
+        use crate::runtime::block_on_timeout;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let joined = block_on_timeout(1000, try_join2(ok::<_, &str>(1), err::<i32, _>("x")));
+        assert_eq!(joined, Ok(Err("x")));
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let guard = Dropped(dropped.clone());
+        let slow = async move {
+            Sleep::new(5000).await;
+            drop(guard);
+            Ok::<_, &str>(1)
+        };
+        let failing = async {
+            Sleep::new(10).await;
+            err::<i32, _>("x").await
+        };
+        assert_eq!(
+            block_on_timeout(1000, try_join2(slow, failing)),
+            Ok(Err("x"))
+        );
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            block_on_timeout(1000, try_join2(ok::<_, ()>(1), ok('a'))),
+            Ok(Ok((1, 'a')))
+        );
+    }
+
+    #[test]
+    /// When one of five futures fails, `try_join_all` should hand back its
+    /// error right away and drop the other four, and when none of them fail
+    /// it should hand back every value in order.
+    /// 当五个future中有一个失败时，`try_join_all`应该立刻交还它的错误并丢弃另外四个，而当它们都没有失败时，应该按顺序交还每一个值。
+    fn try_join_all_drops_the_rest_on_the_first_error() {
+        use crate::runtime::block_on_timeout;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let futures: Vec<BoxFuture<'static, Result<usize, usize>>> = (0..5)
+            .map(|i| {
+                let guard = Dropped(dropped.clone());
+                boxed(async move {
+                    if i == 2 {
+                        Sleep::new(10).await;
+                        return Err(i);
+                    }
+                    Sleep::new(5000).await;
+                    drop(guard);
+                    Ok(i)
+                })
+            })
+            .collect();
+        assert_eq!(block_on_timeout(1000, try_join_all(futures)), Ok(Err(2)));
+        assert_eq!(dropped.load(Ordering::Relaxed), 5);
+
+        let futures = (0..5).map(|i| async move {
+            Sleep::new(10 * (5 - i)).await;
+            Ok::<_, ()>(i)
+        });
+        assert_eq!(
+            block_on_timeout(1000, try_join_all(futures)),
+            Ok(Ok(vec![0, 1, 2, 3, 4]))
+        );
+    }
+
+    #[test]
+    /// A future that keeps waking itself up without ever finishing should be
+    /// given up on after `poll_limit` polls, while one that finishes in time
+    /// should hand back its output.
+    /// 一个不停唤醒自己却永远不会完成的future应该在`poll_limit`次poll之后被放弃，而一个及时完成的future应该交还它的输出。
+    fn poll_limit_catches_wake_loops() {
+        use std::cell::Cell;
+
+        let polls = Cell::new(0);
+        let spinning = poll_fn(|cx| {
+            polls.set(polls.get() + 1);
+            cx.waker().wake_by_ref();
+            Poll::<()>::Pending
+        });
+        assert_eq!(
+            runtime::block_on(spinning.poll_limit(10)),
+            Err(PollLimitExceeded(10))
+        );
+        assert_eq!(polls.get(), 10);
+        assert_eq!(runtime::block_on(Sleep::new(10).poll_limit(10)), Ok(()));
+    }
+
+    #[test]
+    /// A task awaiting `yield_n(3)` should get polled 4 times, and another
+    /// task should get a turn in between each of them.
+    /// 一个await`yield_n(3)`的任务应该被poll 4次，而另一个任务应该在每两次之间都轮到一次。
+    fn yield_n_lets_others_run_in_between() {
+        use crate::runtime::Builder;
+        use std::sync::{Arc, Mutex};
+
+        let runtime = Builder::new().worker_threads(0).build();
+        let log = Arc::new(Mutex::new(String::new()));
+        runtime.spawn({
+            let log = log.clone();
+            let mut yielding = yield_n(3);
+            poll_fn(move |cx| {
+                log.lock().unwrap().push('a');
+                Pin::new(&mut yielding).poll(cx)
+            })
+        });
+        runtime.spawn({
+            let log = log.clone();
+            async move {
+                for _ in 0..3 {
+                    log.lock().unwrap().push('b');
+                    yield_now().await;
+                }
+            }
+        });
+        while runtime.tick() {}
+        assert_eq!(*log.lock().unwrap(), "abababa");
+    }
+
+    #[test]
+    /// `or_else_after` should hand back the primary future's output when it's
+    /// quick, the fallback's when the primary is slow, and when the primary
+    /// finishes after the fallback started it should still win unless it was
+    /// cancelled.
+    /// 当主future很快时，`or_else_after`应该交还它的输出，当主future很慢时应该交还备用的输出，
+    /// 而当主future在备用的开始之后才完成时，它应该仍然会赢，除非它被取消了。
+    fn or_else_after_hedges_slow_futures() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let after = |ms: u128, value: &'static str| async move {
+            Sleep::new(ms).await;
+            value
+        };
+        let quick = after(10, "primary").or_else_after(200, after(10, "fallback"));
+        assert_eq!(runtime::block_on(quick), "primary");
+        let slow = after(500, "primary").or_else_after(10, after(10, "fallback"));
+        assert_eq!(runtime::block_on(slow), "fallback");
+        let racing = after(50, "primary").or_else_after(10, after(500, "fallback"));
+        assert_eq!(runtime::block_on(racing), "primary");
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let guard = Dropped(dropped.clone());
+        let primary = async move {
+            let _guard = guard;
+            after(50, "primary").await
+        };
+        let cancelled = primary
+            .or_else_after(10, after(200, "fallback"))
+            .cancel_on_fallback();
+        assert_eq!(runtime::block_on(cancelled), "fallback");
+        assert_eq!(dropped.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    /// A one second future with a 200ms progress interval should tick about
+    /// four times, and never again once it's done.
+    /// 一个一秒的future配上200ms的进度间隔应该tick大约四次，而且一旦它完成了就再也不会tick。
+    fn with_progress_ticks_while_pending() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let ticks = AtomicUsize::new(0);
+        runtime::block_on(Sleep::new(1000).with_progress(200, || {
+            ticks.fetch_add(1, Ordering::Relaxed);
+        }));
+        let ticked = ticks.load(Ordering::Relaxed);
+        assert!((3..=4).contains(&ticked), "ticked {ticked} times");
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(ticks.load(Ordering::Relaxed), ticked);
+        assert_eq!(
+            runtime::block_on(ready(1).with_progress(1, || panic!("ready futures don't tick"))),
+            1
+        );
+    }
+
+    #[test]
+    /// With a fixed seed the waits between retries should come out the same
+    /// every time, stay within the jitter of the doubled delay, never go past
+    /// the cap, and the operation should get retried until it succeeds.
+    /// 用一个固定的种子，重试之间的等待每次都应该一样，保持在翻倍延迟的抖动范围内，永远不超过上限，而且操作应该被重试直到它成功。
+    fn retry_backs_off_with_jitter() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let backoff = Backoff::new(10, 100).jitter(5).seed(42);
+        let delays =
+            |mut backoff: Backoff| (0..6).map(|_| backoff.next_delay()).collect::<Vec<_>>();
+        let first = delays(backoff.clone());
+        assert_eq!(first, delays(backoff.clone()));
+        for (delay, expected) in first.iter().zip([10, 20, 40, 80, 100, 100]) {
+            assert!(delay.abs_diff(expected) <= 5, "{first:?}");
+            assert!(*delay <= 100, "{first:?}");
+        }
+        assert_ne!(first, delays(Backoff::new(10, 100).jitter(5).seed(7)));
+        // Nothing this big should overflow, it just stays at the cap.
+        // 这么大的数都不应该溢出，它只是停在上限。
+        let cap = u128::MAX - 1;
+        let huge = Backoff::new(cap, cap).jitter(u128::MAX).seed(42);
+        assert!(delays(huge).iter().all(|&delay| delay <= cap));
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let start = Instant::now();
+        let result = runtime::block_on(retry(5, backoff, || {
+            let calls = calls.clone();
+            async move {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0..=2 => Err("not yet"),
+                    _ => Ok("done"),
+                }
+            }
+        }));
+        assert_eq!(result, Ok("done"));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        let slept: u128 = first[..3].iter().sum();
+        assert!(start.elapsed().as_millis() >= slept);
+        let gave_up = runtime::block_on(retry(2, Backoff::new(1, 1), || err::<(), _>("no")));
+        assert_eq!(gave_up, Err("no"));
+    }
+
+    #[test]
+    /// When every branch is ready at once, `select_biased!` should always
+    /// pick the first one, and it should still get to a later branch when
+    /// that's the only one that's ready.
+    /// 当每个分支同时都准备好时，`select_biased!`应该总是选择第一个，而当后面的某个分支是唯一准备好的那个时，它也仍然应该选到它。
+    fn select_biased_picks_the_first_ready_branch() {
+        use std::future::pending;
+
+        for _ in 0..100 {
+            let picked = runtime::block_on(async {
+                crate::select_biased! {
+                    a = ready(1) => a * 10,
+                    b = ready(2) => b * 100,
+                    _ = Sleep::new(0) => 0,
+                }
+            });
+            assert_eq!(picked, 10);
+        }
+        let picked = runtime::block_on(async {
+            crate::select_biased! {
+                () = pending() => "pending",
+                () = Sleep::new(10) => "slept",
+            }
+        });
+        assert_eq!(picked, "slept");
+    }
+
+    #[test]
+    /// An `OptionFuture` should hand back the inner future's output when
+    /// there is one, and `None` when there isn't.
+    /// 一个`OptionFuture`在有里面的future时应该交出它的输出，没有的时候应该交出`None`。
+    fn option_future_handles_both_cases() {
+        let some: OptionFuture<_> = Some(async {
+            Sleep::new(10).await;
+            5
+        })
+        .into();
+        let none: OptionFuture<Ready<i32>> = None.into();
+        assert_eq!(runtime::block_on(some), Some(5));
+        assert_eq!(runtime::block_on(none), None);
+    }
+
+    #[test]
+    /// Futures of different types should fit in one `Vec` once they're
+    /// boxed, and `join_all` should hand back their outputs in order.
+    /// 不同类型的future一旦被装箱就应该能放进同一个`Vec`，而`join_all`应该按顺序交还它们的输出。
+    fn boxed_futures_of_different_types_join_in_order() {
+        let futures: Vec<BoxFuture<'static, i32>> = vec![
+            boxed(async {
+                Sleep::new(20).await;
+                1
+            }),
+            boxed(ready(2)),
+            boxed(async { 3 }),
+        ];
+        assert_eq!(runtime::block_on(join_all(futures)), [1, 2, 3]);
+        assert!(runtime::block_on(join_all(Vec::<BoxFuture<'static, i32>>::new())).is_empty());
+    }
+
+    #[test]
+    /// `join3` and `join4` should hand back outputs of different types in
+    /// the order their futures came in, whichever one finished first.
+    /// `join3`和`join4`应该按照它们的future进来的顺序交还不同类型的输出，无论哪一个先完成。
+    fn join3_and_join4_hand_back_tuples() {
+        assert_eq!(
+            runtime::block_on(join3(ready(1), ready("a"), ready(true))),
+            (1, "a", true)
+        );
+        let slow = async {
+            Sleep::new(20).await;
+            'b'
+        };
+        assert_eq!(
+            runtime::block_on(join4(slow, ready(2), async { "c" }, ready(3.5))),
+            ('b', 2, "c", 3.5)
+        );
+        assert_eq!(
+            runtime::block_on(join2(ready(()), ok::<_, ()>(4))),
+            ((), Ok(4))
+        );
+    }
+}
+
+/// Something that happened while `library_workload` ran, along with which of
+/// the outer tasks it happened in, if it's one of theirs.
+/// `library_workload`运行时发生的一件事，以及如果它属于某个外层任务的话，它发生在哪一个外层任务中。
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LibraryEvent {
+    /// The `block_on` future started running.
+    /// `block_on`的future开始运行了。
+    Begin,
+    /// Outer task `i` started.
+    /// 外层任务`i`开始了。
+    Start(usize),
+    /// Outer task `i` spawned its inner task and finished.
+    /// 外层任务`i`生成了它的内层任务，然后结束了。
+    Ended(usize),
+    /// The inner task spawned by outer task `i` finished.
+    /// 外层任务`i`生成的内层任务结束了。
+    Inner(usize),
+    /// The blocking task was polled to completion.
+    /// 阻塞任务被poll到完成了。
+    Blocking,
+    /// `wait` returned, so every task on the runtime is done.
+    /// `wait`返回了，所以运行时上的每一个任务都完成了。
+    End,
+}
+
+/// The workload behind `library_test`, on `runtime` and without printing
+/// anything, so that it can be run over and over and checked. Sleeps are
+/// counted in `unit_ms` milliseconds, which is a second for the demo but can
+/// be much less when all we want is to look at the order things happened in.
+/// Every event goes to `report` as it happens and the whole log is handed
+/// back at the end.
+/// `library_test`背后的工作负载，运行在`runtime`上并且不打印任何东西，这样它就可以被一遍又一遍地运行和检查。
+/// 睡眠以`unit_ms`毫秒为单位来计算，对于演示来说这是一秒，但当我们只想看看事情发生的顺序时可以短得多。
+/// 每一个事件在发生时都会交给`report`，整个日志会在最后被交还。
+#[cfg(test)]
+fn library_workload(
+    runtime: &runtime::Runtime,
+    unit_ms: u128,
+    report: fn(LibraryEvent),
+) -> Vec<LibraryEvent> {
+    // We're going to import our Sleep future to make sure that it works,
+    // because it's not a complicated future and it's easy to see the
+    // asynchronous nature of the code.
+    // 我们要导入Sleep future，来确保它是可以正常工作的，因为它不是一个复杂的future，所以很容易看出代码的异步本质。
+    use crate::futures::Sleep;
+    use std::sync::{Arc, Mutex};
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let record = {
+        let log = log.clone();
+        move |event| {
+            report(event);
+            log.lock().unwrap().push(event);
+        }
+    };
+    // We want some random numbers so that the sleep futures finish at different
+    // times. If we didn't, then the code would look synchronous in nature even
+    // if it isn't. This is because we schedule and poll tasks in what is
+    // essentially a loop unless we use block_on.
+    // 我们需要一些随机数，这样sleep future就会在不同的时间结束，如果我们不这样做，那么代码看起来就像是同步的，
+    // 即使本质上它不是。这是因为我们在调度和poll任务时，实际上是在一个循环中，除非我们使用block_on。
+    // They come from the runtime itself, so handing it a seed with
+    // `Builder::seed` would make every run come out the same.
+    // 它们来自运行时本身，所以用`Builder::seed`给它一个种子就会让每次运行的结果都一样。
+    //
+    // This function causes us to block on this future. It does so by just
+    // taking this future and polling it till completion right here on the
+    // calling thread, going to sleep whenever it's pending, and then handing
+    // back whatever it returned. Sometimes you need to block on async
+    // functions and treat them as sync. A good example is running a webserver.
+    // You'd want it to always be running, not just sometimes, and so blocking
+    // it makes sense. The main function won't keep going until the future is
+    // done, but the tasks it spawns run on the runtime's worker thread and
+    // can still be running after it returns. This is why we call `wait` to
+    // make sure we wait till all futures finish executing before exiting.
+    // block_on方法会在调用它的线程上就地获取这个future，不停地执行poll方法直到future返回Ready，
+    // 在它pending的时候就去睡觉，最后把它返回的东西交还给我们。
+    // 有时候我们需要阻塞异步函数，把它当做同步函数来使用。
+    // 一个很好的例子就是运行一个web服务器，你希望它一直运行，而不是偶尔运行，所以阻塞它是有意义的。
+    // 在future完成之前main函数不会继续往下走，但是它生成的任务是在运行时的工作线程上运行的，在它返回之后可能还在运行。
+    // 这就是为什么我们调用wait方法，来确保我们等待所有的future执行完毕，然后再退出。
+    runtime.block_on({
+        let record = record.clone();
+        async move {
+            record(LibraryEvent::Begin);
+            // Get the runtime's random number generator so we can generate random numbers
+            // 获取运行时的随机数生成器，这样我们就可以生成随机数
+            let rng = runtime::rng();
+
+            // Spawn 5 different futures on our executor
+            // 生成5个不同的future，然后在executor上执行
+            for i in 0..5 {
+                // Generate the two numbers between 1 and 4. We'll spawn two futures
+                // that will sleep for as many units as the random number creates
+                // 生成两个1到4之间的随机数。我们会生成两个future，这两个future会睡眠多少个单位，取决于随机数的大小
+                let random = u128::from(rng.gen_range(1..5));
+                let random2 = u128::from(rng.gen_range(1..5));
+                let record = record.clone();
+
+                // We now spawn a future onto the runtime from within our future
+                // 我们现在在future里面，从runtime上生成一个future
+                runtime::spawn(async move {
+                    record(LibraryEvent::Start(i));
+                    // This future will sleep for a certain amount of time before
+                    // continuing execution
+                    // 这个future会睡眠一段时间，然后继续执行
+                    Sleep::new(unit_ms * random).await;
+                    // After the future waits for a while, it then spawns another
+                    // future before printing that it finished. This spawned future
+                    // then sleeps for a while and then prints out when it's done.
+                    // Since we're spawning futures inside futures, the order of
+                    // execution can change.
+                    // 在future等待一段时间后，它会生成另一个future，然后打印它已经完成。
+                    // 由于我们在future里面生成future，所以执行的顺序会改变。
+                    let inner = record.clone();
+                    runtime::spawn(async move {
+                        Sleep::new(unit_ms * random2).await;
+                        inner(LibraryEvent::Inner(i));
+                    });
+                    record(LibraryEvent::Ended(i));
+                });
+            }
+            // To demonstrate blocking the runtime itself we use `block_on_worker`,
+            // which puts a blocking task at the front of the queue. The worker
+            // drives it to completion before it even begins polling the other
+            // futures.
+            // 为了演示阻塞运行时本身，我们使用`block_on_worker`，它会把一个阻塞任务放到队列的前端。
+            // 工作线程会先把它驱动到完成，然后才开始poll其他的future。
+            runtime::block_on_worker(async move {
+                // This sleeps longer than any of the spawned functions, but we poll
+                // this to completion first even if we await here.
+                // 这个睡眠的时间比生成的future都长，但是我们会先poll这个future，即使我们在这里await。
+                Sleep::new(unit_ms * 3).await;
+                record(LibraryEvent::Blocking);
+            });
+        }
+    });
+
+    // We now wait on the runtime to complete each of the tasks that were
+    // spawned before we exit the program
+    // 现在我们等待runtime完成所有的任务，然后再退出程序
+    runtime.wait();
+    record(LibraryEvent::End);
+    let log = log.lock().unwrap().clone();
+    log
+}
+
+#[test]
+/// To understand what we'll build, we need to see and understand what we will
+/// run and the output we expect to see. Note that if you wish to run this test,
+/// you should use the command `cargo test -- --nocapture` so that you can see
+/// the output of `println` being used, otherwise it'll look like nothing is
+/// happening at all for a while.
+/// 为了理解我们要构建的内容，我们需要看一下我们要运行的内容，以及我们期望看到的输出。
+/// 注意，如果你想要运行这个测试，你应该使用`cargo test -- --nocapture`命令，这样你就可以看到`println`的输出，
+/// 否则，你会看到一段时间什么都没有发生。
+fn library_test() {
+    // We need to know the time to show when a future completes. Time is cursed
+    // and it's best we dabble not too much in it.
+    // 我们需要知道时间，来显示future何时完成。时间是被诅咒的，所以我们最好不要太多地涉及它。
+    fn print(event: LibraryEvent) {
+        let (thread, time) = (current_thread_id(), current_time());
+        match event {
+            LibraryEvent::Begin => println!("2. Begin Asynchronous Execution,{}, {}", thread, time),
+            LibraryEvent::Start(i) => println!("Spawned Fn #{:02}: Start {} {}", i, thread, time),
+            LibraryEvent::Ended(i) => println!("Spawned Fn #{:02}: Ended {} {}", i, thread, time),
+            LibraryEvent::Inner(i) => println!("Spawned Fn #{:02}: Inner {} {}", i, thread, time),
+            LibraryEvent::Blocking => println!(
+                "3. Blocking Function Polled To Completion {} {}",
+                thread, time
+            ),
+            LibraryEvent::End => println!("4. End of Asynchronous Execution {} {}", thread, time),
+        }
+    }
+
+    println!(
+        "1. Current thread name {} {} {}",
+        thread::current().name().unwrap(),
+        current_thread_id(),
+        current_time()
+    );
+    const SECOND: u128 = 1000; //ms
+    library_workload(runtime::Runtime::get(), SECOND, print);
+
+    // When all is said and done when we run this test we should get output that
+    // looks somewhat like this (though in different order):
+    //
+    // Begin Asynchronous Execution
+    // Blocking Function Polled To Completion
+    // Spawned Fn #00: Start 1634664688
+    // Spawned Fn #01: Start 1634664688
+    // Spawned Fn #02: Start 1634664688
+    // Spawned Fn #03: Start 1634664688
+    // Spawned Fn #04: Start 1634664688
+    // Spawned Fn #01: Ended 1634664690
+    // Spawned Fn #01: Inner 1634664691
+    // Spawned Fn #04: Ended 1634664694
+    // Spawned Fn #04: Inner 1634664695
+    // Spawned Fn #00: Ended 1634664697
+    // Spawned Fn #02: Ended 1634664697
+    // Spawned Fn #03: Ended 1634664697
+    // Spawned Fn #00: Inner 1634664698
+    // Spawned Fn #03: Inner 1634664698
+    // Spawned Fn #02: Inner 1634664702
+    // End of Asynchronous Execution
+}
+
+#[test]
+/// Whatever order the tasks happen to finish in, every outer task should
+/// start before it ends, spawn exactly one inner task, and `wait` should only
+/// return once every one of those is done.
+/// 无论任务碰巧以什么顺序完成，每一个外层任务都应该先开始再结束，恰好生成一个内层任务，而`wait`应该只在所有这些都完成之后才返回。
+fn library_workload_keeps_its_invariants() {
+    for run in 0..100 {
+        let runtime = runtime::Builder::new()
+            .worker_threads(1 + run % 3)
+            .seed(run as u64)
+            .build();
+        let log = library_workload(&runtime, 1, |_| {});
+        let at = |event| {
+            let mut found = log.iter().enumerate().filter(|(_, e)| **e == event);
+            let (at, _) = found
+                .next()
+                .unwrap_or_else(|| panic!("{:?} never happened", event));
+            assert!(found.next().is_none(), "{:?} happened twice", event);
+            at
+        };
+        for i in 0..5 {
+            assert!(at(LibraryEvent::Start(i)) < at(LibraryEvent::Ended(i)));
+            assert!(at(LibraryEvent::Start(i)) < at(LibraryEvent::Inner(i)));
+        }
+        assert!(at(LibraryEvent::Begin) < at(LibraryEvent::Blocking));
+        assert_eq!(at(LibraryEvent::End), log.len() - 1);
+        assert_eq!(log.len(), 18);
+    }
+}
+
+// 获取当前线程唯一id
+pub fn current_thread_id() -> String {
+    let thread = thread::current();
+    let id = thread.id();
+    format!("{:?}", id)
+}
+
+// 获取当前时间 yyyy-MM-dd HH:MM:ss
+pub fn current_time() -> String {
+    let now = Local::now();
+    now.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+pub mod lazy {
+    use std::{
+        // We don't want to use `static mut` since that's UB and so instead we need
+        // a way to set our statics for our code at runtime. Since we want this to
+        // work across threads, we can't use `Cell` or `RefCell` here, and since it's
+        // a static we can't use a `Mutex` as its `new` function is not const. That
+        // means we need to use the actual type that all of these types use to hold
+        // the data: [`UnsafeCell`]! We'll see below where this is used and how, but
+        // just know that this will let us set some global values at runtime!
+        // 我们不想使用`static mut`，因为这是UB，所以我们需要一种方法来在运行时设置我们的代码的静态变量。
+        // 由于我们希望它能跨线程工作，所以我们不能在这里使用`Cell`或`RefCell`，而且由于它是一个静态变量，
+        // 我们不能使用`Mutex`，因为它的`new`函数不是const。这意味着我们需要使用这些类型用来保存数据的实际类型：[`UnsafeCell`]！
+        // 我们将在下面看到它的使用方式，但是要知道，这将让我们在运行时设置一些全局值！
+        cell::UnsafeCell,
+        future::{poll_fn, Future},
+        mem::{
+            // If you want to import the module to use while also specifying other
+            // imports you can use self to do that. In this case it will let us call
+            // `mem::swap` while also letting us just use `MaybeUninit` without any
+            // extra paths prepended to it. I tend to do this for functions that are
+            // exported at the module level and not encapsulated in a type so that
+            // it's more clear where it comes from, but that's a personal
+            // preference! You could just as easily import `swap` here instead!
+            // 如果你想导入模块来使用，同时还指定其他导入，你可以使用self来做到这一点。
+            // 在这种情况下，它将让我们调用`mem::swap`，同时让我们只使用`MaybeUninit`而不需要任何额外的路径前缀。
+            // 我倾向于为模块级别导出的函数而不是封装在类型中的函数执行此操作，以便更清楚地了解它来自哪里，但这是个人偏好！
+            // 你也可以在这里导入`swap`！
+            self,
+            // `MaybeUninit` is the only way to represent a value that's possibly
+            // uninitialized without causing instant UB with `std::mem::uninitialized`
+            // or `std::mem::zeroed`. There's more info in the docs here:
+            // https://doc.rust-lang.org/stable/std/mem/union.MaybeUninit.html#initialization-invariant
+            //
+            // We need this so that we can have an UnsafeCell with nothing inside it
+            // until we initialize it once and only once without causing UB and
+            // having nasal demons come steal random data and give everyone a bad
+            // time.
+            // `MaybeUninit`是表示可能未初始化的值的唯一方法，而不会导致使用`std::mem::uninitialized`或`std::mem::zeroed`立即产生UB。
+            // 有关更多信息，请参阅文档：https://doc.rust-lang.org/stable/std/mem/union.MaybeUninit.html#initialization-invariant
+            // 我们需要使用它来保证一个没有任何数据的UnsafeCell不会导致UB，直到我们对其进行唯一一次初始化。
+            MaybeUninit,
+        },
+        // Sometimes you need to make sure that something is done once and
+        // only once. We also might want to make sure that no matter on what
+        // thread this holds true. Enter `Once`, a really great synchronization
+        // type that's around for just this purpose. It also has the nice
+        // property that if, say, it gets called to be initialized across many
+        // threads that it only runs the initialization function once and has
+        // the other threads wait until it's done before letting them continue
+        // with their execution.
+        // 有时你需要确保某件事只做一次。我们还可能希望确保无论在哪个线程上都是如此。`Once`，一个非常好的同步类型，就是为了这个目的。
+        // 它还具有一个很好的属性，即如果它被用在多线程同时初始化时，则它保证只运行初始化函数一次，并且其他线程等待直到它完成，然后才让它们继续执行。
+        //
+        // `Once` blocks the whole thread while it waits though, which is the
+        // last thing we want to do inside of a task. For `OnceCell` we keep
+        // track of the initialization ourselves behind a `Mutex` instead and
+        // park waiting tasks by holding on to their `Waker`s.
+        // 但是`Once`在等待时会阻塞整个线程，这是我们在任务中最不想做的事情。
+        // 对于`OnceCell`，我们在`Mutex`后面自己记录初始化的状态，并通过持有等待任务的`Waker`来让它们挂起。
+        sync::{Mutex, Once},
+        task::{Poll, Waker},
+    };
+
+    /// We want to have a static value that's set at runtime and this executor will
+    /// only use libstd. As of 10/26/21, the lazy types in std are still only on
+    /// nightly and we can't use another crate, so crates like `once_cell` and
+    /// `lazy_static` are also out. Thus, we create our own Lazy type so that it will
+    /// calculate the value only once and only when we need it.
+    /// 我们想要一个在运行时设置的静态值，而且这个执行器只使用标准库。
+    /// 截至2021年10月26日，std中的延迟类型仍然只在夜间版中，我们不能使用另一个crate，因此像`once_cell`和`lazy_static`这样的crate也不行。
+    /// 因此，我们创建自己的Lazy类型，以便它只计算一次值，并且仅在我们需要时才计算。
+    pub struct Lazy<T> {
+        /// `Once` is a neat synchronization primitive that we just talked about
+        /// and this is where we need it! We want to make sure we only write into
+        /// the value of the Lazy type once and only once. Otherwise we'd have some
+        /// really bad things happen if we let static values be mutated. It'd break
+        /// thread safety!
+        /// Once是一个很好的同步原语，我们刚刚谈到了它，这就是我们需要它的地方！我们想确保我们只将值写入Lazy类型一次且仅一次。
+        /// 否则，如果我们让静态值被改变，就会发生一些非常糟糕的事情。它会破坏线程安全！
+        once: Once,
+        /// The cell is where we hold our data. The use of `UnsafeCell` is what lets
+        /// us sidestep Rust's guarantees, provided we actually use it correctly and
+        /// still uphold those guarantees. Rust can't always validate that
+        /// everything is safe, even if it is, and so the flexibility it provides
+        /// with certain library types and unsafe code lets us handle those cases
+        /// where the compiler cannot possibly understand it's okay. We also use the
+        /// `MaybeUninit` type here to avoid undefined behavior with uninitialized
+        /// data. We'll need to drop the inner value ourselves though to avoid
+        /// memory leaks because data may not be initialized and so the type won't
+        /// call drop when it's not needed anymore. We could get away with not doing
+        /// it though since we're only using it for static values, but let's be
+        /// thorough here!
+        /// cell是我们保存数据的地方。使用`UnsafeCell`是让我们绕过Rust的保证限制，只要我们正确使用它并且仍然遵守这些保证。
+        /// Rust不总是能够确认程序是安全的，即使它确实是安全的，因此UnSafe代码提供的灵活性使我们能够处理编译器无法理解的情况。
+        /// 我们还在这里使用`MaybeUninit`类型来避免未初始化数据的未定义行为。但是我们需要自己丢弃内部值，以避免内存泄漏，因为数据可能未初始化，
+        /// 因此`MaybeUninit`不会在不再需要时调用drop。虽然我们只使用它来保存静态值，但我们还是要彻底地做到这一点！
+        cell: UnsafeCell<MaybeUninit<T>>,
+    }
+
+    impl<T> Lazy<T> {
+        /// We must construct the type using a const fn so that it can be used in
+        /// `static` contexts. The nice thing is that all of the function calls we
+        /// make here are also const and so this will just work. The compiler will
+        /// figure it all out and make sure the `Lazy` static value exists in our
+        /// final binary.
+        /// 我们必须使用const fn来构造类型，以便它可以在`static`上下文中使用。
+        /// 好的是，我们在这里调用的所有函数都是const的，因此这将正常工作。
+        /// 编译器会弄清楚一切，并确保`Lazy`静态值存在于我们的最终二进制文件中。
+        pub const fn new() -> Self {
+            Self {
+                once: Once::new(),
+                cell: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }
+        /// We want a way to check if we have initialized the value so that we can
+        /// get the value from cell without causing who knows what kind of bad
+        /// things if we read garbage data.
+        /// 我们想要一种方法来检查我们是否已经初始化了值，以便我们可以从cell中获取值，而不会导致我们读取垃圾数据而引起未知的坏事情。
+        fn is_initialized(&self) -> bool {
+            self.once.is_completed()
+        }
+
+        /// This function will either grab a reference to the type or creates it
+        /// with a given function
+        ///
+        pub fn get_or_init(&self, func: fn() -> T) -> &T {
+            self.once.call_once(|| {
+                // /!\ SAFETY /!\: We only ever write to the cell once
+                //
+                // We first get a `*mut MaybeUninit` to the cell and turn it into a
+                // `&mut MaybeUninit`. That's when we call `write` on `MaybeUninit`
+                // to pass the value of the function into the now initialized
+                // `MaybeUninit`.
+                // 这是安全的！我们只会将值写入一次。
+                // 我们首先获取一个`*mut MaybeUninit`到cell，并将其转换为`&mut MaybeUninit`。
+                // 这时我们调用`MaybeUninit`上的`write`将函数的值传递到现在初始化的`MaybeUninit`。
+                (unsafe { &mut *self.cell.get() }).write(func());
+            });
+            // /!\ SAFETY /!\: We already made sure `Lazy` was initialized with our call to
+            // `call_once` above
+            //
+            // We now want to actually retrieve the value we wrote so that we can
+            // use it! We get the `*mut MaybeUninit` from the cell and turn it into
+            // a `&MaybeUninit` which then lets us call `assume_init_ref` to get
+            // the `&T`. This function - much like `get` - is also unsafe, but since we
+            // know that the value is initialized it's fine to call this!
+            // 这是安全的！我们已经确保`Lazy`已经初始化了。
+            // 我们现在想要实际获取我们写入的值，以便我们可以使用它！
+            // 我们从cell中获取`*mut MaybeUninit`并将其转换为`&MaybeUninit`，然后我们就可以调用`assume_init_ref`来获取`&T`。
+            // `assume_init_ref`这个函数 - 就像`get`一样 - 也是不安全的，但是由于我们知道值已经初始化，所以可以调用这个函数！
+            unsafe { (*self.cell.get()).assume_init_ref() }
+        }
+    }
+
+    /// `Default` just hands out the same uninitialized `Lazy` that `new` does.
+    /// `Default`只是返回与`new`相同的未初始化的`Lazy`。
+    impl<T> Default for Lazy<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// We now need to implement `Drop` by hand specifically because `MaybeUninit`
+    /// will need us to drop the value it holds by ourselves only if it exists. We
+    /// check if the value exists, swap it out with an uninitialized value and then
+    /// change `MaybeUninit<T>` into just a `T` with a call to `assume_init` and
+    /// then call `drop` on `T` itself
+    /// 我们现在需要手动实现`Drop`，特别是因为`MaybeUninit`需要我们自己丢弃它所持有的值，只有当它存在时才需要这样做。
+    /// 我们检查值是否存在，将其与未初始化的值交换，然后将`MaybeUninit<T>`转换为`T`，并通过调用`assume_init`来改变`T`，然后调用`T`上的`drop`。
+    impl<T> Drop for Lazy<T> {
+        fn drop(&mut self) {
+            if self.is_initialized() {
+                let old = mem::replace(unsafe { &mut *self.cell.get() }, MaybeUninit::uninit());
+                drop(unsafe { old.assume_init() });
+            }
+        }
+    }
+
+    /// Now you might be asking yourself why we are implementing these traits by
+    /// hand and also why it's unsafe to do so. `UnsafeCell`is the big reason here
+    /// and you can see this by commenting these two lines and trying to compile the
+    /// code. Because of how auto traits work then if any part is not `Send` and
+    /// `Sync` then we can't use `Lazy` for a static. Note that auto traits are a
+    /// compiler specific thing where if everything in a type implements a trait
+    /// then that type also implements it. `Send` and `Sync` are great examples of
+    /// this where any type becomes `Send` and/or `Sync` if all its types implement
+    /// them too! `UnsafeCell` specifically implements !Sync and since it is not
+    /// `Sync` then it can't be used in a `static`. We can override this behavior
+    /// though by implementing these traits for `Lazy` here though. We're saying
+    /// that this is okay and that we uphold the invariants to be `Send + Sync`. We
+    /// restrict it though and say that this is only the case if the type `T`
+    /// *inside* `Lazy` is `Sync` only if `T` is `Send + Sync`. We know then that
+    /// this is okay because the type in `UnsafeCell` can be safely referenced
+    /// through an `&'static` and that the type it holds is also safe to use across
+    /// threads. This means we can set `Lazy` as `Send + Sync` even though the
+    /// internal `UnsafeCell` is !Sync in a safe way since we upheld the invariants
+    /// for these traits.
+    /// 现在你可能会问自己为什么我们要手动实现这些trait，以及为什么这样做是不安全的。
+    /// `UnsafeCell`是这里的一个重要原因，你可以通过注释掉这两行并尝试编译代码来看到这一点。
+    /// 由于自动trait的工作方式，如果任何部分不是`Send`和`Sync`，那么我们就不能在静态变量中使用`Lazy`。
+    /// 请注意，自动trait是编译器特定的东西，如果类型中的所有部分都实现了trait，那么该类型也会实现该trait。
+    /// `Send`和`Sync`就是很好的例子，如果类型中所有部分都实现了它们，那么任何类型也都会变成`Send`和/或`Sync`。
+    /// `UnsafeCell`特别实现了!Sync，因为它不是`Sync`，所以它不能在`static`中使用。
+    /// 我们可以通过在这里为`Lazy`实现这些trait来覆盖此行为。
+    /// 也就是说，我们确认只有在`Lazy`内部的类型`T`是`Sync`时才是`Send + Sync`，以此保证了`Send + Sync`的不变量。
+    /// 我们知道这是可以的，因为`UnsafeCell`中的类型可以通过`&'static`安全地引用，并且它所持有的类型也可以安全地跨线程使用。
+    /// 这意味着我们可以在安全的方式中将`Lazy`设置为`Send + Sync`，即使内部的`UnsafeCell`是!Sync，因为我们保证了这些trait的不变量。
+    unsafe impl<T: Send> Send for Lazy<T> {}
+
+    unsafe impl<T: Send + Sync> Sync for Lazy<T> {}
+
+    /// Where a `OnceCell` is in its life. It starts out empty, then one task
+    /// starts initializing it while every other task that wants the value
+    /// leaves its `Waker` behind, and finally the value is there for everyone.
+    /// `OnceCell`所处的生命阶段。它一开始是空的，然后一个任务开始初始化它，
+    /// 而其他所有想要这个值的任务都留下它们的`Waker`，最终这个值对所有人都可用。
+    enum OnceState {
+        Uninit,
+        Initializing(Vec<Waker>),
+        Init,
+    }
+
+    /// `Lazy` is great for a `static`, but it runs a plain `fn` and blocks the
+    /// thread while another thread is initializing it. `OnceCell` is the async
+    /// version you can keep in a struct and share between tasks: the value is
+    /// created by awaiting a future, only one task ever runs that future at a
+    /// time, and every other task awaiting `get_or_init` in the meantime just
+    /// yields until it's done and then gets the very same `&T`. It uses the same
+    /// `UnsafeCell<MaybeUninit<T>>` trick as `Lazy` to hold the value, but its
+    /// state machine lives behind a `Mutex` rather than a `Once`.
+    /// `Lazy`非常适合`static`，但它运行的是一个普通的`fn`，并且在另一个线程初始化它时会阻塞当前线程。
+    /// `OnceCell`是一个可以放在结构体中并在任务之间共享的异步版本：值是通过await一个future来创建的，
+    /// 同一时间只有一个任务会运行这个future，而在此期间其他所有await`get_or_init`的任务只会让出执行权，直到初始化完成，然后得到同一个`&T`。
+    /// 它使用和`Lazy`相同的`UnsafeCell<MaybeUninit<T>>`技巧来保存值，但它的状态机放在`Mutex`后面而不是`Once`中。
+    pub struct OnceCell<T> {
+        state: Mutex<OnceState>,
+        cell: UnsafeCell<MaybeUninit<T>>,
+    }
+
+    impl<T> OnceCell<T> {
+        /// Creates an empty cell. Just like `Lazy::new` this is const.
+        /// 创建一个空的cell。和`Lazy::new`一样，这是const的。
+        pub const fn new() -> Self {
+            Self {
+                state: Mutex::new(OnceState::Uninit),
+                cell: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }
+
+        /// Gets the value if it's been initialized already.
+        /// 如果值已经初始化了，就获取它。
+        pub fn get(&self) -> Option<&T> {
+            match *self.state.lock().unwrap() {
+                // /!\ SAFETY /!\: The state only becomes `Init` after the value
+                // was written and it's never written again.
+                // 这是安全的！状态只有在值被写入之后才会变成`Init`，而且它永远不会被再次写入。
+                OnceState::Init => Some(unsafe { (*self.cell.get()).assume_init_ref() }),
+                _ => None,
+            }
+        }
+
+        /// Gets the value, running `init` to create it if no one has yet. If
+        /// another task is already in the middle of initializing the cell, we
+        /// wait for it to finish rather than running `init` a second time. If
+        /// that task gets dropped part way through, the cell goes back to being
+        /// empty and one of the waiting tasks takes over with its own `init`.
+        /// 获取值，如果还没有人创建它，就运行`init`来创建。如果另一个任务正在初始化这个cell，
+        /// 我们会等待它完成，而不是再运行一次`init`。如果那个任务在中途被丢弃了，
+        /// cell会变回空的状态，其中一个等待的任务会用它自己的`init`接手初始化。
+        pub async fn get_or_init<F, Fut>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> Fut,
+            Fut: Future<Output = T>,
+        {
+            let mut init = Some(init);
+            loop {
+                let initialize = poll_fn(|cx| {
+                    let mut state = self.state.lock().unwrap();
+                    match &mut *state {
+                        OnceState::Init => Poll::Ready(false),
+                        OnceState::Uninit => {
+                            *state = OnceState::Initializing(Vec::new());
+                            Poll::Ready(true)
+                        }
+                        OnceState::Initializing(wakers) => {
+                            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                                wakers.push(cx.waker().clone());
+                            }
+                            Poll::Pending
+                        }
+                    }
+                })
+                .await;
+                if initialize {
+                    // Should this future get dropped while `init` is still
+                    // running, `reset` puts the cell back to `Uninit` so that
+                    // the tasks waiting on us aren't stuck forever.
+                    // 如果这个future在`init`还在运行时被丢弃了，`reset`会把cell恢复为`Uninit`，这样等待我们的任务就不会永远卡住。
+                    let reset = Reset(self);
+                    let value = (init.take().unwrap())().await;
+                    // /!\ SAFETY /!\: We're the only ones who moved the state to
+                    // `Initializing` and so the only ones allowed to write.
+                    // 这是安全的！只有我们把状态改成了`Initializing`，所以只有我们被允许写入。
+                    unsafe { (*self.cell.get()).write(value) };
+                    std::mem::forget(reset);
+                    self.finish(OnceState::Init);
+                }
+                if let Some(value) = self.get() {
+                    return value;
+                }
+            }
+        }
+
+        /// Moves the cell into its next state and wakes every task that was
+        /// waiting on the initialization.
+        /// 把cell转到下一个状态，并唤醒所有在等待初始化的任务。
+        fn finish(&self, next: OnceState) {
+            let old = std::mem::replace(&mut *self.state.lock().unwrap(), next);
+            if let OnceState::Initializing(wakers) = old {
+                wakers.into_iter().for_each(Waker::wake);
+            }
+        }
+    }
+
+    /// Puts a `OnceCell` back to `Uninit` if the initializing future is
+    /// dropped before it finished.
+    /// 如果初始化的future在完成之前被丢弃了，就把`OnceCell`恢复为`Uninit`。
+    struct Reset<'a, T>(&'a OnceCell<T>);
+
+    impl<T> Drop for Reset<'_, T> {
+        fn drop(&mut self) {
+            self.0.finish(OnceState::Uninit);
+        }
+    }
+
+    impl<T> Default for OnceCell<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Just like for `Lazy` we need to drop the value ourselves if it's there.
+    /// 和`Lazy`一样，如果值存在的话，我们需要自己丢弃它。
+    impl<T> Drop for OnceCell<T> {
+        fn drop(&mut self) {
+            if let OnceState::Init = *self.state.get_mut().unwrap() {
+                unsafe { self.cell.get_mut().assume_init_drop() };
+            }
+        }
+    }
+
+    // The same reasoning as for `Lazy` applies here. We only ever hand out a
+    // `&T` once the value is written, so sharing the cell is fine as long as
+    // `T` itself can be shared and sent.
+    // 这里的理由和`Lazy`一样。我们只有在值写入之后才会交出`&T`，所以只要`T`本身可以被共享和发送，共享这个cell就是没问题的。
+    unsafe impl<T: Send> Send for OnceCell<T> {}
+
+    unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+    #[test]
+    /// Many tasks racing to initialize the same cell should only ever run the
+    /// initializing future once and all see the same value.
+    /// 许多任务竞争初始化同一个cell时，应该只运行一次初始化future，并且都看到同一个值。
+    fn once_cell_initializes_once() {
+        use crate::{futures::Sleep, runtime};
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc,
+        };
+        use std::time::Duration;
+
+        let cell = Arc::new(OnceCell::new());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..5 {
+            let cell = cell.clone();
+            let runs = runs.clone();
+            let tx = tx.clone();
+            runtime::spawn(async move {
+                let value = *cell
+                    .get_or_init(|| async {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        Sleep::new(50).await;
+                        42
+                    })
+                    .await;
+                tx.send(value).unwrap();
+            });
+        }
+        for _ in 0..5 {
+            assert_eq!(rx.recv_timeout(Duration::from_secs(30)).unwrap(), 42);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(cell.get(), Some(&42));
+    }
+}
+
+pub mod runtime {
+    use crate::futures::{
+        deadline_in,
+        sync::{mpsc, oneshot},
+        BoxFuture, Elapsed, FutureExt, Sleep, XorShift,
+    };
+    use debug::Arena;
+    use std::time::{Duration, Instant};
+    use std::{
+        // `Cell` is the simpler sibling of `RefCell` for values we only ever
+        // move in and out whole, like the `Parker` each thread keeps around.
+        // `Cell`是`RefCell`更简单的兄弟，用于那些我们只会整个移入移出的值，比如每个线程保留的`Parker`。
+        cell::Cell,
+        // `RefCell` lets us swap out which runtime is the current one for a
+        // thread without needing `&mut` access to the thread local holding it.
+        // `RefCell`让我们可以替换线程的当前运行时，而不需要对持有它的线程局部变量有`&mut`访问权限。
+        cell::RefCell,
+        // `Reverse` flips the ordering of whatever it wraps. We'll need this
+        // for the timer since `BinaryHeap` always hands back its *largest*
+        // value first and we want the *earliest* deadline.
+        // `Reverse`会反转它所包装的值的顺序。定时器需要用到它，因为`BinaryHeap`总是先返回*最大*的值，而我们想要*最早*的截止时间。
+        cmp::Reverse,
+        // We need a place to put the futures that get spawned onto the runtime
+        // somewhere and while we could use something like a `Vec`, we chose a
+        // `LinkedList` here. One reason being that we can put tasks at the front of
+        // the queue if they're a blocking future. The other being that we use a
+        // constant amount of memory. We only ever use as much as we need for tasks.
+        // While this might not matter at a small scale, this does at a larger
+        // scale. If your `Vec` never gets smaller and you have a huge burst of
+        // tasks under, say, heavy HTTP loads in a web server, then you end up eating
+        // up a lot of memory that could be used for other things running on the
+        // same machine. In essence what you've created is a kind of memory leak
+        // unless you make sure to resize the `Vec`. @mycoliza did a good Twitter
+        // thread on this here if you want to learn more!
+        //
+        // https://twitter.com/mycoliza/status/1298399240121544705
+        //
+        // That said a `LinkedList` frees each node as soon as we pop it, so it
+        // never holds on to memory it doesn't need. The cost we pay instead is
+        // an allocation for every push and a free for every pop, and we push a
+        // task every single time it gets woken up. Under those same bursty loads
+        // that's a lot of churn for the allocator! We'll see below how
+        // `TaskQueue` keeps a small pool of spare nodes around to reuse.
+        // 不过`LinkedList`在弹出节点时就会立即释放它，所以它从不持有不需要的内存。
+        // 我们付出的代价是每次push都要分配一次内存，每次pop都要释放一次，而任务每次被唤醒都会被push一次。
+        // 在同样的突发负载下，这对分配器来说是大量的开销！我们会在下面看到`TaskQueue`如何保留一小池空闲节点来复用。
+        //
+        // The `BinaryHeap` and `HashMap` are for the timer, which keeps track
+        // of which sleeping task needs to be woken up next.
+        // `BinaryHeap`和`HashMap`是给定时器用的，定时器记录着下一个需要被唤醒的睡眠任务。
+        collections::{BinaryHeap, HashMap, LinkedList, VecDeque},
+        // For writing out `Debug` output of our tasks.
+        // 用于输出我们任务的`Debug`信息。
+        fmt,
+        // A Future is the fundamental block of any async executor. It is a trait
+        // that types can make or an unnameable type that an async function can
+        // make. We say it's unnameable because you don't actually define the type
+        // anywhere and just like a closure you can only specify its behavior with
+        // a trait. You can't give it a name like you would when you do something
+        // like `pub struct Foo;`. These types, whether nameable or not, represent all
+        // the state needed to have an asynchronous function. You poll the future to
+        // drive its computation along like a state machine that makes transistions
+        // from one state to another till it finishes. If you reach a point where it
+        // would yield execution, then it needs to be rescheduled to be polled again
+        // in the future. It yields though so that you can drive other futures
+        // forward in their computation!
+        //
+        // This is the important part to understand here with the executor: the
+        // Future trait defines the API we use to drive forward computation of it,
+        // while the implementor of the trait defines how that computation will work
+        // and when to yield to the executor. You'll see later that we have an
+        // example of writing a `Sleep` future by hand as well as unnameable async
+        // code using `async { }` and we'll expand on when those yield and what it
+        // desugars to in practice. We're here to demystify the mystical magic of
+        // async code.
+        future::{poll_fn, Future},
+        // Ah Pin. What a confusing type. The best way to think about `Pin` is that
+        // it records when a value became immovable or pinned in place. `Pin` doesn't
+        // actually pin the value, it just notes that the value will not move, much
+        // in the same way that you can specify Rust lifetimes. It only records what
+        // the lifetime already is, it doesn't actually create said lifetime! At the
+        // bottom of this, I've linked some more in depth reading on Pin, but if you
+        // don't know much about Pin, starting with the standard library docs isn't a
+        // bad place.
+        //
+        // Note: Unpin is also a confusing name and if you think of it as
+        // MaybePinned you'll have a better time as the value may be pinned or it
+        // may not be pinned. It just marks that if you have a Pinned value and it
+        // moves that's okay and it's safe to do so, whereas for types that do not
+        // implement Unpin and they somehow move, will cause some really bad things
+        // to happen since it's not safe for the type to be moved after being
+        // pinned. We create our executor with the assumption that every future we
+        // get will need to be a pinned value, even if it is actually Unpin. This
+        // makes it nicer for everyone using the executor as it's very easy to make
+        // types that do not implement Unpin.
+        pin::Pin,
+        sync::{
+            // What's not to love about Atomics? This lets us have thread safe
+            // access to primitives so that we can modify them or load them using
+            // Ordering to tell the compiler how it should handle giving out access
+            // to the data. Atomics are a rather deep topic that's out of scope for
+            // this. Just note that we want to change a usize safely across threads!
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+            // Arc is probably one of the more important types we'll use in the
+            // executor. It lets us freely clone cheap references to the data which
+            // we can use across threads while making it easy to not have to worry about
+            // complicated lifetimes since we can easily own the data with a call to
+            // clone. It's one of my favorite types in the standard library.
+            Arc,
+            // A `Barrier` holds up a group of threads until all of them have
+            // got to it, which is how `build` waits for its workers to start.
+            // `Barrier`会拦住一组线程，直到它们全部都到达了它，`build`就是这样等待它的工作线程启动的。
+            Barrier,
+            // A `Condvar` lets a thread sleep until another one tells it that
+            // something it cares about changed. It's what our `Parker` uses so
+            // that idle threads don't have to spin.
+            // `Condvar`让一个线程可以睡觉，直到另一个线程告诉它它关心的东西发生了变化。我们的`Parker`用它来让空闲的线程不必空转。
+            Condvar,
+            // Normally I would use `parking_lot` for a Mutex, but the goal is to
+            // use stdlib only. A personal gripe is that it cares about Mutex
+            // poisoning (when a thread panics with a hold on the lock), which is
+            // not something I've in practice run into (others might!) and so calling
+            // `lock().unwrap()` everywhere can get a bit tedious. That being said
+            // Mutexes are great. You make sure only one thing has access to the data
+            // at any given time to access or change it.
+            Mutex,
+            // A `OnceLock` gets set exactly once. The timer thread goes in one
+            // so that whoever adds an earlier deadline can wake it up.
+            // `OnceLock`只会被设置恰好一次。定时器线程被放在一个里面，这样添加了更早截止时间的人就可以唤醒它。
+            OnceLock,
+            // A `Mutex` gets poisoned when something panics while holding it,
+            // and `PoisonError` lets us get at what's inside anyway.
+            // 当某个东西在持有`Mutex`时panic了，它就会被毒化，而`PoisonError`让我们无论如何都能拿到里面的东西。
+            PoisonError,
+            // A `Weak` is an `Arc` that doesn't keep what it points to alive.
+            // `Weak`是一个不会让它所指向的东西保持存活的`Arc`。
+            Weak,
+        },
+        // The task module contains all of the types and traits related to
+        // having an executor that can create and run tasks that are `Futures`
+        // that need to be polled.
+        task::{
+            // `Context` is passed in every call to `poll` for a `Future`. We
+            // didn't use it in our `Sleep` one, but it has to be passed in
+            // regardless. It gives us access to the `Waker` for the future so
+            // that we can call it ourselves inside the future if need be!
+            Context,
+            // Poll is the enum returned from when we poll a `Future`. When we
+            // call `poll`, this drives the `Future` forward until it either
+            // yields or it returns a value. `Poll` represents that. It is
+            // either `Poll::Pending` or `Poll::Ready(T)`. We use this to
+            // determine if a `Future` is done or not and if not, then we should
+            // keep polling it.
+            Poll,
+            // `RawWaker` and `RawWakerVTable` are what a `Waker` really is on
+            // the inside: a pointer to some data and a table of functions that
+            // know what to do with it. `Wake` builds them for us, but we build
+            // our own so that we know what the pointer points to.
+            // `RawWaker`和`RawWakerVTable`是`Waker`内部真正的样子：一个指向某些数据的指针，以及一张知道如何处理这些数据的函数表。
+            // `Wake`会为我们构建它们，但我们自己构建，这样我们就知道这个指针指向的是什么。
+            RawWaker,
+            RawWakerVTable,
+            // This is a trait to define how something in an executor is woken
+            // up. We implement it for `Task` which is what lets us create a
+            // `Waker` from it, to then make a `Context` which can then be
+            // passed into the call to `poll` on the `Future` inside the `Task`.
+            Wake,
+            // A `Waker` is the type that has a handle to the runtime to let it
+            // know when a task is ready to be scheduled for polling. The
+            // simplest version would be that as soon as a `Task` is done
+            // polling we tell the executor to wake it, but then we'd just be
+            // spinning on every pending task. Instead we do what you'd want to
+            // do when creating a `Future` and only wake when it would be ready
+            // to poll, such as a timer completing, or listening for some kind
+            // of signal from the OS.
+            // It's kind of up to the executor how it wants to do it. Maybe how
+            // it schedules things is different or it has special behavior for
+            // certain `Future`s that it ships with it. The key thing to note
+            // here is that this is how tasks are supposed to be rescheduled for
+            // polling.
+            Waker,
+        },
+        // Our workers are plain OS threads and we keep their `JoinHandle`s so
+        // that we can wait for them to finish when a runtime is dropped.
+        // 我们的工作线程就是普通的操作系统线程，我们保留它们的`JoinHandle`，以便在运行时被丢弃时等待它们结束。
+        thread,
+    };
+
+    /// This is it, the thing we've been alluding to for most of this file. It's
+    /// the `Runtime`! What is it? What does it do? Well the `Runtime` is what
+    /// actually drives our async code to completion. Remember asynchronous code
+    /// is just code that gets run for a bit, yields part way through the
+    /// function, then continues when polled and it repeats this process till
+    /// being completed. In reality what this means is that the code is run
+    /// using synchronous functions that drive tasks in a concurrent manner.
+    /// They could also be run concurrently and/or in parallel if the executor
+    /// is multithreaded. Tokio is a good example of this model where it runs
+    /// tasks in parallel on separate threads and if it has more tasks than
+    /// threads, it runs them concurrently on those threads.
+    ///
+    /// 这就是我们一直在谈论的东西。它是 `Runtime`！
+    /// 它是什么？它能做什么？好吧，`Runtime` 就是驱动我们的异步代码执行完成的东西！
+    /// 还记得异步代码吗？它只是一些代码，它会运行一段时间，然后通过Sleep函数暂停一段时间，
     /// 然后在轮询时继续运行，然后重复这个过程，直到完成。
     /// 实际上，这意味着代码是使用同步函数运行的，这些函数以并发方式驱动任务。
     /// 如果执行器是多线程的，它们也可以并发运行 和/或 并行运行。
     /// Tokio 就是这种模型的一个很好的例子，它在不同的线程上并行运行任务，
     /// 如果任务数多于线程数，它会在这些线程上并发运行它们。
     ///
-    /// Our `Runtime` in particular has:
-    pub(crate) struct Runtime {
-        /// A queue to place all of the tasks that are spawned on the runtime.
-        /// 一个队列，用于放置在运行时上生成的所有任务。
-        queue: Queue,
-        /// A `Spawner` which can spawn tasks onto our queue for us easily and
-        /// lets us call `spawn` and `block_on` with ease.
-        /// 一个 `Spawner`，它可以轻松地将任务放入我们的队列中，让我们可以轻松地调用 `spawn` 和 `block_on`。
+    /// Our `Runtime` in particular has:
+    pub struct Runtime {
+        /// Everything that the worker threads and the tasks spawned onto the
+        /// runtime need to get at as well. We keep it behind an `Arc` so that
+        /// each of them can hold on to it for as long as they need.
+        /// 工作线程和生成到运行时上的任务也需要访问的所有东西。我们把它放在`Arc`后面，这样它们每个都可以按需持有它。
+        shared: Arc<Shared>,
+        /// The worker threads that poll our tasks. We hold on to them so that
+        /// we can wait for them to stop when the `Runtime` is dropped.
+        /// poll我们任务的工作线程。我们持有它们，以便在`Runtime`被丢弃时等待它们停止。
+        workers: Vec<thread::JoinHandle<()>>,
+        /// The thread that fires the timer, which we wait for along with the
+        /// workers.
+        /// 触发定时器的线程，我们会和工作线程一起等待它。
+        timer_thread: Option<thread::JoinHandle<()>>,
+    }
+
+    /// The state of a `Runtime` that's shared with its worker threads and
+    /// every task spawned onto it:
+    /// `Runtime`与它的工作线程以及生成到它上面的每个任务共享的状态：
+    pub(crate) struct Shared {
+        /// A queue to place all of the tasks that are spawned on the runtime.
+        /// 一个队列，用于放置在运行时上生成的所有任务。
+        queue: Mutex<TaskQueue>,
+        /// A counter for how many Tasks are on the runtime. We use this in
+        /// conjunction with `wait` to block until there are no more tasks on
+        /// the executor.
+        /// 一个计数器，用于记录运行时上有多少任务。
+        /// 我们将其与 `wait` 结合使用，以阻塞直到执行器上没有更多任务。
+        ///
+        /// It goes up exactly once in `Task::new` and down exactly once in
+        /// `release_task`, which `Task::drop` calls, so it can never go below
+        /// zero. That holds for a task `spawn_eager` polls on the calling
+        /// thread too, since it's a `Task` like any other. If the count ever
+        /// did go below zero it would wrap around to a huge number and `wait`
+        /// would hang forever, so debug builds check for that in
+        /// `release_task` rather than let it happen quietly.
+        /// 它在`Task::new`中恰好增加一次，在`Task::drop`调用的`release_task`中恰好减少一次，所以它永远不可能低于零。
+        /// 对于`spawn_eager`在调用线程上poll的任务也是这样，因为它和其他任何任务一样是一个`Task`。
+        /// 如果计数真的低于零了，它会绕回成一个巨大的数字，而`wait`会永远挂起，所以调试构建会在`release_task`中检查这一点，而不是让它悄悄地发生。
+        tasks: AtomicUsize,
+        /// The timer that wakes up sleeping tasks when their deadline passes.
+        /// 在截止时间到达时唤醒睡眠任务的定时器。
+        timer: Mutex<Timer>,
+        /// The thread that sleeps until the next deadline on `timer` and
+        /// fires it. It gets set right after it's started, before anyone can
+        /// spawn anything.
+        /// 睡到`timer`上的下一个截止时间然后触发它的线程。它在启动之后马上就会被设置，在任何人能生成任何东西之前。
+        timer_thread: OnceLock<thread::Thread>,
+        /// Set once the `Runtime` is dropped to tell the workers to stop.
+        /// 一旦`Runtime`被丢弃就会被设置，用来通知工作线程停止。
+        shutdown: AtomicBool,
+        /// The most tasks that can be alive on the runtime at once, if there's
+        /// a limit.
+        /// 运行时上同时可以存活的最多任务数量，如果有限制的话。
+        max_tasks: Option<usize>,
+        /// The most new tasks that can be waiting on the queue at once, if
+        /// there's a limit, and what to do about a spawn past that.
+        /// 同时可以在队列上等待的新任务最多有多少个，如果有限制的话，以及对超过这个数量的生成该怎么办。
+        queue_capacity: Option<usize>,
+        overflow_policy: OverflowPolicy,
+        /// Goes off whenever a task comes off the queue, for spawns that are
+        /// waiting on `OverflowPolicy::Block`.
+        /// 每当一个任务离开队列时就会触发，给那些因为`OverflowPolicy::Block`而在等待的生成用的。
+        room: Condvar,
+        /// Set by `shutdown_timeout` once we stop taking new tasks.
+        /// 一旦我们不再接受新任务，就会被`shutdown_timeout`设置。
+        closed: AtomicBool,
+        /// Every task on the runtime that's still alive, so that we can find
+        /// them again to abort them, even the ones that aren't on the queue
+        /// because they're waiting on something.
+        /// 运行时上每一个仍然存活的任务，这样我们就可以再找到它们并中止它们，即使是那些因为在等待某些东西而不在队列中的任务。
+        registry: Mutex<HashMap<TaskId, Weak<Task>>>,
+        /// Where woken `High` and `Low` priority tasks wait. Everyone else,
+        /// new tasks included, is on `queue`.
+        /// 被唤醒的`High`和`Low`优先级任务在这里等待。其他所有人，包括新任务，都在`queue`上。
+        high: Mutex<VecDeque<Arc<Task>>>,
+        low: Mutex<VecDeque<Arc<Task>>>,
+        /// How many polls in a row each of `high`, `queue` and `low` gets
+        /// before it's the next one's turn, set with
+        /// `Builder::priority_weights`.
+        /// `high`、`queue`和`low`每一个在轮到下一个之前连续得到多少次poll，用`Builder::priority_weights`设置。
+        weights: [usize; 3],
+        /// Whose turn it is.
+        /// 轮到谁了。
+        turn: Mutex<Turn>,
+        /// Whoever wants to hear about what happens to the tasks on this
+        /// runtime, if anyone.
+        /// 想要知道这个运行时上的任务发生了什么的观察者，如果有的话。
+        observer: Option<Arc<dyn Observer>>,
+        /// Where workers with nothing to do go to sleep. Putting a task on
+        /// the queue or a new earliest deadline on the timer wakes one up.
+        /// 没事可做的工作线程睡觉的地方。把任务放到队列上，或者在定时器上放一个新的最早截止时间，都会唤醒其中一个。
+        work: Arc<Parker>,
+        /// Where `wait` goes to sleep until the last task is gone.
+        /// `wait`睡觉直到最后一个任务消失的地方。
+        idle: Parker,
+        /// The `Waker`s of the tasks waiting on `notified`, so that a
+        /// `WakeNotifier` can find them.
+        /// 正在等待`notified`的任务的`Waker`，这样`WakeNotifier`就能找到它们。
+        notify: Mutex<HashMap<TaskId, Waker>>,
+        /// How many tasks ran their `Future` to completion.
+        /// 有多少任务把它们的`Future`运行到了完成。
+        completed: AtomicUsize,
+        /// How many tasks went away without finishing, say because they got
+        /// aborted.
+        /// 有多少任务没有完成就消失了，比如因为它们被中止了。
+        cancelled: AtomicUsize,
+        /// How many times a worker found nothing to do and went to sleep.
+        /// 工作线程有多少次发现无事可做然后去睡觉了。
+        parks: AtomicUsize,
+        /// One queue per worker for tasks that have to be polled on that
+        /// worker and nowhere else. A worker looks at its own queue before
+        /// the shared one.
+        /// 每个工作线程一个队列，用于那些必须在这个工作线程上、而不能在其他地方被poll的任务。工作线程会先查看自己的队列，再查看共享的队列。
+        locals: Vec<Mutex<VecDeque<Arc<Task>>>>,
+        /// Set by `Runtime::pause`, while it's set nobody takes anything off
+        /// the queue.
+        /// 由`Runtime::pause`设置，当它被设置时，没有人会从队列中取出任何东西。
+        paused: AtomicBool,
+        /// Cancelled once someone asks the runtime to shut down, for
+        /// `shutdown_signal`.
+        /// 一旦有人要求运行时关闭就会被取消，给`shutdown_signal`用的。
+        shutting_down: CancellationToken,
+        /// How many workers are asleep with nothing to do.
+        /// 有多少工作线程因为无事可做而在睡觉。
+        sleeping: AtomicUsize,
+        /// Whether any worker has polled a task since the last time they all
+        /// went to sleep, so that `on_idle` only goes off once each time.
+        /// 自从上一次所有工作线程都去睡觉以来，是否有工作线程poll过任务，这样`on_idle`每次只会触发一次。
+        busy: AtomicBool,
+        /// What to call once every worker has run out of work, set by
+        /// `Runtime::on_idle`.
+        /// 一旦每个工作线程都没活干了要调用的东西，由`Runtime::on_idle`设置。
+        on_idle: Mutex<Option<Arc<dyn Fn() + Send + Sync>>>,
+        /// What each worker calls when it starts up and right before it
+        /// stops, set with `Builder::on_thread_start` and
+        /// `Builder::on_thread_stop`.
+        /// 每个工作线程在启动时和停止之前调用的东西，用`Builder::on_thread_start`和`Builder::on_thread_stop`设置。
+        on_thread_start: Option<Arc<dyn Fn() + Send + Sync>>,
+        on_thread_stop: Option<Arc<dyn Fn() + Send + Sync>>,
+        /// Where `rng` gets its numbers from, seeded with `Builder::seed`.
+        /// `rng`从这里得到它的数字，用`Builder::seed`设置种子。
+        rng: Mutex<XorShift>,
+        /// Whether blocking tasks get a thread of their own rather than a
+        /// worker, set with `Builder::dedicated_blocking_threads`.
+        /// 阻塞任务是否得到一个自己的线程而不是一个工作线程，用`Builder::dedicated_blocking_threads`设置。
+        dedicated_blocking: bool,
+        /// Whether we watch the async `Mutex` for deadlocks, set with
+        /// `Builder::detect_deadlocks`.
+        /// 我们是否监视异步`Mutex`的死锁，用`Builder::detect_deadlocks`设置。
+        detect_deadlocks: bool,
+        /// What a wake up does, set with `Builder::wake_strategy`.
+        /// 一次唤醒会做什么，用`Builder::wake_strategy`设置。
+        wake_strategy: WakeStrategy,
+        /// Tasks that got woken up under `WakeStrategy::Batched` and are
+        /// waiting for the next tick to put them all on the queue at once.
+        /// 在`WakeStrategy::Batched`下被唤醒、正在等待下一次tick把它们一次性全部放到队列上的任务。
+        woken: Mutex<Vec<Arc<Task>>>,
+        /// How many times something took the queue's lock to put tasks on
+        /// it, which is what batching wake ups saves on.
+        /// 有多少次有东西为了往队列上放任务而拿了队列的锁，这就是把唤醒攒成一批所节省的东西。
+        enqueues: AtomicUsize,
+    }
+
+    /// There's one global runtime that the free functions like `spawn` and
+    /// `block_on` use, created the first time it's needed. You might want to
+    /// have multiple running in production code though. For instance you limit
+    /// what happens on one runtime for a free tier version and let the
+    /// non-free version use as many resources as it can. That's what the
+    /// `Builder` below is for. We implement 4 functions: `start` to actually
+    /// get async code running, `tick` to drive it forward a single step, `get`
+    /// so that we can get references to the global runtime, and `spawner` a
+    /// convenience function to get a `Spawner` to spawn tasks onto the
+    /// `Runtime`. On top of that the same `spawn`, `block_on`, and `wait` you
+    /// can use for the global runtime are there as methods.
+    /// 有一个全局运行时，供`spawn`和`block_on`这样的自由函数使用，它会在第一次被需要时创建。
+    /// 但是在生产代码中，您可能希望有多个运行时。
+    /// 例如，您限制了免费版本上运行时的功能，并让非免费版本可以使用尽可能多的资源。这就是下面的`Builder`的用途。
+    /// 我们实现了4个函数：
+    /// `start` 来实际运行异步代码。
+    /// `tick` 来驱动运行时前进一步。
+    /// `get` 以便我们可以获取对全局运行时的引用。
+    /// `spawner` 一个方便的函数，获取一个 `Spawner` 来将任务放入 `Runtime`。
+    /// 除此之外，可以用于全局运行时的`spawn`、`block_on`和`wait`也都作为方法提供。
+    impl Runtime {
+        /// A runtime of your very own with the default settings, which you
+        /// can have as many of as you like next to the global one. It doesn't
+        /// share anything with any other runtime, and once you drop it its
+        /// workers stop. Use a `Builder` if you want something other than the
+        /// defaults.
+        /// 一个完全属于你自己的、使用默认设置的运行时，除了全局运行时之外你想要多少个都可以。
+        /// 它不和任何其他运行时共享任何东西，一旦你丢弃它，它的工作线程就会停止。如果你想要默认设置以外的东西，请使用`Builder`。
+        pub fn new() -> Self {
+            Builder::new().build()
+        }
+
+        /// This is what actually drives all of our async code. We spawn
+        /// separate threads that loop getting the next task off the queue and
+        /// if it exists polls it or continues if not. It also checks if the
+        /// task should block and if it does it just keeps polling the task
+        /// until it completes! Otherwise it polls the task once and leaves it
+        /// to whatever the task is waiting on to wake it and put it back in
+        /// the queue if it's still pending. Otherwise it drops the task by not
+        /// putting it back into the queue since it's completed. Every worker
+        /// runs this loop on its own, so while one of them is stuck blocking
+        /// on a task the others carry on, and that includes picking up any
+        /// other blocking tasks.
+        /// 这就是实际驱动我们所有异步代码的程序。
+        /// 我们启动单独的线程，在循环中从队列中获取下一个任务，
+        /// 如果存在，则poll它，如果不存在，则继续循环获取任务。
+        /// 获取任务后，检查任务是否应该阻塞，如果是，则只会持续poll该任务，直到任务完成！
+        /// 否则，它会poll一次任务，如果任务仍然未完成，则由任务正在等待的东西负责唤醒它并将其放回队列中。
+        /// 每个工作线程都独立地运行这个循环，所以当其中一个被某个任务阻塞时，其他的会继续工作，包括接手其他的阻塞任务。
+        fn start(shared: &Arc<Shared>, workers: usize) -> Vec<thread::JoinHandle<()>> {
+            // Everyone, us included, waits here until every worker has
+            // called `on_thread_start`.
+            // 所有人，包括我们自己，都在这里等待，直到每个工作线程都调用过`on_thread_start`。
+            let started = Arc::new(Barrier::new(workers + 1));
+            let handles = (0..workers)
+                .map(|index| {
+                    let shared = shared.clone();
+                    let started = started.clone();
+                    thread::spawn(move || {
+                        WORKER.with(|worker| worker.set(Some((Arc::as_ptr(&shared), index))));
+                        if let Some(on_thread_start) = &shared.on_thread_start {
+                            on_thread_start();
+                        }
+                        started.wait();
+                        let mut worked = false;
+                        while !shared.shutdown.load(Ordering::Acquire) {
+                            if shared.tick() {
+                                worked = true;
+                            } else {
+                                shared.go_idle(std::mem::take(&mut worked));
+                                shared.park(None);
+                                shared.sleeping.fetch_sub(1, Ordering::AcqRel);
+                            }
+                        }
+                        if let Some(on_thread_stop) = &shared.on_thread_stop {
+                            on_thread_stop();
+                        }
+                    })
+                })
+                .collect();
+            started.wait();
+            handles
+        }
+
+        /// Starts the thread that fires the timer. It works out when the
+        /// earliest deadline is and sleeps with `park_timeout` until then, or
+        /// with no timeout at all if nothing is sleeping, so it costs nothing
+        /// while it waits. Anyone who adds a deadline that's earlier than the
+        /// one it's sleeping towards unparks it so that it can take another
+        /// look. A wake up that comes early is harmless, it just finds nothing
+        /// expired and goes back to sleep.
+        /// 启动触发定时器的线程。它会算出最早的截止时间是什么时候，然后用`park_timeout`一直睡到那个时候，
+        /// 如果没有人在睡眠就不带超时地睡，所以它在等待时不花费任何东西。任何添加了比它正在等待的截止时间更早的截止时间的人都会unpark它，
+        /// 让它再看一眼。一次提前的唤醒是无害的，它只会发现没有东西到期，然后回去接着睡。
+        fn start_timer(shared: &Arc<Shared>) -> thread::JoinHandle<()> {
+            let shared = shared.clone();
+            let handle = thread::spawn({
+                let shared = shared.clone();
+                move || {
+                    while !shared.shutdown.load(Ordering::Acquire) {
+                        match shared.fire_timers() {
+                            Some(next) => {
+                                thread::park_timeout(next.saturating_duration_since(Instant::now()))
+                            }
+                            None => thread::park(),
+                        }
+                    }
+                }
+            });
+            let _ = shared.timer_thread.set(handle.thread().clone());
+            handle
+        }
+
+        /// This is a single turn of the loop in `start`, pulled out so that
+        /// anyone can drive the runtime one step at a time, say from a REPL or
+        /// a teaching tool that wants to look at what changed between steps.
+        /// It pops exactly one task off the queue and polls it, returning
+        /// whether there was a task to poll at all. A task that's still pending
+        /// isn't lost, it goes back on the queue as soon as whatever it's
+        /// waiting on wakes it up.
+        /// 这是`start`中循环的一次迭代，把它提取出来是为了让任何人都可以一次一步地驱动运行时，
+        /// 比如REPL或者想要观察每一步之间发生了什么变化的教学工具。
+        /// 它从队列中弹出恰好一个任务并poll它，返回是否有任务可以poll。
+        /// 仍然处于pending状态的任务不会丢失，只要它等待的东西唤醒了它，它就会回到队列中。
+        pub fn tick(&self) -> bool {
+            self.shared.tick()
+        }
+
+        /// A function to get a reference to the global `Runtime`, which is
+        /// the one from `set_test_runtime` if a test swapped one in
+        /// 一个获取全局 `Runtime` 引用的函数，如果某个测试换进来了一个运行时，那就是来自 `set_test_runtime` 的那一个
+        pub fn get() -> &'static Runtime {
+            #[cfg(any(test, feature = "test-util"))]
+            // Whatever's in there lives until `clear_test_runtime`, which
+            // nobody calls while this reference is still in use.
+            // 那里面的东西会一直活到`clear_test_runtime`，而在这个引用还在使用的时候没有人会调用它。
+            if let Some(runtime) = unsafe { TEST_RUNTIME.load(Ordering::Acquire).as_ref() } {
+                return runtime;
+            }
+            RUNTIME.get_or_init(setup_runtime)
+        }
+
+        /// A function to get a new `Spawner` from the `Runtime`
+        /// 一个从 `Runtime` 获取新 `Spawner` 的函数
+        pub(crate) fn spawner(&self) -> Spawner {
+            Spawner {
+                shared: self.shared.clone(),
+            }
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime
+        /// 将非阻塞的 `Future` 放入这个运行时
+        pub fn spawn<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn(future)
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime, unless it won't take
+        /// any more tasks
+        /// 将非阻塞的 `Future` 放入这个运行时，除非它不再接受更多任务
+        pub fn try_spawn<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> Result<JoinHandle<T>, SpawnError> {
+            self.spawner().try_spawn(future)
+        }
+
+        /// Spawn a task onto this runtime whose `Future` gets made by calling `f`
+        /// on one of its workers
+        /// 将一个任务放入这个运行时，它的 `Future` 由这个运行时的某个工作线程调用 `f` 来创建
+        pub fn spawn_fn<F, Fut, T>(&self, f: F) -> JoinHandle<T>
+        where
+            F: FnOnce() -> Fut + Send + 'static,
+            Fut: Future<Output = T> + Send + Sync + 'static,
+            T: Send + 'static,
+        {
+            self.spawner().spawn_fn(f)
+        }
+
+        /// Spawn the `Future` that `f` makes out of `state` onto this runtime
+        /// 将 `f` 用 `state` 创建的 `Future` 放入这个运行时
+        pub fn spawn_with<S, F, Fut, T>(&self, state: S, f: F) -> JoinHandle<T>
+        where
+            S: Send + 'static,
+            F: FnOnce(S) -> Fut,
+            Fut: Future<Output = T> + Send + Sync + 'static,
+            T: Send + 'static,
+        {
+            self.spawn(f(state))
+        }
+
+        /// Spawn a non-blocking `Future` with a name onto this runtime
+        /// 将一个带名字的非阻塞 `Future` 放入这个运行时
+        pub fn spawn_named<T: Send + 'static>(
+            &self,
+            name: &str,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_named(name, future)
+        }
+
+        /// Spawn a non-blocking `Future` with a `Priority` onto this runtime
+        /// 将一个带 `Priority` 的非阻塞 `Future` 放入这个运行时
+        pub fn spawn_with_priority<T: Send + 'static>(
+            &self,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_with_priority(priority, future)
+        }
+
+        /// Spawn a non-blocking `Future` onto the front of this runtime's queue
+        /// 将非阻塞的 `Future` 放到这个运行时队列的前端
+        pub fn spawn_front<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_front(future)
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime that always gets
+        /// polled on the same worker
+        /// 将一个总是在同一个工作线程上被poll的非阻塞 `Future` 放入这个运行时
+        pub fn spawn_affine<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_affine(future)
+        }
+
+        /// Spawn a `Future` that's already boxed onto this runtime as it is
+        /// 将一个已经装箱了的 `Future` 按原样放入这个运行时
+        pub fn spawn_boxed(&self, future: BoxFuture<'static, ()>) {
+            self.spawner().spawn_boxed(future)
+        }
+
+        /// Spawn a batch of non-blocking `Future`s onto this runtime at once
+        /// 一次把一批非阻塞的 `Future` 放入这个运行时
+        pub fn spawn_many<T: Send + 'static, F>(
+            &self,
+            futures: impl IntoIterator<Item = F>,
+        ) -> Vec<JoinHandle<T>>
+        where
+            F: Future<Output = T> + Send + Sync + 'static,
+        {
+            self.spawner().spawn_many(futures)
+        }
+
+        /// Set up a `pipeline` of `stages` on this runtime
+        /// 在这个运行时上搭建一个由`stages`组成的`pipeline`
+        pub fn pipeline<T: Send + 'static>(
+            &self,
+            stages: Vec<Stage<T>>,
+        ) -> (mpsc::Sender<T>, mpsc::Receiver<T>) {
+            self.spawner().pipeline(stages)
+        }
+
+        /// Spawn a task onto this runtime that's already done and holds `value`
+        /// 将一个已经完成并持有 `value` 的任务放入这个运行时
+        pub fn spawn_ready<T: Send + 'static>(&self, value: T) -> JoinHandle<T> {
+            self.spawner().spawn_ready(value)
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime once `ms` milliseconds
+        /// have passed
+        /// 在`ms`毫秒过去之后将一个非阻塞的 `Future` 放入这个运行时
+        pub fn spawn_after<T: Send + 'static>(
+            &self,
+            ms: u128,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_after(ms, future)
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime whose state lives in
+        /// `arena`
+        /// 将一个状态存放在`arena`中的非阻塞 `Future` 放入这个运行时
+        pub fn spawn_in<T: Send + 'static>(
+            &self,
+            arena: &Arena,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_in(arena, future)
+        }
+
+        /// Spawn a non-blocking `Future` onto this runtime that gets aborted
+        /// once its polls have taken longer than `budget` all together
+        /// 将一个非阻塞的 `Future` 放入这个运行时，一旦它的所有poll加起来花的时间超过了`budget`，它就会被中止
+        pub fn spawn_cpu_bounded<T: Send + 'static>(
+            &self,
+            budget: Duration,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_cpu_bounded(budget, future)
+        }
+
+        /// Spawn a `Future` onto this runtime, polling it once right away, and
+        /// skip the queue altogether if that's all it needed
+        /// 将一个 `Future` 放入这个运行时并立刻poll它一次，如果这就是它所需要的全部，就完全跳过队列
+        pub fn spawn_eager<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawner().spawn_eager(future)
+        }
+
+        /// Block the calling thread on a `Future` until it completes and hand
+        /// back its output. Anything it spawns or sets a timer for lands on
+        /// this runtime.
+        /// 在调用线程上阻塞 `Future` 直到它完成，并交还它的输出。它生成的任何东西或者设置的任何定时器都会落在这个运行时上。
+        ///
+        /// A task on one runtime can make a whole other runtime and block on
+        /// it, and while it does, anything spawned goes to the inner one. Once
+        /// the block is over we swap the outer runtime back in, and dropping
+        /// the inner one stops and joins its workers. Just keep in mind that
+        /// the outer worker is stuck for as long as the block lasts. With only
+        /// one worker, nothing else on the outer runtime runs in the meantime,
+        /// and if the inner future waits on something only an outer task can
+        /// do, it'll wait forever.
+        /// 一个运行时上的任务可以创建另一个完整的运行时并阻塞在它上面，在这期间，生成的任何东西都会去到内层的那个运行时。
+        /// 一旦阻塞结束，我们就把外层运行时换回来，而丢弃内层运行时会停止并join它的工作线程。只是要记住，
+        /// 在阻塞持续的这段时间里外层的那个工作线程是卡住的。如果只有一个工作线程，外层运行时上的其他东西在此期间都不会运行，
+        /// 而如果内层的future在等待某个只有外层任务才能做的事情，它就会永远等下去。
+        pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+            let previous = CURRENT.with(|current| current.replace(Some(self.spawner())));
+            let _restore = Restore(previous);
+            drive(future)
+        }
+
+        /// Block on a `Future` and stop others on one of this runtime's workers
+        /// until this one completes.
+        /// 阻塞 `Future`，并在这个运行时的一个工作线程上停止其他任务，直到此任务完成。
+        pub fn block_on_worker(&self, future: impl Future<Output = ()> + Send + Sync + 'static) {
+            self.spawner().spawn_blocking(future);
+        }
+
+        /// Block the calling thread on a `Future` and get back its output, but
+        /// keep the rest of the runtime going while we wait. Every time around
+        /// the loop we poll the future and then run one task off the queue, so
+        /// anything spawned before or during the block still makes progress.
+        /// This is what you want on a runtime built with `worker_threads(0)`,
+        /// where the calling thread is the only one there is: the plain
+        /// `block_on` would leave everything else sitting on the queue. Don't
+        /// call it from inside a task though, since that task is what we'd be
+        /// holding up.
+        /// 在调用线程上阻塞一个`Future`并拿回它的输出，但在我们等待的时候让运行时的其余部分继续运转。
+        /// 每一轮循环我们都会poll这个future，然后从队列中运行一个任务，所以在阻塞之前或者期间生成的任何东西都仍然会有进展。
+        /// 在用`worker_threads(0)`构建的运行时上这正是你想要的，因为调用线程是唯一的线程：普通的`block_on`会让其他所有东西都待在队列上。
+        /// 不过不要在任务里面调用它，因为我们会拖住的正是那个任务。
+        ///
+        /// Neither the future nor its output has to be `Send`, so it can hold
+        /// an `Rc` across an `.await` or hand one back. They never leave the
+        /// calling thread, which is the only thing `Send` guards against. The
+        /// tasks it drives are a different matter, they're `spawn`ed like any
+        /// others and still have to be `Send`.
+        /// 无论是future还是它的输出都不需要是`Send`的，所以它可以跨越`.await`持有一个`Rc`，或者交出一个`Rc`。它们永远不会离开调用线程，
+        /// 而这正是`Send`唯一要防范的事情。它驱动的任务就是另一回事了，它们像其他任务一样是被`spawn`出来的，仍然必须是`Send`的。
+        pub fn block_on_cooperative<T>(&self, future: impl Future<Output = T>) -> T {
+            let mut future = std::pin::pin!(future);
+            // We sleep in the same place as the workers do, so that a task
+            // showing up wakes either us or one of them, and either way it
+            // gets run. The future's own wake ups are only for us though, so
+            // they're addressed to our number, which comes after the workers'.
+            // 我们和工作线程睡在同一个地方，这样一个任务出现时会唤醒我们或者它们中的一个，无论哪种情况它都会被运行。
+            // 不过future自己的唤醒只是给我们的，所以它们是寄给我们的编号的，它排在工作线程的编号之后。
+            let thread = self.shared.locals.len() + CALLER.with(|caller| *caller);
+            let waker = Waker::from(Arc::new(Nudge {
+                work: self.shared.work.clone(),
+                thread,
+            }));
+            let mut ctx = Context::from_waker(&waker);
+            loop {
+                // Anything the future spawns or sets a timer for should land
+                // on this runtime, the same as if it were one of its tasks.
+                // 这个future生成的任何东西或者设置的任何定时器都应该落在这个运行时上，就像它是这个运行时的任务之一一样。
+                let previous = CURRENT.with(|current| current.replace(Some(self.spawner())));
+                let restore = Restore(previous);
+                let poll = future.as_mut().poll(&mut ctx);
+                drop(restore);
+                if let Poll::Ready(output) = poll {
+                    return output;
+                }
+                // With nothing on the queue we go to sleep until the future
+                // wakes us, a task shows up, or a timer is due.
+                // 队列上什么都没有的时候我们就去睡觉，直到future唤醒我们、有任务出现、或者某个定时器到期。
+                if !self.shared.tick() {
+                    self.shared.parks.fetch_add(1, Ordering::Relaxed);
+                    self.shared.work.park_as(thread, None);
+                }
+            }
+        }
+
+        /// Block further execution of a program until all of the tasks on this
+        /// runtime are completed. See the free `wait` for exactly which tasks
+        /// that covers.
+        /// 阻止程序的进一步执行，直到这个运行时上的所有任务完成。具体包括哪些任务，请看自由函数`wait`。
+        pub fn wait(&self) {
+            self.shared.wait();
+        }
+
+        /// Stop polling tasks without shutting down. The workers go to sleep
+        /// and anything that gets spawned or woken up waits on the queue until
+        /// `resume`, as does `tick`, so a debugger can hold everything still
+        /// while it looks around. A blocking task a worker is already driving
+        /// keeps going though, since that worker won't get back to its loop
+        /// until the task is done, and `block_on` runs on its own thread so it
+        /// isn't affected either.
+        /// 停止poll任务，但不关闭。工作线程会去睡觉，任何被生成或者被唤醒的东西都会在队列上等待直到`resume`，`tick`也是一样，
+        /// 所以一个调试器可以在它四处查看时让一切保持静止。不过一个工作线程已经在驱动的阻塞任务会继续运行，
+        /// 因为那个工作线程在任务完成之前不会回到它的循环中，而`block_on`运行在它自己的线程上，所以也不受影响。
+        pub fn pause(&self) {
+            self.shared.paused.store(true, Ordering::Release);
+        }
+
+        /// Start polling tasks again after `pause`.
+        /// 在`pause`之后重新开始poll任务。
+        pub fn resume(&self) {
+            self.shared.paused.store(false, Ordering::Release);
+            self.shared.work.unpark_all();
+        }
+
+        /// Calls `f` every time the runtime goes idle, meaning every worker
+        /// has run out of tasks and gone to sleep. It only goes off once per
+        /// busy spell, so workers waking up and going straight back to sleep
+        /// don't call it again until some task has actually been polled. This
+        /// is a good spot to flush work you've been batching up. It runs on
+        /// the last worker to go to sleep, so keep it quick. A runtime with no
+        /// workers never goes idle this way and never calls it.
+        /// 每次运行时变得空闲的时候调用`f`，也就是每个工作线程都没有任务了并且去睡觉了的时候。每一段忙碌期它只会触发一次，
+        /// 所以工作线程醒过来又马上回去睡觉不会再次调用它，直到某个任务真的被poll了为止。这是一个把你一直在攒着的工作刷出去的好地方。
+        /// 它运行在最后一个去睡觉的工作线程上，所以要让它快一点。一个没有工作线程的运行时永远不会以这种方式变得空闲，也永远不会调用它。
+        pub fn on_idle(&self, f: impl Fn() + Send + Sync + 'static) {
+            *self.shared.on_idle.lock().unwrap() = Some(Arc::new(f));
+        }
+
+        /// Ask the runtime to shut down, without waiting for it to happen. We
+        /// stop taking new tasks and every `shutdown_signal` goes off, so the
+        /// tasks that listen for it get to wrap up and finish on their own.
+        /// Nothing gets aborted, `shutdown_timeout` is for that.
+        /// 要求运行时关闭，但不等待它发生。我们停止接受新任务，并且每个`shutdown_signal`都会触发，
+        /// 所以监听它的任务可以收尾并自己结束。什么都不会被中止，那是`shutdown_timeout`干的事。
+        pub fn shutdown(&self) {
+            self.shared.closed.store(true, Ordering::Release);
+            self.shared.shutting_down.cancel();
+            // Anyone waiting for room on the queue isn't getting any.
+            // 任何在等待队列上腾出空间的人都等不到了。
+            let _queue = self.shared.queue.lock().unwrap();
+            self.shared.room.notify_all();
+        }
+
+        /// Shut the runtime down gracefully, but not forever. We stop taking
+        /// new tasks right away, so spawning onto the runtime from here on out
+        /// gives you a `JoinHandle` that's already cancelled. The tasks that
+        /// are already on it get `ms` milliseconds to finish up, and whatever
+        /// is still around after that gets aborted.
+        /// 优雅地关闭运行时，但不会永远等下去。我们会立刻停止接受新任务，所以从这之后再往运行时上生成任务，你会得到一个已经被取消的`JoinHandle`。
+        /// 已经在运行时上的任务有`ms`毫秒的时间来完成，之后还在的任何东西都会被中止。
+        pub fn shutdown_timeout(&self, ms: u128) {
+            self.shutdown();
+            let deadline = deadline_in(ms);
+            while self.shared.tasks.load(Ordering::Acquire) > 0 {
+                if Instant::now() >= deadline {
+                    self.shared.abort_all();
+                    return;
+                }
+                // Without any workers nobody else is going to run the tasks,
+                // so it's up to us.
+                // 没有任何工作线程的话，就没有别人会运行这些任务了，所以得靠我们自己。
+                if self.workers.is_empty() {
+                    if !self.shared.tick() {
+                        self.shared.park(Some(deadline));
+                    }
+                } else {
+                    self.shared.idle.park_until(Some(deadline));
+                }
+            }
+        }
+
+        /// Aborts every task on this runtime whose `TaskMeta` `predicate` says
+        /// yes to, whether it's on the queue, being polled, or waiting on
+        /// something, and returns how many it aborted. This is the lever to
+        /// pull when, say, you're under load and want to drop all of the
+        /// `Low` priority work. Tasks spawned while this is going on might be
+        /// missed.
+        /// 中止这个运行时上每一个`predicate`对它的`TaskMeta`说是的任务，无论它是在队列上、正在被poll、还是在等待某个东西，
+        /// 并返回它中止了多少个。比如当你负载很高、想要丢掉所有`Low`优先级的工作时，这就是该拉的那根杆。在这期间生成的任务可能会被漏掉。
+        pub fn abort_matching(&self, predicate: impl Fn(&TaskMeta<'_>) -> bool) -> usize {
+            self.shared.abort_matching(predicate)
+        }
+
+        /// How many tasks are alive on this runtime right now and how the ones
+        /// that are gone ended.
+        /// 这个运行时上此刻有多少任务存活，以及已经消失的任务是怎么结束的。
+        pub fn metrics(&self) -> Metrics {
+            Metrics {
+                alive: self.shared.tasks.load(Ordering::Acquire),
+                completed: self.shared.completed.load(Ordering::Relaxed),
+                cancelled: self.shared.cancelled.load(Ordering::Relaxed),
+            }
+        }
+
+        /// Lists every task that's alive on this runtime right now, oldest
+        /// first, which is what you'd build a `top` for the runtime out of.
+        /// It's read from the same registry that `abort_matching` goes
+        /// through, and tasks can come and go while we look, so it's only a
+        /// snapshot: a task spawned or dropped at the same time might or might
+        /// not make it in.
+        /// 列出这个运行时上此刻存活的每一个任务，最老的排在最前面，你可以用它为运行时搭一个`top`。
+        /// 它是从`abort_matching`所遍历的同一个registry中读出来的，而任务在我们查看的时候可能会来来去去，所以它只是一个快照：
+        /// 一个同时被生成或者被丢弃的任务可能在里面，也可能不在。
+        pub fn tasks(&self) -> Vec<TaskInfo> {
+            let tasks: Vec<_> = self
+                .shared
+                .registry
+                .lock()
+                .unwrap()
+                .values()
+                .filter_map(Weak::upgrade)
+                .collect();
+            let mut infos: Vec<_> = tasks
+                .iter()
+                .map(|task| TaskInfo {
+                    id: task.id,
+                    name: task.name.clone(),
+                    priority: task.priority,
+                    blocking: task.block,
+                    polls: task.polls.load(Ordering::Relaxed),
+                    age: task.spawned_at.elapsed(),
+                })
+                .collect();
+            // Like in `abort_matching`, a task we were the last to hold on to
+            // takes itself out of the registry when it's dropped, so that has
+            // to happen after we let go of it.
+            // 和`abort_matching`中一样，一个我们是最后持有者的任务在被丢弃时会把自己从registry中移除，所以这必须发生在我们放开registry之后。
+            drop(tasks);
+            infos.sort_by_key(|info| info.id);
+            infos
+        }
+
+        /// Panics if there are still tasks alive on this runtime. This is meant
+        /// for the end of a test, to catch a task that got forgotten about or
+        /// was left running in the background. A task that just finished might
+        /// still be on its way out, say because a worker is dropping it right
+        /// now, so we give them `LEAK_GRACE` to be gone before we complain.
+        /// 如果这个运行时上还有存活的任务就panic。这是用在测试的结尾的，用来抓住一个被遗忘了或者被留在后台运行的任务。
+        /// 一个刚刚完成的任务可能还在离开的路上，比如因为一个工作线程正在丢弃它，所以在抱怨之前我们给它们`LEAK_GRACE`的时间消失。
+        pub fn assert_no_leaked_tasks(&self) {
+            let deadline = Instant::now() + LEAK_GRACE;
+            let mut alive = self.shared.tasks.load(Ordering::Acquire);
+            while alive > 0 && Instant::now() < deadline {
+                self.shared
+                    .idle
+                    .park_timeout(deadline.saturating_duration_since(Instant::now()));
+                alive = self.shared.tasks.load(Ordering::Acquire);
+            }
+            // We might have used up a wake up that someone in `wait` needs.
+            // 我们可能用掉了一次某个在`wait`中的人需要的唤醒。
+            self.shared.idle.unpark();
+            assert!(alive == 0, "{alive} task(s) still alive on the runtime");
+        }
+
+        /// A `WakeNotifier` for the tasks on this runtime.
+        /// 这个运行时上的任务的`WakeNotifier`。
+        pub fn wake_notifier(&self) -> WakeNotifier {
+            WakeNotifier {
+                shared: Arc::downgrade(&self.shared),
+            }
+        }
+
+        /// Runs a made up workload on this runtime and times it. We spawn
+        /// `n_tasks` tasks that each need to be polled `per_task_polls` times
+        /// before they finish, waking themselves straight back up every time
+        /// they return `Pending`. There's no real work in them, so what gets
+        /// measured is the cost of the runtime itself: the queue, the wakers,
+        /// and the hops between worker threads. Run it on runtimes built with
+        /// different `worker_threads` to see how they compare.
+        /// 在这个运行时上运行一个虚构的工作负载并计时。我们生成`n_tasks`个任务，每个任务需要被poll `per_task_polls`次才会完成，
+        /// 每次返回`Pending`时都会立刻把自己唤醒。它们里面没有真正的工作，所以测量的是运行时本身的开销：
+        /// 队列、waker以及工作线程之间的切换。在用不同`worker_threads`构建的运行时上运行它，看看它们相比如何。
+        pub fn run_benchmark(&self, n_tasks: usize, per_task_polls: usize) -> BenchStats {
+            let polls = Arc::new(AtomicUsize::new(0));
+            let done = Arc::new(AtomicUsize::new(0));
+            let finished = Arc::new(Parker::new(1));
+            let start = Instant::now();
+            let mut tasks = 0;
+            for _ in 0..n_tasks {
+                let spin = Spin {
+                    left: per_task_polls.max(1),
+                    polls: polls.clone(),
+                };
+                let done = done.clone();
+                let finished = finished.clone();
+                let spawned = self.try_spawn(async move {
+                    spin.await;
+                    done.fetch_add(1, Ordering::Release);
+                    finished.unpark();
+                });
+                if spawned.is_ok() {
+                    tasks += 1;
+                }
+            }
+            while done.load(Ordering::Acquire) < tasks {
+                if self.workers.is_empty() {
+                    self.shared.tick();
+                } else {
+                    finished.park();
+                }
+            }
+            BenchStats {
+                tasks,
+                polls: polls.load(Ordering::Relaxed),
+                elapsed: start.elapsed(),
+            }
+        }
+    }
+
+    /// A snapshot of how many tasks a `Runtime` has seen. Every task that's
+    /// ever been spawned is in exactly one of the three counts.
+    /// 一个`Runtime`见过多少任务的快照。每一个曾经被生成的任务都恰好属于这三个计数中的一个。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Metrics {
+        /// Tasks that are still around.
+        /// 仍然存在的任务。
+        pub alive: usize,
+        /// Tasks whose `Future` ran to completion.
+        /// `Future`运行到完成的任务。
+        pub completed: usize,
+        /// Tasks that went away before their `Future` finished.
+        /// 在`Future`完成之前就消失了的任务。
+        pub cancelled: usize,
+    }
+
+    /// What `Runtime::tasks` knows about one task.
+    /// `Runtime::tasks`所知道的关于一个任务的信息。
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TaskInfo {
+        /// The task's `TaskId`.
+        /// 任务的`TaskId`。
+        pub id: TaskId,
+        /// The name the task was spawned with, if it was given one.
+        /// 任务生成时被赋予的名字，如果有的话。
+        pub name: Option<String>,
+        /// The `Priority` the task was spawned with.
+        /// 任务生成时的`Priority`。
+        pub priority: Priority,
+        /// Whether it's a blocking task from `block_on_worker`.
+        /// 它是不是一个来自`block_on_worker`的阻塞任务。
+        pub blocking: bool,
+        /// How many times it's been polled so far.
+        /// 到目前为止它被poll了多少次。
+        pub polls: usize,
+        /// How long ago it was spawned.
+        /// 它是多久之前被生成的。
+        pub age: Duration,
+    }
+
+    /// What `Runtime::run_benchmark` measured.
+    /// `Runtime::run_benchmark`测量到的结果。
+    #[derive(Debug, Clone, Copy)]
+    pub struct BenchStats {
+        /// How many tasks ran to completion. This can be less than what was
+        /// asked for if the runtime turned some of them away.
+        /// 有多少任务运行完成了。如果运行时拒绝了其中一些，这个数可能比要求的少。
+        pub tasks: usize,
+        /// How many times the tasks got polled in total.
+        /// 这些任务总共被poll了多少次。
+        pub polls: usize,
+        /// How long it took from the first spawn to the last task finishing.
+        /// 从第一次生成到最后一个任务完成花了多长时间。
+        pub elapsed: Duration,
+    }
+
+    impl BenchStats {
+        /// How many polls the runtime got through each second.
+        /// 运行时每秒完成了多少次poll。
+        pub fn polls_per_sec(&self) -> f64 {
+            self.polls as f64 / self.elapsed.as_secs_f64()
+        }
+    }
+
+    /// The synthetic future behind `Runtime::run_benchmark`. It's `Ready` on
+    /// its `left`th poll and wakes itself up right away every time before
+    /// that.
+    /// `Runtime::run_benchmark`背后的合成future。它在第`left`次poll时变为`Ready`，在那之前每次都会立刻唤醒自己。
+    struct Spin {
+        left: usize,
+        polls: Arc<AtomicUsize>,
+    }
+
+    impl Future for Spin {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            self.polls.fetch_add(1, Ordering::Relaxed);
+            self.left -= 1;
+            if self.left == 0 {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    impl Shared {
+        /// Takes a task off the `tasks` count, waking up anyone in `wait` if
+        /// it was the last one.
+        /// 从`tasks`计数中减去一个任务，如果它是最后一个，就唤醒在`wait`中的人。
+        fn release_task(&self) {
+            let previous = self.tasks.fetch_sub(1, Ordering::Release);
+            debug_assert!(previous != 0, "the task counter went below zero");
+            if previous == 1 {
+                self.idle.unpark();
+            }
+        }
+
+        /// Which of our workers we're on, if we're on one of them at all.
+        /// 我们在我们的哪一个工作线程上，如果我们确实在其中某一个上的话。
+        fn worker(&self) -> Option<usize> {
+            match WORKER.with(Cell::get) {
+                Some((shared, worker)) if std::ptr::eq(shared, self) => Some(worker),
+                _ => None,
+            }
+        }
+
+        /// The body of `Runtime::tick`, which lives here so that the worker
+        /// threads, which only have the shared state, can call it too.
+        /// `Runtime::tick`的主体，放在这里是为了让只持有共享状态的工作线程也可以调用它。
+        fn tick(&self) -> bool {
+            // The timer thread keeps going while we're paused, so that
+            // whatever it wakes up is waiting on the queue once we resume,
+            // but nothing gets polled.
+            // 暂停期间定时器线程仍然会继续运转，这样它唤醒的东西在我们恢复时就已经在队列上等着了，但是什么都不会被poll。
+            if self.paused.load(Ordering::Acquire) {
+                return false;
+            }
+            self.flush_woken();
+            let local = self
+                .worker()
+                .and_then(|worker| self.locals[worker].lock().unwrap().pop_front());
+            let task = match local.or_else(|| self.pop_next()) {
+                Some(task) => task,
+                None => return false,
+            };
+            if self.queue_capacity.is_some() {
+                self.room.notify_one();
+            }
+            // From here on a wake up has to put the task back on the queue,
+            // even if it happens while we're still polling it.
+            // 从这里开始，一次唤醒就必须把任务放回队列了，即使它发生在我们还在poll它的时候。
+            task.scheduled.store(false, Ordering::Release);
+            if task.will_block() {
+                // A blocking task that got woken up after it was taken off the
+                // queue but before it was claimed goes back on the queue, so it
+                // can get popped a second time. Whichever worker claims it
+                // first drives it to completion and everyone else leaves it be,
+                // otherwise a second worker would get stuck spinning on the
+                // same task instead of picking up other work.
+                // 一个在被从队列中取出之后、被认领之前被唤醒的阻塞任务会回到队列中，所以它可能会被弹出第二次。
+                // 哪个工作线程先认领它，就由它驱动任务直到完成，其他的工作线程都不去管它，
+                // 否则第二个工作线程会卡在同一个任务上空转，而不是去接手其他工作。
+                if task.claimed.swap(true, Ordering::AcqRel) {
+                    return true;
+                }
+                // Rather than polling it over and over, we sleep until it gets
+                // woken up, say by the timer thread.
+                // 我们不是一遍又一遍地poll它，而是睡到它被唤醒为止，比如被定时器线程唤醒。
+                while task.poll().is_pending() {
+                    task.parker.park();
+                }
+            } else {
+                // We don't put the task back on the queue here if it's still
+                // pending. The future that returned `Pending` is holding on to
+                // the task's `Waker` and will call `wake` once it's worth
+                // polling it again.
+                // 如果任务仍然是pending的，我们不会在这里把它放回队列。返回`Pending`的future持有任务的`Waker`，
+                // 当值得再次poll它时，它会调用`wake`。
+                let _ = task.poll();
+            }
+            true
+        }
+
+        /// Takes a task off of whichever of `high`, `queue` and `low` it's the
+        /// turn of, giving the turn up to the next one once it's used up its
+        /// weight or has nothing left. Going all the way around and back to
+        /// where we started is enough to find a task if there's one anywhere.
+        /// 从轮到的那个`high`、`queue`或`low`中取出一个任务，一旦它用完了它的权重或者什么都不剩了，就把回合让给下一个。
+        /// 转完一整圈再回到我们开始的地方，就足以找到一个任务，如果任何地方有的话。
+        fn pop_next(&self) -> Option<Arc<Task>> {
+            let mut turn = self.turn.lock().unwrap();
+            for _ in 0..=self.weights.len() {
+                if turn.left > 0 {
+                    let task = match turn.class {
+                        0 => self.high.lock().unwrap().pop_front(),
+                        1 => self.queue.lock().unwrap().pop_front(),
+                        _ => self.low.lock().unwrap().pop_front(),
+                    };
+                    if let Some(task) = task {
+                        turn.left -= 1;
+                        return Some(task);
+                    }
+                }
+                turn.class = (turn.class + 1) % self.weights.len();
+                turn.left = self.weights[turn.class];
+            }
+            None
+        }
+
+        /// Puts a woken `High` or `Low` priority task on its class's queue.
+        /// 把一个被唤醒的`High`或`Low`优先级任务放到它的类别的队列上。
+        fn push_class(&self, task: Arc<Task>) {
+            match task.priority {
+                Priority::High => self.high.lock().unwrap().push_back(task),
+                _ => self.low.lock().unwrap().push_back(task),
+            }
+            self.enqueues.fetch_add(1, Ordering::Relaxed);
+            self.work.unpark();
+        }
+
+        /// Under `WakeStrategy::Batched`, holds on to a task that just got
+        /// woken up until the next tick. Only the first one in a batch needs
+        /// to wake a worker up, everyone after it is covered by that tick.
+        /// 在`WakeStrategy::Batched`下，留住一个刚被唤醒的任务直到下一次tick。一批中只有第一个需要唤醒一个工作线程，
+        /// 它之后的每一个都由那次tick负责。
+        fn defer_wake(&self, task: Arc<Task>) {
+            let first = {
+                let mut woken = self.woken.lock().unwrap();
+                woken.push(task);
+                woken.len() == 1
+            };
+            if first {
+                self.work.unpark();
+            }
+        }
+
+        /// Puts every task `defer_wake` held on to on the queue at once.
+        /// 把`defer_wake`留住的每一个任务一次性放到队列上。
+        fn flush_woken(&self) {
+            if self.wake_strategy != WakeStrategy::Batched {
+                return;
+            }
+            let woken = std::mem::take(&mut *self.woken.lock().unwrap());
+            if woken.is_empty() {
+                return;
+            }
+            let batch = woken.len();
+            let mut queue = self.queue.lock().unwrap();
+            self.enqueues.fetch_add(1, Ordering::Relaxed);
+            woken.into_iter().for_each(|task| queue.push_back(task));
+            drop(queue);
+            // We're about to take one of them ourselves, so we only need help
+            // with the rest.
+            // 我们马上就要自己拿走其中一个了，所以只需要别人帮忙处理剩下的。
+            for _ in 1..batch.min(self.locals.len()) {
+                self.work.unpark();
+            }
+        }
+
+        /// Wakes up every sleeper on the timer whose deadline has passed and
+        /// returns when the next one is due, if there is one.
+        /// 唤醒定时器上每一个截止时间已过的睡眠者，并返回下一个到期的时间，如果有的话。
+        fn fire_timers(&self) -> Option<Instant> {
+            let (expired, next) = {
+                let mut timer = self.timer.lock().unwrap();
+                (timer.expired(), timer.next_deadline())
+            };
+            expired.into_iter().for_each(Waker::wake);
+            next
+        }
+
+        /// Counts a worker that's about to go to sleep, and if it's the last
+        /// one awake after a busy spell, calls the `on_idle` hook.
+        /// 记下一个将要去睡觉的工作线程，如果它是一段忙碌期之后最后一个醒着的，就调用`on_idle`钩子。
+        fn go_idle(&self, worked: bool) {
+            if worked {
+                self.busy.store(true, Ordering::Release);
+            }
+            let asleep = self.sleeping.fetch_add(1, Ordering::AcqRel) + 1;
+            if asleep < self.locals.len()
+                || !self.queue.lock().unwrap().list.is_empty()
+                || !self.high.lock().unwrap().is_empty()
+                || !self.low.lock().unwrap().is_empty()
+                || !self.woken.lock().unwrap().is_empty()
+            {
+                return;
+            }
+            if self.busy.swap(false, Ordering::AcqRel) {
+                // We don't hold the lock while it runs, in case it wants to
+                // swap itself out.
+                // 在它运行的时候我们不持有锁，以防它想要把自己换掉。
+                let on_idle = self.on_idle.lock().unwrap().clone();
+                if let Some(on_idle) = on_idle {
+                    on_idle();
+                }
+            }
+        }
+
+        /// Puts a thread with nothing to do to sleep until there's a task on
+        /// the queue or `until` has come, whichever happens first. A timer
+        /// going off counts as a task showing up, since the timer thread puts
+        /// whatever it wakes on the queue.
+        /// 让一个无事可做的线程睡觉，直到队列上有了任务或者`until`到来，以先发生的为准。
+        /// 一个定时器的触发也算是有任务出现了，因为定时器线程会把它唤醒的东西放到队列上。
+        fn park(&self, until: Option<Instant>) {
+            self.parks.fetch_add(1, Ordering::Relaxed);
+            match self.worker() {
+                Some(worker) => self.work.park_as(worker, until),
+                None => self.work.park_until(until),
+            }
+        }
+
+        /// Sleeps until there are no more tasks on the runtime.
+        /// 睡觉直到运行时上没有更多任务。
+        fn wait(&self) {
+            // This pairs with the `Release` decrement in `Task::drop`. Seeing
+            // zero here means we've seen the last task's decrement, so
+            // everything every task did before it was dropped happens before
+            // we return.
+            // 这和`Task::drop`中`Release`的减少是配对的。在这里看到零意味着我们看到了最后一个任务的减少，
+            // 所以每个任务在被丢弃之前所做的一切都发生在我们返回之前。
+            while self.tasks.load(Ordering::Acquire) > 0 {
+                self.idle.park();
+            }
+            // Someone else might be waiting too, so we pass the wake up on.
+            // 可能还有别人也在等待，所以我们把唤醒传递下去。
+            self.idle.unpark();
+        }
+
+        /// Aborts every task that's still alive on the runtime.
+        /// 中止运行时上所有仍然存活的任务。
+        fn abort_all(&self) {
+            self.abort_matching(|_| true);
+        }
+
+        /// Aborts every task that's still alive on the runtime and that
+        /// `predicate` picks out, and returns how many that was.
+        /// 中止运行时上每一个仍然存活并且被`predicate`挑中的任务，并返回有多少个。
+        fn abort_matching(&self, predicate: impl Fn(&TaskMeta<'_>) -> bool) -> usize {
+            // We let go of the registry before anything else happens to the
+            // tasks. One we were the last to hold on to takes itself out of it
+            // when it's dropped, and `predicate` might spawn, which goes into
+            // it too, so either one would deadlock while we held the lock.
+            // 在对这些任务做任何其他事情之前，我们先释放registry。一个我们是最后持有者的任务在被丢弃时会把自己从中移除，
+            // 而`predicate`可能会生成任务，那也会进到registry里，所以在我们持有锁的时候两者都会造成死锁。
+            let tasks: Vec<_> = self
+                .registry
+                .lock()
+                .unwrap()
+                .values()
+                .filter_map(Weak::upgrade)
+                .collect();
+            let tasks: Vec<_> = tasks
+                .into_iter()
+                .filter(|task| predicate(&task.meta()))
+                .collect();
+            tasks.iter().for_each(|task| task.abort());
+            tasks.len()
+        }
+
+        /// Checks whether we'll take another task. We just read the `tasks`
+        /// counter, so a bunch of spawns racing each other might go a little
+        /// past `max_tasks` but that's fine for a guard rail against runaway
+        /// spawning.
+        /// 检查我们是否还会接受另一个任务。我们只是读取`tasks`计数器，所以一堆互相竞争的生成可能会稍微超过`max_tasks`，
+        /// 但是作为防止失控生成的护栏，这已经足够了。
+        fn admit(&self) -> Result<(), SpawnError> {
+            if self.closed.load(Ordering::Acquire) {
+                return Err(SpawnError::ShutDown);
+            }
+            match self.max_tasks {
+                Some(max) if self.tasks.load(Ordering::Relaxed) >= max => {
+                    return Err(SpawnError::TooManyTasks);
+                }
+                _ => {}
+            }
+            match self.queue_capacity {
+                Some(capacity) => self.make_room(capacity),
+                None => Ok(()),
+            }
+        }
+
+        /// Deals with a spawn onto a queue that might be full, depending on
+        /// the `OverflowPolicy`. Like `admit` this is only a guard rail, so
+        /// spawns racing each other can go a little past `capacity`.
+        /// 根据`OverflowPolicy`处理一次往可能已经满了的队列上的生成。和`admit`一样，这只是一个护栏，所以互相竞争的生成可能会稍微超过`capacity`。
+        fn make_room(&self, capacity: usize) -> Result<(), SpawnError> {
+            let mut queue = self.queue.lock().unwrap();
+            if queue.fresh < capacity {
+                return Ok(());
+            }
+            match self.overflow_policy {
+                OverflowPolicy::Block => {
+                    while queue.fresh >= capacity {
+                        if self.closed.load(Ordering::Acquire)
+                            || self.shutdown.load(Ordering::Acquire)
+                        {
+                            return Err(SpawnError::ShutDown);
+                        }
+                        queue = self.room.wait(queue).unwrap();
+                    }
+                    Ok(())
+                }
+                OverflowPolicy::DropNewest | OverflowPolicy::Reject => Err(SpawnError::QueueFull),
+                OverflowPolicy::DropOldest => {
+                    let oldest = queue.remove_oldest_fresh();
+                    drop(queue);
+                    if let Some(oldest) = oldest {
+                        oldest.abort();
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Dropping a `Runtime` stops its workers once they're done with whatever
+    /// they're polling right now. Any tasks left over on the queue, waiting
+    /// on the timer, or waiting on `notified` hold on to the shared state,
+    /// which holds on to them in turn, so we empty all of those out to make
+    /// sure they actually get dropped.
+    /// 丢弃一个`Runtime`会在工作线程完成它们当前正在poll的东西之后停止它们。
+    /// 任何留在队列中、在定时器上等待或者在等待`notified`的任务都持有共享状态，而共享状态反过来又持有它们，
+    /// 所以我们把这些全部清空，确保它们真的被丢弃。
+    impl Drop for Runtime {
+        fn drop(&mut self) {
+            self.shared.shutdown.store(true, Ordering::Release);
+            self.shared.work.unpark_all();
+            drop(self.shared.queue.lock().unwrap());
+            self.shared.room.notify_all();
+            for worker in self.workers.drain(..) {
+                // We can't wait on ourselves if the runtime gets dropped from
+                // inside one of its own tasks.
+                // 如果运行时是在它自己的某个任务中被丢弃的，我们不能等待我们自己。
+                if worker.thread().id() != thread::current().id() {
+                    let _ = worker.join();
+                }
+            }
+            // The timer thread can end up dropping us too, when the last
+            // thing holding on to the runtime is a task it just woke up.
+            // 定时器线程也可能最终丢弃我们，当最后一个持有运行时的东西是它刚刚唤醒的一个任务的时候。
+            if let Some(timer_thread) = self.timer_thread.take() {
+                timer_thread.thread().unpark();
+                if timer_thread.thread().id() != thread::current().id() {
+                    let _ = timer_thread.join();
+                }
+            }
+            let mut queued: Vec<_> = {
+                let mut queue = self.shared.queue.lock().unwrap();
+                std::iter::from_fn(|| queue.pop_front()).collect()
+            };
+            for local in &self.shared.locals {
+                queued.extend(std::mem::take(&mut *local.lock().unwrap()));
+            }
+            queued.extend(std::mem::take(&mut *self.shared.woken.lock().unwrap()));
+            queued.extend(std::mem::take(&mut *self.shared.high.lock().unwrap()));
+            queued.extend(std::mem::take(&mut *self.shared.low.lock().unwrap()));
+            let sleeping = std::mem::take(&mut self.shared.timer.lock().unwrap().wakers);
+            let notified = std::mem::take(&mut *self.shared.notify.lock().unwrap());
+            drop(queued);
+            drop(sleeping);
+            drop(notified);
+        }
+    }
+
+    /// A `Builder` lets you decide how a `Runtime` is put together before it
+    /// starts running anything. Right now that's just how many worker threads
+    /// it gets. With more than one, tasks get polled in parallel, and a task
+    /// that blocks only ties up the worker that's driving it. You can even ask
+    /// for zero workers, in which case nothing runs unless you drive the
+    /// runtime yourself with `tick`.
+    /// `Builder`让你在`Runtime`开始运行任何东西之前决定如何组装它。目前只能决定它有多少个工作线程。
+    /// 如果多于一个，任务就会被并行地poll，而一个阻塞的任务只会占用驱动它的那个工作线程。
+    /// 你甚至可以要求零个工作线程，这种情况下除非你自己用`tick`驱动运行时，否则什么都不会运行。
+    pub struct Builder {
+        worker_threads: usize,
+        observer: Option<Arc<dyn Observer>>,
+        max_tasks: Option<usize>,
+        queue_capacity: Option<usize>,
+        overflow_policy: OverflowPolicy,
+        on_thread_start: Option<Arc<dyn Fn() + Send + Sync>>,
+        on_thread_stop: Option<Arc<dyn Fn() + Send + Sync>>,
+        seed: Option<u64>,
+        dedicated_blocking: bool,
+        detect_deadlocks: bool,
+        wake_strategy: WakeStrategy,
+        priority_weights: [usize; 3],
+    }
+
+    impl Builder {
+        /// A builder for a runtime with a single worker thread, just like the
+        /// global one.
+        /// 一个构建只有一个工作线程的运行时的builder，就像全局运行时一样。
+        pub fn new() -> Self {
+            Self {
+                worker_threads: 1,
+                observer: None,
+                max_tasks: None,
+                queue_capacity: None,
+                overflow_policy: OverflowPolicy::Block,
+                on_thread_start: None,
+                on_thread_stop: None,
+                seed: None,
+                dedicated_blocking: false,
+                detect_deadlocks: false,
+                wake_strategy: WakeStrategy::Immediate,
+                priority_weights: [4, 2, 1],
+            }
+        }
+
+        /// Sets how many worker threads the runtime will poll tasks on.
+        /// 设置运行时用来poll任务的工作线程数量。
+        pub fn worker_threads(mut self, worker_threads: usize) -> Self {
+            self.worker_threads = worker_threads;
+            self
+        }
+
+        /// Caps how many tasks can be alive on the runtime at once. Past that,
+        /// `try_spawn` gives back `SpawnError::TooManyTasks` and `spawn` gives
+        /// back a `JoinHandle` that's already cancelled. It's a handy guard
+        /// rail when tasks spawn more tasks and a bug could make that go on
+        /// forever.
+        /// 限制运行时上同时可以存活多少个任务。超过了这个数量，`try_spawn`会返回`SpawnError::TooManyTasks`，
+        /// 而`spawn`会返回一个已经被取消的`JoinHandle`。当任务会生成更多任务、而一个bug可能让这种情况永无止境时，这是一个很方便的护栏。
+        pub fn max_tasks(mut self, max_tasks: usize) -> Self {
+            self.max_tasks = Some(max_tasks);
+            self
+        }
+
+        /// Caps how many newly spawned tasks can be waiting on the queue at
+        /// once. `overflow_policy` decides what a spawn past that does. Only
+        /// spawns count, a task that's woken back up always gets back on the
+        /// queue, since dropping it would lose work that had already started.
+        /// 限制同时可以在队列上等待的新生成的任务有多少个。`overflow_policy`决定超过这个数量的生成会做什么。
+        /// 只有生成才算数，一个被唤醒的任务总是会回到队列上，因为丢弃它会丢掉已经开始了的工作。
+        pub fn queue_capacity(mut self, capacity: usize) -> Self {
+            self.queue_capacity = Some(capacity);
+            self
+        }
+
+        /// Sets what happens to a spawn when the queue is full. See
+        /// `queue_capacity`. The default is `OverflowPolicy::Block`.
+        /// 设置当队列满了的时候一次生成会怎么样。请看`queue_capacity`。默认是`OverflowPolicy::Block`。
+        pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+            self.overflow_policy = policy;
+            self
+        }
+
+        /// Sets how many polls in a row woken `High`, `Normal` and `Low`
+        /// priority tasks get before it's the next class's turn. The
+        /// classes take turns in that order, and one with nothing waiting
+        /// gives up its turn, so the weights only matter once there's more
+        /// work than the runtime can keep up with. That's when always picking
+        /// the most urgent task would mean the least urgent ones never run at
+        /// all, whereas this way every class keeps getting its share. New
+        /// tasks get their first poll on the `Normal` turn whatever their
+        /// priority, along with blocking tasks, which are at the front of
+        /// it. Weights of zero count as one. The default is `4, 2, 1`.
+        /// 设置被唤醒的`High`、`Normal`和`Low`优先级任务在轮到下一个类别之前连续得到多少次poll。这些类别按照这个顺序轮流，
+        /// 而一个没有东西在等待的类别会放弃它的回合，所以只有在工作多到运行时跟不上的时候这些权重才有意义。
+        /// 那时候如果总是挑最紧急的任务，就意味着最不紧急的那些永远都不会运行，而用这种方式每一个类别都能一直得到它的那一份。
+        /// 新任务无论优先级如何，都在`Normal`的回合得到它们的第一次poll，阻塞任务也是，它们排在这个回合的最前面。
+        /// 为零的权重按一来算。默认是`4, 2, 1`。
+        pub fn priority_weights(mut self, high: usize, normal: usize, low: usize) -> Self {
+            self.priority_weights = [high.max(1), normal.max(1), low.max(1)];
+            self
+        }
+
+        /// Sets what waking a task up does. The default is
+        /// `WakeStrategy::Immediate`.
+        /// 设置唤醒一个任务会做什么。默认是`WakeStrategy::Immediate`。
+        pub fn wake_strategy(mut self, strategy: WakeStrategy) -> Self {
+            self.wake_strategy = strategy;
+            self
+        }
+
+        /// Sets something for every worker to call on its own thread when it
+        /// starts, before it polls anything. This is the place to set up
+        /// thread locals that tasks expect to find. `build` doesn't return
+        /// until every worker has called it.
+        /// 设置一个每个工作线程在启动时、poll任何东西之前，都会在它自己的线程上调用的东西。这是设置任务期望找到的线程局部变量的地方。
+        /// `build`要等到每个工作线程都调用过它之后才会返回。
+        pub fn on_thread_start(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+            self.on_thread_start = Some(Arc::new(f));
+            self
+        }
+
+        /// Sets something for every worker to call on its own thread once it
+        /// has stopped polling, when the `Runtime` is dropped.
+        /// 设置一个每个工作线程在停止poll之后，也就是`Runtime`被丢弃的时候，都会在它自己的线程上调用的东西。
+        pub fn on_thread_stop(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+            self.on_thread_stop = Some(Arc::new(f));
+            self
+        }
+
+        /// Runs every blocking task, the ones from `block_on_worker`, on a
+        /// brand new thread of its own rather than on one of the workers.
+        /// The thread is spawned when the task is and exits as soon as the
+        /// task is done, so however long a blocking task takes it never keeps
+        /// a worker from polling everything else. That's the thread per
+        /// blocking task model, and it's a lot easier to reason about than a
+        /// worker that goes missing for a while. The cost is a thread spawn
+        /// for each one, and nothing stops you from making a great many.
+        /// 让每一个阻塞任务，也就是来自`block_on_worker`的那些，在一个它自己的全新线程上运行，而不是在某个工作线程上。
+        /// 这个线程在任务生成时生成，并在任务完成后马上退出，所以无论一个阻塞任务花多长时间，它都不会让一个工作线程没法poll其他的东西。
+        /// 这就是每个阻塞任务一个线程的模型，它比一个消失一阵子的工作线程要容易理解得多。代价是每一个都要生成一个线程，而且没有什么能阻止你生成非常多个。
+        pub fn dedicated_blocking_threads(mut self, dedicated: bool) -> Self {
+            self.dedicated_blocking = dedicated;
+            self
+        }
+
+        /// Keeps track of which task holds and which waits on every async
+        /// `Mutex` the runtime's tasks use, and tells the `Observer` through
+        /// `on_deadlock` when some of them end up waiting on each other in a
+        /// loop. Deadlocks happen in async code just like with threads, except
+        /// that nothing looks stuck: the workers are happily polling other
+        /// tasks, the deadlocked ones just never get woken again. It costs a
+        /// global lock on every lock and unlock, so it only does anything in
+        /// debug builds. A `try_lock` isn't tracked, since it can't wait.
+        /// 记录运行时的任务用到的每一个异步`Mutex`是哪个任务持有、哪个任务在等待，并且当它们中的一些最终在一个圈里互相等待时，
+        /// 通过`on_deadlock`告诉`Observer`。死锁在异步代码中就像在线程中一样会发生，只不过看起来什么都没有卡住：工作线程在开心地poll其他任务，
+        /// 死锁了的那些只是永远不会再被唤醒了。它在每次加锁和解锁时都要花费一把全局锁，所以它只在调试构建中做事情。`try_lock`不会被记录，因为它不会等待。
+        pub fn detect_deadlocks(mut self) -> Self {
+            self.detect_deadlocks = true;
+            self
+        }
+
+        /// Seeds the runtime's random numbers, the ones tasks get from `rng`.
+        /// Two runtimes with the same seed hand out the same numbers in the
+        /// same order, which makes "random" delays reproducible. Without a
+        /// seed we take one from the clock.
+        /// 设置运行时的随机数的种子，也就是任务从`rng`得到的那些。两个有同样种子的运行时会以同样的顺序交出同样的数字，
+        /// 这让"随机"的延迟变得可以重现。没有种子的话我们就从时钟上取一个。
+        pub fn seed(mut self, seed: u64) -> Self {
+            self.seed = Some(seed);
+            self
+        }
+
+        /// Sets the `Observer` that gets told about the runtime's tasks.
+        /// 设置会被告知运行时上任务情况的`Observer`。
+        pub fn observer(mut self, observer: impl Observer + 'static) -> Self {
+            self.observer = Some(Arc::new(observer));
+            self
+        }
+
+        /// Creates the `Runtime` and starts up its workers.
+        /// 创建`Runtime`并启动它的工作线程。
+        pub fn build(self) -> Runtime {
+            let shared = Arc::new(Shared {
+                queue: Mutex::new(TaskQueue::new()),
+                tasks: AtomicUsize::new(0),
+                timer: Mutex::new(Timer::new()),
+                timer_thread: OnceLock::new(),
+                shutdown: AtomicBool::new(false),
+                max_tasks: self.max_tasks,
+                queue_capacity: self.queue_capacity,
+                overflow_policy: self.overflow_policy,
+                room: Condvar::new(),
+                closed: AtomicBool::new(false),
+                registry: Mutex::new(HashMap::new()),
+                high: Mutex::new(VecDeque::new()),
+                low: Mutex::new(VecDeque::new()),
+                weights: self.priority_weights,
+                turn: Mutex::new(Turn {
+                    class: 0,
+                    left: self.priority_weights[0],
+                }),
+                observer: self.observer,
+                work: Arc::new(Parker::new(self.worker_threads.max(1))),
+                idle: Parker::new(1),
+                notify: Mutex::new(HashMap::new()),
+                completed: AtomicUsize::new(0),
+                cancelled: AtomicUsize::new(0),
+                parks: AtomicUsize::new(0),
+                locals: (0..self.worker_threads)
+                    .map(|_| Mutex::new(VecDeque::new()))
+                    .collect(),
+                paused: AtomicBool::new(false),
+                shutting_down: CancellationToken::new(),
+                sleeping: AtomicUsize::new(0),
+                busy: AtomicBool::new(false),
+                on_idle: Mutex::new(None),
+                on_thread_start: self.on_thread_start,
+                on_thread_stop: self.on_thread_stop,
+                rng: Mutex::new(self.seed.map_or_else(XorShift::from_clock, XorShift::new)),
+                dedicated_blocking: self.dedicated_blocking,
+                detect_deadlocks: self.detect_deadlocks,
+                wake_strategy: self.wake_strategy,
+                woken: Mutex::new(Vec::new()),
+                enqueues: AtomicUsize::new(0),
+            });
+            Runtime {
+                timer_thread: Some(Runtime::start_timer(&shared)),
+                workers: Runtime::start(&shared, self.worker_threads),
+                shared,
+            }
+        }
+    }
+
+    impl Default for Runtime {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Default for Builder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// An `Observer` gets called whenever something interesting happens to a
+    /// task on the runtime it was given to. All of the methods do nothing by
+    /// default so you only need to write the ones you care about. They're
+    /// called right on the thread where it happens, so keep them quick!
+    /// 每当交给它的运行时上的任务发生了有意思的事情时，`Observer`就会被调用。
+    /// 所有方法默认什么都不做，所以你只需要写你关心的那些。它们会在事情发生的线程上直接被调用，所以要让它们快一点！
+    pub trait Observer: Send + Sync {
+        /// A task was just spawned.
+        /// 一个任务刚刚被生成。
+        fn on_spawn(&self, _task: &TaskMeta<'_>) {}
+
+        /// An async resource called `resource` got dropped without being
+        /// closed first. See `crate::futures::close` for what that's about.
+        /// 一个名叫`resource`的异步资源在没有先被关闭的情况下就被丢弃了。关于这是怎么回事，请看`crate::futures::close`。
+        fn on_unclosed(&self, _resource: &str) {}
+
+        /// A single `poll` of a task took `took`, which is longer than
+        /// `SLOW_POLL`. We only look in debug builds, so timing every poll
+        /// doesn't cost anything in release ones.
+        /// 一个任务的单次`poll`花了`took`，这比`SLOW_POLL`更久。我们只在调试构建中看，所以给每次poll计时在发布构建中不会花费任何东西。
+        fn on_slow_poll(&self, _task: &TaskMeta<'_>, _took: Duration) {}
+
+        /// The tasks in `cycle` are each waiting on an async `Mutex` that the
+        /// next one holds, and the last one is waiting on the first one's, so
+        /// none of them will ever go on. The first one is the task whose wait
+        /// closed the loop. We only look on runtimes built with
+        /// `Builder::detect_deadlocks`, and only in debug builds.
+        /// `cycle`中的每个任务都在等待下一个任务持有的一个异步`Mutex`，而最后一个在等待第一个持有的那个，所以它们谁都永远不会继续下去。
+        /// 第一个是它的等待让这个圈闭合了的那个任务。我们只在用`Builder::detect_deadlocks`构建的运行时上查看，而且只在调试构建中。
+        fn on_deadlock(&self, _cycle: &[TaskId]) {}
+    }
+
+    /// Hands the `Observer` of the runtime we're running on, if it has one, to
+    /// `f`.
+    /// 如果我们正在运行的运行时有`Observer`，就把它交给`f`。
+    pub(crate) fn observe(f: impl FnOnce(&dyn Observer)) {
+        if let Some(observer) = &Spawner::current().shared.observer {
+            f(observer.as_ref());
+        }
+    }
+
+    /// Who holds which async `Mutex` and who is waiting on which, for
+    /// `Builder::detect_deadlocks`. A deadlock is a loop in here: a task waits
+    /// on a lock, whose holder waits on another lock, and so on until we get
+    /// back to the task we started with. Locks go by their address, which
+    /// can't change while anyone holds or waits on them, and tasks by their
+    /// `TaskId`, which is unique across every runtime, so one graph does for
+    /// all of them.
+    /// 谁持有哪个异步`Mutex`、谁在等待哪个，给`Builder::detect_deadlocks`用的。一个死锁就是这里面的一个圈：
+    /// 一个任务在等待一把锁，它的持有者在等待另一把锁，以此类推，直到我们回到开始的那个任务。锁按照它们的地址来区分，
+    /// 在有人持有或者等待它们的时候地址不会变；任务按照它们的`TaskId`来区分，它在所有运行时之间都是唯一的，所以一张图就够所有运行时用了。
+    #[derive(Default)]
+    struct WaitGraph {
+        holders: HashMap<usize, TaskId>,
+        waiting: HashMap<TaskId, usize>,
+    }
+
+    fn wait_graph() -> &'static Mutex<WaitGraph> {
+        static GRAPH: OnceLock<Mutex<WaitGraph>> = OnceLock::new();
+        GRAPH.get_or_init(Default::default)
+    }
+
+    /// The task polling us, if its runtime wants to know about deadlocks.
+    /// 正在poll我们的任务，如果它的运行时想知道死锁的话。
+    pub(crate) fn deadlock_tracking(cx: &Context<'_>) -> Option<TaskId> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        polling_task(cx)
+            .filter(|task| task.spawner.shared.detect_deadlocks)
+            .map(|task| task.id)
+    }
+
+    /// `task` has to wait for the lock at `lock`. If that closes a loop we
+    /// tell the `Observer`, once, rather than every time the wait is polled.
+    /// `task`必须等待`lock`处的那把锁。如果这让一个圈闭合了，我们就告诉`Observer`，只说一次，而不是等待每被poll一次就说一次。
+    pub(crate) fn lock_waiting(task: TaskId, lock: usize) {
+        let cycle = {
+            let mut graph = wait_graph().lock().unwrap();
+            if graph.waiting.insert(task, lock) == Some(lock) {
+                return;
+            }
+            let mut cycle = vec![task];
+            let mut waiting_on = lock;
+            loop {
+                let Some(&holder) = graph.holders.get(&waiting_on) else {
+                    break None;
+                };
+                if holder == task {
+                    break Some(cycle);
+                }
+                // A loop that doesn't go through us isn't ours to report, and
+                // without this we'd go around it forever.
+                // 一个不经过我们的圈不归我们来报告，而且没有这个的话我们会永远绕着它转。
+                if cycle.contains(&holder) {
+                    break None;
+                }
+                cycle.push(holder);
+                match graph.waiting.get(&holder) {
+                    Some(&next) => waiting_on = next,
+                    None => break None,
+                }
+            }
+        };
+        if let Some(cycle) = cycle {
+            observe(|observer| observer.on_deadlock(&cycle));
+        }
+    }
+
+    /// `task` got the lock at `lock`.
+    /// `task`拿到了`lock`处的那把锁。
+    pub(crate) fn lock_acquired(task: TaskId, lock: usize) {
+        let mut graph = wait_graph().lock().unwrap();
+        graph.waiting.remove(&task);
+        graph.holders.insert(lock, task);
+    }
+
+    /// Whoever held the lock at `lock` let go of it.
+    /// 持有`lock`处那把锁的人放开了它。
+    pub(crate) fn lock_released(lock: usize) {
+        wait_graph().lock().unwrap().holders.remove(&lock);
+    }
+
+    /// `task` stopped waiting on a lock without getting it.
+    /// `task`没有拿到锁就停止了等待。
+    pub(crate) fn lock_abandoned(task: TaskId) {
+        wait_graph().lock().unwrap().waiting.remove(&task);
+    }
+
+    /// Gets at the random numbers of the runtime we're running on, or of the
+    /// global runtime if we're not inside of a task. They all come out of one
+    /// generator per runtime, so a single task drawing from a seeded runtime
+    /// always sees the same numbers. Tasks drawing at the same time still take
+    /// turns in whatever order they get polled in, though.
+    /// 获取我们正在运行的运行时的随机数，如果我们不在任务中，就获取全局运行时的。它们都来自每个运行时的一个生成器，
+    /// 所以一个从有种子的运行时中取数的任务总是会看到同样的数字。不过同时取数的任务仍然会按照它们被poll的顺序轮流取。
+    pub fn rng() -> Rng {
+        Rng {
+            spawner: Spawner::current(),
+        }
+    }
+
+    /// The handle `rng` gives back.
+    /// `rng`交出的句柄。
+    pub struct Rng {
+        spawner: Spawner,
+    }
+
+    impl Rng {
+        /// The next random number.
+        /// 下一个随机数。
+        pub fn next_u64(&self) -> u64 {
+            self.spawner.shared.rng.lock().unwrap().next()
+        }
+
+        /// A random number somewhere in `range`.
+        /// 一个在`range`中某处的随机数。
+        pub fn gen_range(&self, range: std::ops::Range<u64>) -> u64 {
+            assert!(!range.is_empty(), "can't pick from an empty range");
+            range.start + self.next_u64() % (range.end - range.start)
+        }
+    }
+
+    /// A number that's unique to each task, for as long as the program runs.
+    /// 每个任务独有的一个数字，在程序运行期间一直如此。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct TaskId(u64);
+
+    impl TaskId {
+        fn next() -> TaskId {
+            static NEXT: AtomicU64 = AtomicU64::new(0);
+            TaskId(NEXT.fetch_add(1, Ordering::Relaxed))
+        }
+    }
+
+    /// What an `Observer` gets to know about a task.
+    /// `Observer`能够知道的关于一个任务的信息。
+    #[derive(Debug)]
+    pub struct TaskMeta<'a> {
+        id: TaskId,
+        name: Option<&'a str>,
+        priority: Priority,
+    }
+
+    impl TaskMeta<'_> {
+        /// The task's `TaskId`.
+        /// 任务的`TaskId`。
+        pub fn id(&self) -> TaskId {
+            self.id
+        }
+
+        /// The name the task was spawned with, if it was given one.
+        /// 任务生成时被赋予的名字，如果有的话。
+        pub fn name(&self) -> Option<&str> {
+            self.name
+        }
+
+        /// The `Priority` the task was spawned with.
+        /// 任务生成时的`Priority`。
+        pub fn priority(&self) -> Priority {
+            self.priority
+        }
+    }
+
+    /// A `WakeNotifier` lets code that isn't async at all, like an I/O thread
+    /// or a signal handler, wake up a task when something it was waiting on
+    /// happened. The task awaits `notified`, which leaves its `Waker` with
+    /// the runtime under its `TaskId`, and hands that id over however it
+    /// likes. It's the timer idea turned inside out: instead of the runtime
+    /// deciding when to wake a task, someone outside of it does. The notifier
+    /// doesn't keep the runtime alive and it's fine for the task to be long
+    /// gone by the time the wake up shows up, we just tell you there was
+    /// nobody to wake.
+    /// `WakeNotifier`让完全不是异步的代码，比如一个I/O线程或者一个信号处理函数，能够在任务等待的某件事发生时唤醒它。
+    /// 任务会await `notified`，它会把任务的`Waker`以它的`TaskId`留在运行时那里，然后任务可以随便用什么方式把这个id交出去。
+    /// 这是把定时器的思路反过来：不是由运行时决定何时唤醒任务，而是由运行时之外的某个人决定。
+    /// 通知器不会让运行时保持存活，而且在唤醒到达时任务早就没了也没关系，我们只是告诉你没有人可以唤醒。
+    #[derive(Clone)]
+    pub struct WakeNotifier {
+        shared: Weak<Shared>,
+    }
+
+    impl WakeNotifier {
+        /// Wakes the task with `id` if it's waiting on `notified`, returning
+        /// whether it was.
+        /// 如果`id`对应的任务正在等待`notified`，就唤醒它，并返回它是否在等待。
+        pub fn wake(&self, id: TaskId) -> bool {
+            let waker = self
+                .shared
+                .upgrade()
+                .and_then(|shared| shared.notify.lock().unwrap().remove(&id));
+            match waker {
+                Some(waker) => {
+                    waker.wake();
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    /// Waits until a `WakeNotifier` wakes the task we're in. It has to be
+    /// awaited inside one of our tasks, since the `TaskId` is how the
+    /// notifier finds us.
+    /// 等待直到一个`WakeNotifier`唤醒我们所在的任务。它必须在我们的任务里面被await，因为通知器是通过`TaskId`找到我们的。
+    pub fn notified() -> Notified {
+        Notified { entry: None }
+    }
+
+    /// The `Future` returned by `notified`.
+    /// `notified`返回的`Future`。
+    pub struct Notified {
+        /// Where we left our `Waker`, once we have.
+        /// 我们留下`Waker`的地方，一旦我们留下了的话。
+        entry: Option<(Arc<Shared>, TaskId)>,
+    }
+
+    impl Future for Notified {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let (shared, id) = match &self.entry {
+                Some((shared, id)) => (shared.clone(), *id),
+                None => {
+                    let id = context(cx)
+                        .expect("`notified` has to be awaited inside a task")
+                        .id();
+                    let shared = Spawner::current().shared;
+                    shared.notify.lock().unwrap().insert(id, cx.waker().clone());
+                    self.entry = Some((shared, id));
+                    return Poll::Pending;
+                }
+            };
+            // The notifier takes our `Waker` out when it wakes us, so if it's
+            // still there this is just some other wake up and we keep waiting.
+            // 通知器唤醒我们的时候会把我们的`Waker`拿走，所以如果它还在，这只是别的什么唤醒，我们继续等待。
+            let mut notify = shared.notify.lock().unwrap();
+            match notify.get_mut(&id) {
+                Some(waker) => {
+                    waker.clone_from(cx.waker());
+                    Poll::Pending
+                }
+                None => {
+                    drop(notify);
+                    self.entry = None;
+                    Poll::Ready(())
+                }
+            }
+        }
+    }
+
+    impl Drop for Notified {
+        fn drop(&mut self) {
+            if let Some((shared, id)) = self.entry.take() {
+                let waker = shared.notify.lock().unwrap().remove(&id);
+                drop(waker);
+            }
+        }
+    }
+
+    /// Waits until the runtime has gotten through everything that's on its
+    /// queue right now. That's a stronger promise than just going to the back
+    /// of the queue once: every task that was waiting when we asked has been
+    /// taken off the queue and polled by the time we're done, so it's a handy
+    /// way to let the tasks you just spawned get going before you carry on.
+    /// We do it by putting a tiny marker task on the back of the queue. Once
+    /// a worker gets to it, it has already gotten to everything in front of
+    /// it, and all the marker does is wake us up. It counts as a task like
+    /// any other, which also means `wait` waits for it.
+    /// 等待直到运行时处理完它队列上此刻的所有东西。这比只是去队列末尾排一次队的承诺更强：
+    /// 当我们完成时，我们请求的那一刻正在等待的每一个任务都已经被从队列中取出并被poll过了，
+    /// 所以在继续之前让你刚刚生成的任务先开始运行，这是一个很方便的方法。
+    /// 我们的做法是在队列末尾放一个很小的标记任务。一旦某个工作线程处理到了它，它前面的所有东西就都已经被处理过了，
+    /// 而这个标记所做的只是唤醒我们。它和其他任务一样算作一个任务，这也意味着`wait`会等它。
+    pub fn next_tick() -> NextTick {
+        NextTick { marker: None }
+    }
+
+    /// The `Future` returned by `next_tick`.
+    /// `next_tick`返回的`Future`。
+    pub struct NextTick {
+        marker: Option<Arc<Mutex<Marker>>>,
+    }
+
+    /// What `NextTick` and its marker task share.
+    /// `NextTick`和它的标记任务共享的东西。
+    struct Marker {
+        /// Whether the marker task has been polled yet.
+        /// 标记任务是否已经被poll了。
+        passed: bool,
+        /// Who to wake up once it has.
+        /// 它被poll之后要唤醒谁。
+        waker: Option<Waker>,
+    }
+
+    impl Future for NextTick {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if let Some(marker) = &self.marker {
+                let mut marker = marker.lock().unwrap();
+                if marker.passed {
+                    return Poll::Ready(());
+                }
+                marker.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            let marker = Arc::new(Mutex::new(Marker {
+                passed: false,
+                waker: Some(cx.waker().clone()),
+            }));
+            let theirs = marker.clone();
+            let spawner = Spawner::current();
+            let task = Task::new(spawner.clone(), false, None, Priority::Normal, async move {
+                let waker = {
+                    let mut marker = theirs.lock().unwrap();
+                    marker.passed = true;
+                    marker.waker.take()
+                };
+                waker.into_iter().for_each(Waker::wake);
+            });
+            spawner.inner_spawn(task);
+            self.marker = Some(marker);
+            Poll::Pending
+        }
+    }
+
+    /// A `CancellationToken` is a way to politely ask tasks to stop. Anyone
+    /// holding a clone of the token can `cancel` it, and any task that's
+    /// waiting on `cancelled` gets woken up and can wrap up whatever it was
+    /// doing. The task decides for itself when and how to stop, which is the
+    /// big difference to just dropping it from the outside: it gets a chance
+    /// to clean up and it never gets stopped halfway through something.
+    ///
+    /// Tokens form a tree. Cancelling a token cancels every token made from it
+    /// with `child_token`, and their children, and so on, but cancelling a
+    /// child leaves its parent alone. That way a server can have one token for
+    /// the whole thing and one child for each connection for instance.
+    /// `CancellationToken`是一种礼貌地请求任务停止的方式。任何持有这个token克隆的人都可以`cancel`它，
+    /// 任何正在等待`cancelled`的任务都会被唤醒，然后可以收尾它正在做的事情。
+    /// 任务自己决定何时以及如何停止，这和从外部直接丢弃它有很大的区别：它有机会进行清理，而且永远不会在做某件事做到一半时被停止。
+    ///
+    /// token组成了一棵树。取消一个token会取消所有通过`child_token`从它创建的token，以及它们的子token，以此类推，
+    /// 但是取消一个子token不会影响它的父token。这样一个服务器就可以比如为整个服务准备一个token，并为每个连接准备一个子token。
+    #[derive(Clone, Default)]
+    pub struct CancellationToken {
+        node: Arc<Mutex<TokenNode>>,
+    }
+
+    #[derive(Default)]
+    struct TokenNode {
+        cancelled: bool,
+        /// The tasks waiting to hear about the cancellation.
+        /// 等待得知取消消息的任务。
+        wakers: Vec<Waker>,
+        /// We only hold on to children weakly so that a long lived parent
+        /// doesn't keep every child it ever made alive.
+        /// 我们只弱引用子token，这样一个长期存活的父token就不会让它创建过的每个子token都一直存活。
+        children: Vec<Weak<Mutex<TokenNode>>>,
+    }
+
+    impl CancellationToken {
+        /// Creates a new token that isn't cancelled yet.
+        /// 创建一个还没有被取消的新token。
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Creates a token that gets cancelled once this one is. If this one
+        /// already is, so is the child.
+        /// 创建一个在当前token被取消时也会被取消的token。如果当前token已经被取消了，子token也是。
+        pub fn child_token(&self) -> CancellationToken {
+            let child = CancellationToken::new();
+            let mut node = self.node.lock().unwrap();
+            if node.cancelled {
+                child.node.lock().unwrap().cancelled = true;
+            } else {
+                node.children.retain(|child| child.strong_count() > 0);
+                node.children.push(Arc::downgrade(&child.node));
+            }
+            child
+        }
+
+        /// Cancels this token and all of its children.
+        /// 取消这个token和它所有的子token。
+        pub fn cancel(&self) {
+            let mut pending = vec![self.node.clone()];
+            while let Some(node) = pending.pop() {
+                let (wakers, children) = {
+                    let mut node = node.lock().unwrap();
+                    if node.cancelled {
+                        continue;
+                    }
+                    node.cancelled = true;
+                    (
+                        std::mem::take(&mut node.wakers),
+                        std::mem::take(&mut node.children),
+                    )
+                };
+                // We wake everyone after letting go of the lock, just in case
+                // a waker wants to look at the token right away.
+                // 我们在释放锁之后才唤醒所有人，以防某个waker想立刻查看这个token。
+                wakers.into_iter().for_each(Waker::wake);
+                pending.extend(children.iter().filter_map(Weak::upgrade));
+            }
+        }
+
+        /// Whether this token has been cancelled.
+        /// 这个token是否已经被取消。
+        pub fn is_cancelled(&self) -> bool {
+            self.node.lock().unwrap().cancelled
+        }
+
+        /// Completes once this token is cancelled.
+        /// 在这个token被取消时完成。
+        pub async fn cancelled(&self) {
+            poll_fn(|cx| {
+                let mut node = self.node.lock().unwrap();
+                if node.cancelled {
+                    return Poll::Ready(());
+                }
+                // We check and store the waker while holding the lock, so
+                // `cancel` can't sneak in between the two and leave us waiting
+                // forever.
+                // 我们在持有锁的时候检查并存储waker，所以`cancel`不可能在两者之间插进来，让我们永远等下去。
+                if !node.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    node.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            })
+            .await
+        }
+    }
+
+    /// How a `sleep_cancellable` ended.
+    /// `sleep_cancellable`是怎么结束的。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SleepOutcome {
+        /// We slept for the whole time.
+        /// 我们睡满了整段时间。
+        Elapsed,
+        /// The token got cancelled before the time was up.
+        /// token在时间到之前被取消了。
+        Cancelled,
+    }
+
+    /// Sleeps for `ms` milliseconds unless `token` gets cancelled first, in
+    /// which case we wake up right away. This is the usual way for a task
+    /// that does something every so often to notice that it's time to shut
+    /// down without having to wait out its whole sleep.
+    /// 睡`ms`毫秒，除非`token`先被取消了，这种情况下我们会立刻醒来。对于一个每隔一段时间做一些事情的任务，
+    /// 这是注意到该关闭了而不必等完整段睡眠的常用方式。
+    pub async fn sleep_cancellable(ms: u128, token: &CancellationToken) -> SleepOutcome {
+        let mut sleep = Sleep::new(ms);
+        let mut cancelled = std::pin::pin!(token.cancelled());
+        poll_fn(|cx| {
+            if cancelled.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(SleepOutcome::Cancelled);
+            }
+            match Pin::new(&mut sleep).poll(cx) {
+                Poll::Ready(()) => Poll::Ready(SleepOutcome::Elapsed),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Sleep for `ms` milliseconds without holding up the thread. This is just
+    /// `Sleep::new`, under a name that sits right next to the mistake it's
+    /// here to stop: calling `std::thread::sleep` from async code puts the
+    /// whole worker to sleep, along with every other task that could have
+    /// run on it in the meantime. In debug builds the `Observer` hears about
+    /// a poll that does that through `on_slow_poll`.
+    /// 睡`ms`毫秒而不拖住线程。这就是`Sleep::new`，只是换了一个紧挨着它要阻止的那个错误的名字：
+    /// 在异步代码中调用`std::thread::sleep`会让整个工作线程都去睡觉，连同这段时间里本来可以在它上面运行的其他每一个任务。
+    /// 在调试构建中，`Observer`会通过`on_slow_poll`听到做了这种事的poll。
+    pub fn async_sleep(ms: u128) -> Sleep {
+        Sleep::new(ms)
+    }
+
+    /// Completes once the runtime we're running on is asked to shut down,
+    /// with `Runtime::shutdown` or `Runtime::shutdown_timeout`. A task that
+    /// runs for a long time can race its work against this, say with
+    /// `select_biased!`, and leave cleanly instead of getting aborted.
+    /// 在我们正在运行的运行时被要求关闭时完成，无论是通过`Runtime::shutdown`还是`Runtime::shutdown_timeout`。
+    /// 一个运行很长时间的任务可以让它的工作和这个赛跑，比如用`select_biased!`，然后干净地离开，而不是被中止。
+    pub fn shutdown_signal() -> impl Future<Output = ()> + Send + Sync + 'static {
+        let token = Spawner::current().shared.shutting_down.clone();
+        async move { token.cancelled().await }
+    }
+
+    /// This is the initialization function for our `RUNTIME` static below. We
+    /// build a `Runtime` with the default settings and return it to be put in
+    /// the static value
+    /// 这是我们下面的 `RUNTIME` 静态变量的初始化函数。
+    /// 我们用默认设置构建一个 `Runtime`，然后返回它以放入静态值 RUNTIME 中。
+    fn setup_runtime() -> Runtime {
+        // The workers are handed the shared state directly rather than going
+        // through `Runtime::get()`, so they never have to wait on the `Lazy`
+        // type that is being initialized by this very function.
+        // 工作线程是直接拿到共享状态的，而不是通过`Runtime::get()`，所以它们永远不需要等待正在由这个函数初始化的`Lazy`类型。
+        //
+        // What if a bunch of threads all call `spawn` at once before anyone
+        // touched the runtime? `call_once` lets exactly one of them in here,
+        // so exactly one worker gets started, and everyone else is held up
+        // until the `Runtime` is all set up. Nobody can push a task before
+        // the queue exists, and the worker doesn't need to be running yet
+        // either: it checks the queue every time around its loop, so whatever
+        // was pushed before it got going is still sitting there waiting for it.
+        // 如果一堆线程在任何人碰过运行时之前同时调用`spawn`会怎样？`call_once`只会让它们中的一个进到这里，
+        // 所以只会启动一个工作线程，其他所有人都会被挡住，直到`Runtime`完全设置好。在队列存在之前没有人可以推送任务，
+        // 工作线程也不需要已经在运行：它每一轮循环都会检查队列，所以在它开始运行之前推送的任何东西都还在那里等着它。
+        Runtime::new()
+    }
+
+    /// With all of the work we did in `crate::lazy` we can now create our static type to represent
+    /// the singular `Runtime` when it is finally initialized by the `setup_runtime` function.
+    /// 在 `crate::lazy` 中完成了所有工作后，我们现在可以创建一个静态类型，以表示最终由 `setup_runtime` 函数初始化的单个 `Runtime`。
+    static RUNTIME: crate::lazy::Lazy<Runtime> = crate::lazy::Lazy::new();
+
+    /// The runtime a test asked to stand in for `RUNTIME`, if there is one.
+    /// A `Lazy` can only ever be set once, so rather than making `RUNTIME`
+    /// itself swappable we look here first.
+    /// 某个测试要求用来代替`RUNTIME`的运行时，如果有的话。一个`Lazy`只能被设置一次，所以我们没有让`RUNTIME`本身可以被替换，而是先看这里。
+    #[cfg(any(test, feature = "test-util"))]
+    static TEST_RUNTIME: std::sync::atomic::AtomicPtr<Runtime> =
+        std::sync::atomic::AtomicPtr::new(std::ptr::null_mut());
+
+    /// Makes `runtime` the global runtime, so that the free functions like
+    /// `spawn` and `block_on_worker` use it from outside of a task, say a
+    /// runtime built with `Builder::seed` or `worker_threads(0)` that a test
+    /// wants to drive itself. It's only there in tests, yours with the
+    /// `test-util` feature. It stays in until `clear_test_runtime`, which
+    /// drops it, and a runtime that was already swapped in gets replaced
+    /// without being dropped, since something might still be using it.
+    ///
+    /// There's only one global runtime, while `cargo test` runs tests on
+    /// several threads at once, so every other test using the free functions
+    /// at the same time ends up on it too. Keep the tests that swap it in a
+    /// binary of their own, or run them with `--test-threads=1`.
+    /// 让`runtime`成为全局运行时，这样像`spawn`和`block_on_worker`这样的自由函数在任务之外就会使用它，
+    /// 比如一个测试想自己驱动的、用`Builder::seed`或者`worker_threads(0)`构建的运行时。它只在测试中存在，你的测试需要`test-util`特性。
+    /// 它会一直待在那里直到`clear_test_runtime`把它丢弃，而一个已经被换进来的运行时会被替换掉但不会被丢弃，因为可能还有东西在使用它。
+    ///
+    /// 全局运行时只有一个，而`cargo test`会同时在好几个线程上运行测试，所以同一时间每一个其他使用自由函数的测试也都会落到它上面。
+    /// 把替换它的测试放在一个它们自己的二进制文件中，或者用`--test-threads=1`来运行它们。
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn set_test_runtime(runtime: Runtime) {
+        TEST_RUNTIME.store(Box::into_raw(Box::new(runtime)), Ordering::Release);
+    }
+
+    /// Goes back to the usual global runtime after `set_test_runtime`, and
+    /// drops the test runtime, which stops its workers and aborts whatever
+    /// tasks it still has.
+    /// 在`set_test_runtime`之后回到平常的全局运行时，并丢弃测试运行时，这会停止它的工作线程并中止它还剩下的任何任务。
+    ///
+    /// # Safety
+    ///
+    /// `Runtime::get` hands out `&'static` references to the test runtime,
+    /// and none of them can be used once this returns. That includes the
+    /// ones inside of anything spawned from outside of a task in the
+    /// meantime, so wait for those to finish first.
+    /// `Runtime::get`交出的是指向测试运行时的`&'static`引用，一旦这个函数返回，它们中的任何一个都不能再被使用了。
+    /// 这包括在此期间从任务之外生成的任何东西里面的那些引用，所以先等它们完成。
+    #[cfg(any(test, feature = "test-util"))]
+    pub unsafe fn clear_test_runtime() {
+        let runtime = TEST_RUNTIME.swap(std::ptr::null_mut(), Ordering::AcqRel);
+        if !runtime.is_null() {
+            // SAFETY: It came from `Box::into_raw` in `set_test_runtime`, and
+            // nobody's using it anymore.
+            // SAFETY：它来自`set_test_runtime`中的`Box::into_raw`，而且已经没有人在使用它了。
+            drop(unsafe { Box::from_raw(runtime) });
+        }
+    }
+
+    /// The timer keeps every registered deadline in a min-heap so that the
+    /// earliest one is always on top, no matter what order they were
+    /// registered in. Each deadline is paired with a registration id, which
+    /// both breaks ties between equal deadlines in registration order and lets
+    /// us find the `Waker` to call in `wakers`. A `Sleep` that gets dropped
+    /// removes its waker from `wakers` and we skip its heap entry when it comes
+    /// up.
+    /// 定时器把每个注册的截止时间都放在一个最小堆中，这样无论它们以什么顺序注册，最早的那个总是在堆顶。
+    /// 每个截止时间都和一个注册id配对，它既可以按照注册顺序打破相同截止时间之间的平局，也可以让我们在`wakers`中找到要调用的`Waker`。
+    /// 被丢弃的`Sleep`会从`wakers`中移除它的waker，当它的堆条目出现时我们就跳过它。
+    pub(crate) struct Timer {
+        /// The deadlines ordered earliest first.
+        /// 按照最早优先排序的截止时间。
+        deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+        /// The wakers to call for each registration that's still wanted.
+        /// 每个仍然需要的注册所对应的waker。
+        wakers: HashMap<u64, Waker>,
+        /// The id the next registration will get.
+        /// 下一个注册将会得到的id。
+        next_id: u64,
+    }
+
+    impl Timer {
+        /// Creates a timer with nothing registered.
+        /// 创建一个没有任何注册的定时器。
+        fn new() -> Self {
+            Self {
+                deadlines: BinaryHeap::new(),
+                wakers: HashMap::new(),
+                next_id: 0,
+            }
+        }
+
+        /// Registers `waker` to be woken once `deadline` passes.
+        /// 注册`waker`，在`deadline`过去后唤醒它。
+        fn register(&mut self, deadline: Instant, waker: Waker) -> u64 {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.deadlines.push(Reverse((deadline, id)));
+            self.wakers.insert(id, waker);
+            id
+        }
+
+        /// When the earliest registration is due, if there is one.
+        /// 最早的注册什么时候到期，如果有的话。
+        fn next_deadline(&self) -> Option<Instant> {
+            self.deadlines
+                .peek()
+                .map(|Reverse((deadline, _))| *deadline)
+        }
+
+        /// Takes out the waker of every registration whose deadline has passed,
+        /// earliest first. We hand them back rather than waking them here so
+        /// that they get woken after the lock on the timer is released, which
+        /// means a `Waker` that touches the timer itself can't deadlock.
+        /// 按照最早优先的顺序取出每个截止时间已经过去的注册的waker。我们把它们返回，而不是在这里唤醒它们，
+        /// 这样它们会在定时器的锁释放之后才被唤醒，这意味着一个会访问定时器本身的`Waker`不会造成死锁。
+        fn expired(&mut self) -> Vec<Waker> {
+            let now = Instant::now();
+            let mut expired = Vec::new();
+            while let Some(Reverse((deadline, id))) = self.deadlines.peek().copied() {
+                if deadline > now {
+                    break;
+                }
+                self.deadlines.pop();
+                expired.extend(self.wakers.remove(&id));
+            }
+            expired
+        }
+    }
+
+    /// Asks the timer of the runtime we're running on to wake `waker` once
+    /// `deadline` has passed. Outside of a runtime that's the global one.
+    /// 让我们正在运行的运行时的定时器在`deadline`过去后唤醒`waker`。在运行时之外就是全局运行时。
+    pub(crate) fn register_timer(deadline: Instant, waker: Waker) -> TimerEntry {
+        let shared = Spawner::current().shared;
+        let id = shared.add_timer(deadline, waker);
+        TimerEntry { shared, id }
+    }
+
+    impl Shared {
+        /// Puts `waker` on the timer for `deadline` and hands back its id.
+        /// Nothing takes it off again unless it fires or the id is removed.
+        /// 把`waker`放到定时器上等待`deadline`，并交出它的id。除非它触发了或者这个id被移除了，否则没有东西会把它再拿下来。
+        fn add_timer(&self, deadline: Instant, waker: Waker) -> u64 {
+            let (id, earliest) = {
+                let mut timer = self.timer.lock().unwrap();
+                let id = timer.register(deadline, waker);
+                (id, timer.next_deadline() == Some(deadline))
+            };
+            // The timer thread might be asleep waiting on a later deadline than
+            // this one, so it needs to wake up and take another look.
+            // 定时器线程可能正在睡觉，等待的是比这个更晚的截止时间，所以它需要醒过来再看一眼。
+            if earliest {
+                if let Some(timer_thread) = self.timer_thread.get() {
+                    timer_thread.unpark();
+                }
+            }
+            id
+        }
+    }
+
+    /// A registration with a runtime's timer. If it gets dropped before it
+    /// fires, say because the `Sleep` it belongs to was dropped since whatever
+    /// was awaiting it got cancelled, then there's no point in the timer
+    /// holding on to the waker and waking a task that doesn't care anymore.
+    /// 在运行时定时器中的一个注册。如果它在触发之前被丢弃了，比如它所属的`Sleep`因为等待它的东西被取消而被丢弃，
+    /// 那么定时器就没有必要继续持有waker去唤醒一个已经不关心它的任务。
+    pub(crate) struct TimerEntry {
+        shared: Arc<Shared>,
+        id: u64,
+    }
+
+    impl TimerEntry {
+        /// Makes sure it's `waker` that gets woken when the timer fires, unless
+        /// it already has.
+        /// 确保定时器触发时被唤醒的是`waker`，除非它已经触发过了。
+        pub(crate) fn update(&self, waker: &Waker) {
+            let mut timer = self.shared.timer.lock().unwrap();
+            if let Some(stored) = timer.wakers.get_mut(&self.id) {
+                if !stored.will_wake(waker) {
+                    *stored = waker.clone();
+                }
+            }
+        }
+    }
+
+    impl Drop for TimerEntry {
+        fn drop(&mut self) {
+            let waker = self.shared.timer.lock().unwrap().wakers.remove(&self.id);
+            drop(waker);
+        }
+    }
+
+    // The queue is a single linked list that contains all of the tasks being
+    // run on it. We hand out access to it using a Mutex inside of the shared
+    // state so that we can make sure only one thing is touching the queue
+    // state at a given time. This isn't the most efficient pattern especially
+    // with more than one worker fighting over the lock, but for the purposes
+    // of the code this works just fine.
+    // 队列是一个单链表，其中包含在其上运行的所有任务。
+    // 我们使用共享状态中的一个 Mutex 来访问它，以便我们可以确保在给定时间只有一个「事物」能够获取队列状态。
+    // 这种模式不是最高效的，特别是在多个工作线程争抢这把锁时，但是对于这段代码来说，这是可以的。
+
+    /// How many spare nodes `TaskQueue` will hold on to. Anything past this is
+    /// handed back to the allocator so that a single huge burst of tasks doesn't
+    /// pin that memory forever.
+    /// `TaskQueue`最多保留多少个空闲节点。超过这个数量的节点会被归还给分配器，
+    /// 这样一次巨大的任务突发就不会永远占用那部分内存。
+    const MAX_SPARE_NODES: usize = 1024;
+
+    /// A `LinkedList` of tasks that recycles its nodes. The trick is that the
+    /// standard library lets us move nodes between lists without touching the
+    /// allocator with `split_off` and `append`. So rather than popping a task
+    /// and freeing its node, we split the front node off into its own one
+    /// element list, take the task out of it, and keep that empty node around
+    /// in `spare`. The next push reuses it by filling it back up and appending
+    /// it onto the queue. Nodes hold an `Option` so they can sit empty.
+    /// 一个会复用节点的任务`LinkedList`。诀窍在于标准库的`split_off`和`append`可以让我们在链表之间移动节点而不需要调用分配器。
+    /// 因此，我们不是弹出任务并释放其节点，而是把队首节点拆分成一个只有一个元素的链表，取出其中的任务，并将这个空节点保存在`spare`中。
+    /// 下一次push时，我们把任务填回这个节点并把它追加到队列上来复用它。节点持有一个`Option`，这样它们就可以为空。
+    pub(crate) struct TaskQueue {
+        /// The tasks waiting to be polled. Every node in here holds `Some`.
+        /// 等待被poll的任务，这里的每个节点都持有`Some`。
+        list: LinkedList<Option<Arc<Task>>>,
+        /// Empty single node lists waiting to be reused by the next push.
+        /// 等待被下一次push复用的空的单节点链表。
+        spare: Vec<LinkedList<Option<Arc<Task>>>>,
+        /// How many nodes we've ever had to ask the allocator for.
+        /// 我们总共向分配器申请过多少个节点。
+        allocated: usize,
+        /// How many of the tasks in `list` have never been polled, which is
+        /// what `Builder::queue_capacity` counts.
+        /// `list`中有多少个任务从来没有被poll过，这就是`Builder::queue_capacity`所计算的。
+        fresh: usize,
+    }
+
+    impl TaskQueue {
+        /// Creates an empty queue without any spare nodes.
+        /// 创建一个没有任何空闲节点的空队列。
+        fn new() -> Self {
+            Self {
+                list: LinkedList::new(),
+                spare: Vec::new(),
+                allocated: 0,
+                fresh: 0,
+            }
+        }
+
+        /// Gets a single node list holding `task`, reusing a spare node if we
+        /// have one and only allocating when we've run out.
+        /// 获取一个持有`task`的单节点链表，如果有空闲节点就复用它，只有在用完时才分配新的节点。
+        fn node(&mut self, task: Arc<Task>) -> LinkedList<Option<Arc<Task>>> {
+            match self.spare.pop() {
+                Some(mut node) => {
+                    if let Some(slot) = node.front_mut() {
+                        *slot = Some(task);
+                    }
+                    node
+                }
+                None => {
+                    self.allocated += 1;
+                    let mut node = LinkedList::new();
+                    node.push_back(Some(task));
+                    node
+                }
+            }
+        }
+
+        /// Pushes a task onto the back of the queue.
+        /// 将任务推送到队列的末尾。
+        fn push_back(&mut self, task: Arc<Task>) {
+            self.fresh += task.is_fresh() as usize;
+            let mut node = self.node(task);
+            self.list.append(&mut node);
+        }
+
+        /// Pushes a task onto the front of the queue.
+        /// 将任务推送到队列的前端。
+        fn push_front(&mut self, task: Arc<Task>) {
+            self.fresh += task.is_fresh() as usize;
+            let mut node = self.node(task);
+            node.append(&mut self.list);
+            self.list = node;
+        }
+
+        /// Pops the task at the front of the queue. `split_off(1)` only has to
+        /// walk a single node so this stays O(1), and the emptied node goes
+        /// into `spare` unless we already have plenty.
+        /// 弹出队首的任务。`split_off(1)`只需要遍历一个节点，所以仍然是O(1)的，
+        /// 清空后的节点会放入`spare`，除非我们已经有足够多的空闲节点了。
+        fn pop_front(&mut self) -> Option<Arc<Task>> {
+            if self.list.is_empty() {
+                return None;
+            }
+            let rest = self.list.split_off(1);
+            let mut node = std::mem::replace(&mut self.list, rest);
+            let task = node.front_mut().and_then(Option::take);
+            if self.spare.len() < MAX_SPARE_NODES {
+                self.spare.push(node);
+            }
+            if task.as_ref().is_some_and(|task| task.is_fresh()) {
+                self.fresh -= 1;
+            }
+            task
+        }
+
+        /// Takes out the task that's been waiting the longest out of the ones
+        /// that have never been polled, wherever it is in the queue. Anything
+        /// in front of it stays where it was.
+        /// 从那些从来没有被poll过的任务中取出等待最久的那一个，无论它在队列中的什么位置。在它前面的任何东西都留在原地。
+        fn remove_oldest_fresh(&mut self) -> Option<Arc<Task>> {
+            let index = self
+                .list
+                .iter()
+                .position(|task| task.as_ref().is_some_and(|task| task.is_fresh()))?;
+            let mut rest = self.list.split_off(index);
+            std::mem::swap(&mut self.list, &mut rest);
+            let task = self.pop_front();
+            rest.append(&mut self.list);
+            self.list = rest;
+            task
+        }
+    }
+
+    /// We've talked about the `Spawner` a lot up till this point, but it's
+    /// really just a light wrapper around a runtime's shared state that knows
+    /// how to push tasks onto the queue and create new ones.
+    /// 我们一直在讨论 `Spawner`，但它实际上只是一个包装运行时共享状态的轻量级包装器，它知道如何将任务推送到队列中并创建新任务。
+    #[derive(Clone)]
+    pub(crate) struct Spawner {
+        shared: Arc<Shared>,
+    }
+
+    thread_local! {
+        /// The `Spawner` of the runtime whose task is being polled on this
+        /// thread right now, if any. This is how a `spawn` or a `Sleep` inside
+        /// of a task knows which runtime it belongs to.
+        /// 当前线程上正在被poll的任务所属运行时的`Spawner`，如果有的话。
+        /// 任务中的`spawn`或者`Sleep`就是通过它知道自己属于哪个运行时的。
+        static CURRENT: RefCell<Option<Spawner>> = const { RefCell::new(None) };
+
+        /// Which runtime this thread is a worker of and which worker it is, if
+        /// it's a worker at all.
+        /// 这个线程是哪个运行时的工作线程，以及它是第几个工作线程，如果它是工作线程的话。
+        static WORKER: Cell<Option<(*const Shared, usize)>> = const { Cell::new(None) };
+
+        /// A number for this thread that no other thread has, so that it can
+        /// park on a runtime's `Parker` in `block_on_cooperative` and be woken
+        /// up by itself, without being one of the workers.
+        /// 一个这个线程独有的编号，这样它就可以在`block_on_cooperative`中park在一个运行时的`Parker`上，
+        /// 并且被单独唤醒，而不需要是工作线程之一。
+        static CALLER: usize = NEXT_CALLER.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number the next thread to call `block_on_cooperative` gets.
+    /// 下一个调用`block_on_cooperative`的线程会得到的编号。
+    static NEXT_CALLER: AtomicUsize = AtomicUsize::new(0);
+
+    /// The `Waker` `spawn_after` puts on the timer. Waking it is what spawns
+    /// the task. The runtime is only held weakly, since the timer holds on
+    /// to us and a runtime that holds on to itself would never get dropped.
+    /// `spawn_after`放到定时器上的`Waker`。唤醒它就是生成任务的方式。运行时只被弱引用持有，
+    /// 因为定时器持有着我们，而一个持有着自己的运行时永远不会被丢弃。
+    struct Deferred {
+        shared: Weak<Shared>,
+        spawn: Mutex<Option<DeferredSpawn>>,
+    }
+
+    /// What `Deferred` does once the timer fires.
+    /// 一旦定时器触发，`Deferred`要做的事情。
+    type DeferredSpawn = Box<dyn FnOnce(Spawner) + Send>;
+
+    impl Wake for Deferred {
+        fn wake(self: Arc<Self>) {
+            let spawn = self.spawn.lock().unwrap().take();
+            if let (Some(spawn), Some(shared)) = (spawn, self.shared.upgrade()) {
+                spawn(Spawner { shared });
+            }
+        }
+    }
+
+    impl Spawner {
+        /// Gets the `Spawner` of the runtime we're running on, or of the global
+        /// runtime if we're not inside of a task.
+        /// 获取我们正在运行的运行时的`Spawner`，如果我们不在任务中，就获取全局运行时的。
+        pub(crate) fn current() -> Spawner {
+            CURRENT
+                .with(|current| current.borrow().clone())
+                .unwrap_or_else(|| Runtime::get().spawner())
+        }
+
+        /// This is the function that gets called by the `spawn` function to
+        /// actually create a new `Task` in our queue. It takes the `Future`,
+        /// constructs a `Task` and then pushes it to the back of the queue.
+        /// 这是 `spawn` 函数，用于在队列中实际创建新的 `Task`。
+        /// 它接收 `Future`，构造一个 `Task`，然后将其推送到队列的末尾。
+        fn spawn<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawn_with(None, Priority::Normal, future)
+        }
+
+        fn try_spawn<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> Result<JoinHandle<T>, SpawnError> {
+            self.try_spawn_with(None, Priority::Normal, future)
+        }
+
+        /// Every kind of non-blocking spawn ends up here. We wrap the `Future`
+        /// in one that hands its output over to the `JoinHandle`, since the
+        /// runtime itself only knows how to run futures that return `()`.
+        /// 每一种非阻塞的生成最终都会来到这里。我们把`Future`包装进另一个会把它的输出交给`JoinHandle`的future里，
+        /// 因为运行时本身只知道如何运行返回`()`的future。
+        fn spawn_with<T: Send + 'static>(
+            &self,
+            name: Option<String>,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            // A task the runtime won't take gets a `JoinHandle` that says it
+            // was cancelled.
+            // 运行时不接受的任务会得到一个表明它被取消了的`JoinHandle`。
+            self.try_spawn_with(name, priority, future)
+                .unwrap_or_else(|_| JoinHandle::cancelled())
+        }
+
+        /// The same as `spawn_with` but we get told when the runtime won't
+        /// take the task.
+        /// 和`spawn_with`一样，不过当运行时不接受这个任务时我们会得到通知。
+        fn try_spawn_with<T: Send + 'static>(
+            &self,
+            name: Option<String>,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> Result<JoinHandle<T>, SpawnError> {
+            let (handle, task) = match self.new_task(name, priority, future) {
+                Err(SpawnError::QueueFull)
+                    if self.shared.overflow_policy == OverflowPolicy::DropNewest =>
+                {
+                    return Ok(JoinHandle::cancelled());
+                }
+                spawned => spawned?,
+            };
+            self.inner_spawn(task);
+            Ok(handle)
+        }
+
+        /// Makes the `Task` for a non-blocking spawn without putting it on the
+        /// queue, so that the caller gets to decide where on it the task goes.
+        /// 为一次非阻塞的生成创建`Task`，但不把它放到队列上，这样调用者可以决定任务放在队列的哪里。
+        fn new_task<T: Send + 'static>(
+            &self,
+            name: Option<String>,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> Result<(JoinHandle<T>, Arc<Task>), SpawnError> {
+            self.shared.admit()?;
+            Ok(self.admitted_task(name, priority, future))
+        }
+
+        /// The part of `new_task` that comes after the runtime has said it'll
+        /// take the task.
+        /// `new_task`中在运行时已经说了它会接受这个任务之后的那部分。
+        fn admitted_task<T: Send + 'static>(
+            &self,
+            name: Option<String>,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> (JoinHandle<T>, Arc<Task>) {
+            let (mut handle, completer) = JoinHandle::new();
+            let task = Task::new(self.clone(), false, name, priority, async move {
+                completer.complete(future.await);
+            });
+            handle.task = Arc::downgrade(&task);
+            let _ = task.handle.set(handle.state.clone());
+            (handle, task)
+        }
+
+        /// Spawns one task per stage, each one taking values from the channel
+        /// behind it and sending them on to the channel in front of it. A
+        /// stage stops once the channel it takes from is empty and every
+        /// `Sender` for it is gone, which drops its own `Sender`, so closing
+        /// the input ripples through every stage down to the output.
+        /// 为每个阶段生成一个任务，每个任务从它后面的通道取出值，再把它们发送到它前面的通道。
+        /// 一旦一个阶段所取值的通道空了并且它的所有`Sender`都没了，这个阶段就会停下来，这会丢弃它自己的`Sender`，
+        /// 所以关闭输入会像涟漪一样传递过每一个阶段，一直到输出。
+        fn pipeline<T: Send + 'static>(
+            &self,
+            stages: Vec<Stage<T>>,
+        ) -> (mpsc::Sender<T>, mpsc::Receiver<T>) {
+            let (input, mut rx) = mpsc::channel();
+            for stage in stages {
+                let (tx, next) = mpsc::channel();
+                let mut from = std::mem::replace(&mut rx, next);
+                // The stage's `BoxFuture` isn't `Sync`, and neither might `T`
+                // be, so this goes through `spawn_boxed`, which only needs the
+                // task to be `Send`.
+                // 阶段的`BoxFuture`不是`Sync`的，`T`也可能不是，所以这里走的是`spawn_boxed`，它只需要任务是`Send`的。
+                self.spawn_boxed(Box::pin(async move {
+                    while let Some(value) = from.recv().await {
+                        let output = stage(value).await;
+                        // Nobody's listening at the end anymore, so there's no
+                        // point carrying on.
+                        // 末端已经没有人在听了，所以没有必要继续下去。
+                        if tx.send(output).is_err() {
+                            break;
+                        }
+                    }
+                }));
+            }
+            (input, rx)
+        }
+
+        /// The same as `spawn` but the `Task` goes to the front of the queue
+        /// instead of the back, ahead of everything that's already waiting.
+        /// 和`spawn`一样，不过`Task`会去队列的前端而不是末尾，排在所有已经在等待的东西前面。
+        fn spawn_front<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            match self.new_task(None, Priority::Normal, future) {
+                Ok((handle, task)) => {
+                    self.inner_spawn_blocking(task);
+                    handle
+                }
+                Err(_) => JoinHandle::cancelled(),
+            }
+        }
+
+        /// There's no work to do to get to `value`, so the `JoinHandle` gets it
+        /// right away. We still spawn an empty task for it though so that it
+        /// counts as a task like any other, like when we `wait` on all of them.
+        /// 得到`value`不需要做任何工作，所以`JoinHandle`立刻就能拿到它。
+        /// 不过我们仍然为它生成一个空任务，这样它就和其他任务一样被算作一个任务，比如在我们`wait`所有任务时。
+        fn spawn_ready<T: Send + 'static>(&self, value: T) -> JoinHandle<T> {
+            if self.shared.admit().is_err() {
+                return JoinHandle::cancelled();
+            }
+            let (handle, completer) = JoinHandle::new();
+            completer.complete(value);
+            let task = Task::new(self.clone(), false, None, Priority::Normal, async {});
+            self.inner_spawn(task);
+            handle
+        }
+
+        /// Polls `future` once right here instead of putting it on the queue.
+        /// If it's already done, the `JoinHandle` gets the value and that's it:
+        /// nothing ever goes on the queue. Otherwise it carries on like any
+        /// other task, since its `Waker` puts it on the queue once it's worth
+        /// polling again.
+        /// 就在这里poll `future`一次，而不是把它放到队列上。如果它已经完成了，`JoinHandle`就拿到这个值，然后就结束了：
+        /// 永远不会有东西进入队列。否则它就像任何其他任务一样继续下去，因为一旦值得再次poll它，它的`Waker`就会把它放到队列上。
+        ///
+        /// That first poll goes through a real `Task`, the same one a worker
+        /// would poll, so `checkpoint`, `context`, slow poll reports and the
+        /// CPU budget all work in it, and the task is counted and shows up in
+        /// the `Metrics` like any other.
+        /// 第一次poll是通过一个真正的`Task`进行的，和工作线程会poll的是同一个，所以`checkpoint`、`context`、慢poll报告和CPU预算在其中都能工作，
+        /// 而且这个任务会像其他任何任务一样被计数，并出现在`Metrics`里。
+        fn spawn_eager<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            let (handle, task) = match self.new_task(None, Priority::Normal, future) {
+                Ok(spawned) => spawned,
+                Err(_) => return JoinHandle::cancelled(),
+            };
+            // The task isn't on the queue, so a wake up during this poll has
+            // to put it there, the same as for one a worker took off of it.
+            // 任务不在队列上，所以这次poll期间的一次唤醒必须把它放到队列上，和工作线程从队列上取下的任务一样。
+            task.scheduled.store(false, Ordering::Release);
+            // Whichever way the poll goes there's nothing left for us to do. A
+            // task that's done gets dropped along with our `Arc`, and one that
+            // isn't lives on in its `Waker`.
+            // 无论这次poll的结果如何，我们都没有什么要做的了。一个完成了的任务会随着我们的`Arc`一起被丢弃，而一个没有完成的会活在它的`Waker`里。
+            let _ = task.poll();
+            handle
+        }
+
+        /// Moves `future` into `arena` and spawns the small handle to it that
+        /// `Arena::alloc` gives back. A future that doesn't fit gets spawned
+        /// the usual way instead.
+        /// 把`future`移进`arena`，然后生成`Arena::alloc`交还的那个指向它的小handle。一个放不下的future会改用平常的方式生成。
+        fn spawn_in<T: Send + 'static>(
+            &self,
+            arena: &Arena,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            match arena.alloc(future) {
+                Ok(future) => self.spawn(future),
+                Err(future) => self.spawn(future),
+            }
+        }
+
+        /// Waits `ms` milliseconds on the timer and only then makes the task
+        /// and puts it on the queue. Until then there's nothing on the queue
+        /// and no task is counted, it's just one more entry on the timer.
+        /// 在定时器上等待`ms`毫秒，然后才创建任务并把它放到队列上。在那之前队列上什么都没有，也没有任务被计数，它只是定时器上多出来的一个条目。
+        fn spawn_after<T: Send + 'static>(
+            &self,
+            ms: u128,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            let (handle, completer) = JoinHandle::new();
+            let state: Arc<dyn HandleState> = handle.state.clone();
+            let spawn = move |spawner: Spawner| {
+                // The runtime might have filled up or closed while we waited,
+                // in which case dropping the `Completer` cancels the handle.
+                // 在我们等待的时候运行时可能已经满了或者关闭了，这种情况下丢弃`Completer`会取消这个handle。
+                if spawner.shared.admit().is_err() {
+                    return;
+                }
+                let task = Task::new(spawner.clone(), false, None, Priority::Normal, async move {
+                    completer.complete(future.await);
+                });
+                let _ = task.handle.set(state);
+                spawner.inner_spawn(task);
+            };
+            let deferred = Arc::new(Deferred {
+                shared: Arc::downgrade(&self.shared),
+                spawn: Mutex::new(Some(Box::new(spawn))),
+            });
+            self.shared
+                .add_timer(deadline_in(ms), Waker::from(deferred));
+            handle
+        }
+
+        /// Spawns a task whose polls, added up, can only take `budget`.
+        /// 生成一个所有poll加起来只能花`budget`时间的任务。
+        fn spawn_cpu_bounded<T: Send + 'static>(
+            &self,
+            budget: Duration,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            match self.new_task(None, Priority::Normal, future) {
+                Ok((handle, task)) => {
+                    let _ = task.cpu_budget.set(budget);
+                    self.inner_spawn(task);
+                    handle
+                }
+                Err(_) => JoinHandle::cancelled(),
+            }
+        }
+
+        /// Spawns a task that first calls `f` to make its `Future` and then
+        /// drives that. `f` only has to be `Send`, while our tasks also need to
+        /// be `Sync`, so we keep it in a `Mutex` until it's called.
+        /// 生成一个任务，它首先调用`f`来创建它的`Future`，然后驱动这个`Future`。
+        /// `f`只需要是`Send`的，而我们的任务还需要是`Sync`的，所以在它被调用之前我们把它放在一个`Mutex`里。
+        fn spawn_fn<F, Fut, T>(&self, f: F) -> JoinHandle<T>
+        where
+            F: FnOnce() -> Fut + Send + 'static,
+            Fut: Future<Output = T> + Send + Sync + 'static,
+            T: Send + 'static,
+        {
+            let f = Mutex::new(Some(f));
+            self.spawn(async move {
+                let f = f.lock().unwrap().take().unwrap();
+                f().await
+            })
+        }
+
+        /// The same as `spawn` but the `Task` gets a name to go with it.
+        /// 和`spawn`一样，不过`Task`会带上一个名字。
+        fn spawn_named<T: Send + 'static>(
+            &self,
+            name: &str,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawn_with(Some(name.to_owned()), Priority::Normal, future)
+        }
+
+        /// The same as `spawn` but the `Task` gets the given `Priority`.
+        /// 和`spawn`一样，不过`Task`会得到给定的`Priority`。
+        fn spawn_with_priority<T: Send + 'static>(
+            &self,
+            priority: Priority,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            self.spawn_with(None, priority, future)
+        }
+        /// This is the function that gets called by the `spawn_blocking` function to
+        /// actually create a new `Task` in our queue. It takes the `Future`,
+        /// constructs a `Task` and then pushes it to the front of the queue
+        /// where the runtime will check if it should block and then block until
+        /// this future completes.
+        /// 这是 `spawn_blocking` 函数，用于在队列中实际创建新的 `Task`。
+        /// 它接收 `Future`，构造一个 `Task`，然后将其推送到队列的前端，运行时将检查它是否应该阻塞，然后阻塞直到此 future 完成。
+        fn spawn_blocking(&self, future: impl Future<Output = ()> + Send + Sync + 'static) {
+            if self.shared.admit().is_err() {
+                return;
+            }
+            let task = Task::new(self.clone(), true, None, Priority::Normal, future);
+            if self.shared.dedicated_blocking {
+                // Claiming it up front means a wake up only ever unparks the
+                // thread, it never puts the task on the queue for a worker.
+                // 提前认领它意味着一次唤醒只会unpark这个线程，永远不会把任务放到队列上交给工作线程。
+                task.claimed.store(true, Ordering::Release);
+                thread::spawn(move || {
+                    while task.poll().is_pending() {
+                        task.parker.park();
+                    }
+                });
+                return;
+            }
+            self.inner_spawn_blocking(task);
+        }
+        /// The same as `spawn` but the `Task` stays on whichever worker polls
+        /// it first.
+        /// 和`spawn`一样，不过`Task`会一直待在第一个poll它的工作线程上。
+        fn spawn_affine<T: Send + 'static>(
+            &self,
+            future: impl Future<Output = T> + Send + Sync + 'static,
+        ) -> JoinHandle<T> {
+            match self.new_task(None, Priority::Normal, future) {
+                Ok((handle, task)) => {
+                    *task.home.lock().unwrap() = Home::Unsettled;
+                    self.inner_spawn(task);
+                    handle
+                }
+                Err(_) => JoinHandle::cancelled(),
+            }
+        }
+
+        /// Spawns a `Future` that's already boxed as it is. There's no
+        /// `JoinHandle` since handing the output over to one would mean
+        /// wrapping the `Future` in another one, and boxing that all over
+        /// again.
+        /// 按原样生成一个已经装箱了的`Future`。这里没有`JoinHandle`，因为把输出交给它就意味着要把`Future`包装进另一个future里，然后把它再装箱一遍。
+        fn spawn_boxed(&self, future: BoxFuture<'static, ()>) {
+            if self.shared.admit().is_ok() {
+                let task = Task::new_boxed(self.clone(), false, None, Priority::Normal, future);
+                self.inner_spawn(task);
+            }
+        }
+
+        /// Spawns a whole batch of tasks at once. They all go on the queue
+        /// while we hold the lock once, and then we wake the workers once for
+        /// the lot instead of once for every task. A runtime with a
+        /// `queue_capacity` is the exception: whether there's room for a task
+        /// depends on the ones before it already being on the queue, so there
+        /// they go on one at a time, the same as with `spawn`.
+        /// 一次生成一整批任务。它们在我们只获取一次锁的期间全部进入队列，然后我们为这一整批只唤醒一次工作线程，而不是每个任务唤醒一次。
+        /// 有`queue_capacity`的运行时是个例外：一个任务有没有空间取决于它前面的那些是否已经在队列上了，
+        /// 所以在那里它们是一个一个放上去的，和`spawn`一样。
+        fn spawn_many<T: Send + 'static, F>(
+            &self,
+            futures: impl IntoIterator<Item = F>,
+        ) -> Vec<JoinHandle<T>>
+        where
+            F: Future<Output = T> + Send + Sync + 'static,
+        {
+            let bounded = self.shared.queue_capacity.is_some();
+            let mut tasks = Vec::new();
+            let handles = futures
+                .into_iter()
+                .map(
+                    |future| match self.new_task(None, Priority::Normal, future) {
+                        Ok((handle, task)) if bounded => {
+                            self.inner_spawn(task);
+                            handle
+                        }
+                        Ok((handle, task)) => {
+                            tasks.push(task);
+                            handle
+                        }
+                        Err(_) => JoinHandle::cancelled(),
+                    },
+                )
+                .collect();
+            if !tasks.is_empty() {
+                let mut queue = self.shared.queue.lock().unwrap();
+                self.shared.enqueues.fetch_add(1, Ordering::Relaxed);
+                tasks.into_iter().for_each(|task| queue.push_back(task));
+                drop(queue);
+                self.shared.work.unpark_all();
+            }
+            handles
+        }
+
+        /// This function just takes a `Task` and pushes it onto the queue. We use this
+        /// both for spawning new `Task`s and to push old ones that get woken up
+        /// back onto the queue.
+        /// 这个函数只是接收一个 `Task` 并将其推送到队列中。
+        /// 我们用它来启动新的 `Task`，以及将唤醒的旧任务推送回队列。
+        fn inner_spawn(&self, task: Arc<Task>) {
+            self.shared.queue.lock().unwrap().push_back(task);
+            self.shared.enqueues.fetch_add(1, Ordering::Relaxed);
+            self.shared.work.unpark();
+        }
+        /// This function takes a `Task` and pushes it to the front of the queue
+        /// if it is meant to block. We use this both for spawning new blocking
+        /// `Task`s and to push old ones that get woken up back onto the queue.
+        /// `spawn_front` uses it too, for non-blocking tasks that want to go
+        /// first.
+        /// 如果它是用于阻塞的，则此函数将 `Task` 推送到队列的前端。
+        /// 我们用它来启动新的阻塞 `Task`，以及将唤醒的旧任务推送回队列。
+        /// `spawn_front`也会用到它，用于那些想要排在最前面的非阻塞任务。
+        fn inner_spawn_blocking(&self, task: Arc<Task>) {
+            self.shared.queue.lock().unwrap().push_front(task);
+            self.shared.enqueues.fetch_add(1, Ordering::Relaxed);
+            self.shared.work.unpark();
+        }
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime. If we're inside
+    /// of a task that's the runtime the task is running on, otherwise it's the
+    /// global one.
+    /// 将非阻塞的 `Future` 放入 `whorl` 运行时。如果我们在一个任务中，那就是这个任务所在的运行时，否则就是全局运行时。
+    pub fn spawn<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn(future)
+    }
+
+    /// Spawn a task that gets started over whenever it panics, like a
+    /// supervisor in Erlang would. Each attempt runs a brand new future from
+    /// `mk`, since the one that panicked can't be trusted to carry on. Once
+    /// an attempt finishes we hand back its output, so a task that finishes
+    /// normally never reruns. After `max_retries` restarts we stop trying and
+    /// hand back the payload of the last panic instead.
+    /// 生成一个每当它panic时就会重新开始的任务，就像Erlang中的监督者会做的那样。每次尝试都会运行一个来自`mk`的全新future，
+    /// 因为那个panic了的future不能被信任去继续下去。一旦某次尝试完成了，我们就交出它的输出，所以一个正常完成的任务永远不会重新运行。
+    /// 在重启了`max_retries`次之后，我们就不再尝试，转而交出最后一次panic的载荷。
+    pub fn spawn_resilient<T, F, Fut>(
+        max_retries: usize,
+        mk: F,
+    ) -> JoinHandle<Result<T, Box<dyn std::any::Any + Send>>>
+    where
+        T: Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + Sync + 'static,
+    {
+        spawn(async move {
+            let mut restarts = 0;
+            loop {
+                match mk().catch_unwind().await {
+                    Ok(output) => return Ok(output),
+                    Err(payload) if restarts == max_retries => return Err(payload),
+                    Err(_) => restarts += 1,
+                }
+            }
+        })
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime unless the
+    /// runtime won't take it, in which case you get told why.
+    /// 将非阻塞的 `Future` 放入 `whorl` 运行时，除非运行时不接受它，这种情况下你会被告知原因。
+    pub fn try_spawn<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> Result<JoinHandle<T>, SpawnError> {
+        Spawner::current().try_spawn(future)
+    }
+
+    /// Spawn a non-blocking `Future` from inside of a `Drop`, for some last bit
+    /// of cleanup that needs to `await`. This never panics, since a panic in a
+    /// destructor that's running because of another panic aborts the whole
+    /// process. If the runtime won't take the task it just doesn't run. When
+    /// a task's `Future` gets dropped we make its runtime the current one and
+    /// only count the task as gone once its `Future` is, so whatever gets
+    /// spawned from in there runs before `wait` returns.
+    /// 在`Drop`内部生成一个非阻塞的`Future`，用于一些需要`await`的最后清理工作。它永远不会panic，
+    /// 因为一个因为另一个panic而运行的析构函数中的panic会让整个进程中止。如果运行时不接受这个任务，它就不会运行。
+    /// 当一个任务的`Future`被丢弃时，我们会把它所在的运行时设为当前运行时，并且只在它的`Future`被丢弃之后才把任务算作消失，
+    /// 所以从那里生成的任何东西都会在`wait`返回之前运行。
+    pub fn spawn_detached_from_drop(future: impl Future<Output = ()> + Send + Sync + 'static) {
+        // The thread might be exiting, in which case `CURRENT` could already
+        // be gone and `with` would panic.
+        // 线程可能正在退出，这种情况下`CURRENT`可能已经不在了，`with`会panic。
+        let spawner = CURRENT
+            .try_with(|current| current.borrow().clone())
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| Runtime::get().spawner());
+        let _ = spawner.try_spawn(future);
+    }
+
+    /// Why a runtime wouldn't take a task.
+    /// 运行时不接受一个任务的原因。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SpawnError {
+        /// The runtime is shutting down.
+        /// 运行时正在关闭。
+        ShutDown,
+        /// The runtime already has as many tasks as `Builder::max_tasks` allows.
+        /// 运行时已经有了`Builder::max_tasks`所允许的那么多任务。
+        TooManyTasks,
+        /// The queue is full and the `OverflowPolicy` is `Reject`.
+        /// 队列满了，并且`OverflowPolicy`是`Reject`。
+        QueueFull,
+    }
+
+    /// What a spawn does when the queue already holds as many new tasks as
+    /// `Builder::queue_capacity` allows. These are the usual ways a system
+    /// that's getting more work than it can keep up with sheds load.
+    /// 当队列已经持有`Builder::queue_capacity`所允许的那么多新任务时，一次生成会做什么。
+    /// 这些是一个收到的工作多于它能跟上的系统通常用来卸载负载的方式。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OverflowPolicy {
+        /// The spawn waits until a task comes off the queue. This pushes back
+        /// on whoever is spawning, but a task spawning onto its own full
+        /// runtime can end up waiting on itself, so save it for spawns from
+        /// outside.
+        /// 生成会等待，直到有一个任务离开队列。这会反压正在生成的人，但是一个往它自己满了的运行时上生成的任务可能最终会等待它自己，
+        /// 所以把它留给从外部进行的生成。
+        Block,
+        /// The new task is dropped and its `JoinHandle` is already cancelled.
+        /// 新任务被丢弃，它的`JoinHandle`已经是被取消了的。
+        DropNewest,
+        /// The new task that's been waiting the longest is aborted to make
+        /// room. Tasks that were woken back up are never picked.
+        /// 等待最久的新任务被中止以腾出空间。被唤醒回来的任务永远不会被选中。
+        DropOldest,
+        /// `try_spawn` gives back `SpawnError::QueueFull`. `spawn` gives back
+        /// a cancelled `JoinHandle`, the same as `DropNewest`.
+        /// `try_spawn`会返回`SpawnError::QueueFull`。`spawn`会返回一个被取消了的`JoinHandle`，和`DropNewest`一样。
+        Reject,
+    }
+
+    /// What a task's `Waker` does with the task when it gets woken up.
+    /// Putting it straight back on the queue gets it polled the soonest, but
+    /// every wake up takes the queue's lock once, and a timer going off or a
+    /// latch opening can wake up hundreds of tasks in a row. Saving them up
+    /// and putting them on together takes the lock once for the lot, and
+    /// the tasks that were woken together get polled together, at the cost of
+    /// each one waiting until a worker gets around to its next tick.
+    /// 一个任务的`Waker`在任务被唤醒时对它做什么。直接把它放回队列能让它最快被poll，但是每次唤醒都要拿一次队列的锁，
+    /// 而一个定时器的触发或者一个门闩的打开可能会一口气唤醒几百个任务。把它们攒起来一起放上去只需要为这一整批拿一次锁，
+    /// 而且一起被唤醒的任务会一起被poll，代价是每一个都要等到某个工作线程进行它的下一次tick。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WakeStrategy {
+        /// The task goes back on the queue right away.
+        /// 任务立刻回到队列上。
+        Immediate,
+        /// The task waits with the others that were woken up until the start
+        /// of the next tick, which puts all of them on the queue in one go.
+        /// Only `Normal` priority tasks wait like this. Blocking ones, ones
+        /// with a `Priority` of their own and ones going back to their own
+        /// worker skip it, since they have somewhere else to be.
+        /// 任务和其他被唤醒的任务一起等到下一次tick开始，那次tick会一口气把它们全都放到队列上。
+        /// 只有`Normal`优先级的任务会这样等待。阻塞任务、有自己的`Priority`的任务以及回到它们自己的工作线程的任务会跳过它，因为它们有别的地方要去。
+        Batched,
+    }
+
+    impl fmt::Display for SpawnError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SpawnError::ShutDown => write!(f, "runtime is shutting down"),
+                SpawnError::TooManyTasks => write!(f, "runtime has too many tasks"),
+                SpawnError::QueueFull => write!(f, "runtime queue is full"),
+            }
+        }
+    }
+
+    impl std::error::Error for SpawnError {}
+
+    /// Spawn a task onto the `whorl` runtime that already holds `value`, for
+    /// when you sometimes have a value on hand and sometimes need to compute
+    /// it, but want a `JoinHandle` either way.
+    /// 将一个已经持有`value`的任务放入 `whorl` 运行时。用于有时手头已经有值、有时需要计算它，但无论哪种情况都想要一个`JoinHandle`的时候。
+    pub fn spawn_ready<T: Send + 'static>(value: T) -> JoinHandle<T> {
+        Spawner::current().spawn_ready(value)
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime `ms` milliseconds
+    /// from now. Unlike spawning a task that starts with a `Sleep`, nothing
+    /// goes on the queue until the time is up, and the task isn't made until
+    /// then either. That also means it isn't counted yet, so `wait` won't hang
+    /// around for it, and `abort` on the handle can't reach it. If the runtime
+    /// won't take the task once it's due, the handle says it was cancelled.
+    /// 在从现在起`ms`毫秒之后将一个非阻塞的`Future`放入 `whorl` 运行时。和生成一个以`Sleep`开头的任务不同，
+    /// 在时间到之前队列上什么都不会有，任务也要到那时才会被创建。这也意味着它还没有被计数，所以`wait`不会等它，
+    /// handle上的`abort`也碰不到它。如果到时候运行时不接受这个任务，handle会说它被取消了。
+    pub fn spawn_after<T: Send + 'static>(
+        ms: u128,
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_after(ms, future)
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime with its state
+    /// kept in `arena` rather than in a heap allocation of its own. The task
+    /// still gets boxed, but all that's in the box is a pointer into the
+    /// arena, so the future itself, which is where most of a task's memory
+    /// goes, can be seen piling up in one place and freed all at once with
+    /// `Arena::reset`. If it doesn't fit in what's left of the arena it's
+    /// spawned the usual way, which `Arena::allocations` can tell you about.
+    /// 将一个非阻塞的`Future`放入 `whorl` 运行时，它的状态保存在`arena`中，而不是在它自己的一块堆内存里。
+    /// 任务仍然会被装箱，但是box里只有一个指向arena的指针，所以future本身，也就是一个任务的大部分内存所在的地方，
+    /// 可以被看到堆积在同一个地方，并用`Arena::reset`一次性释放。如果arena剩下的空间放不下它，它就会用平常的方式生成，
+    /// `Arena::allocations`可以告诉你这一点。
+    pub fn spawn_in<T: Send + 'static>(
+        arena: &Arena,
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_in(arena, future)
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime that can only
+    /// spend `budget` being polled, all of its polls added up. Once it's past
+    /// that it gets aborted and its handle gives back `JoinError::CpuLimit`.
+    /// This is the guard rail for a future that does a lot of work between
+    /// its `await`s, or one that never reaches one at all, which a plain
+    /// timeout can't catch since a timeout only gets checked in between polls.
+    /// We can't stop a poll part way through either, so a single poll that
+    /// takes far too long gets caught as soon as it's over.
+    /// 将一个非阻塞的`Future`放入 `whorl` 运行时，它被poll的时间，所有poll加起来，只能有`budget`那么多。一旦超过了，它就会被中止，
+    /// 它的handle会返回`JoinError::CpuLimit`。这是给那些在`await`之间做了大量工作、或者根本就到不了一个`await`的future准备的护栏，
+    /// 普通的超时抓不住它们，因为超时只在两次poll之间才会被检查。我们也没法在一次poll的中途停下它，所以一次花了太久的poll会在它一结束就被抓住。
+    pub fn spawn_cpu_bounded<T: Send + 'static>(
+        budget: Duration,
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_cpu_bounded(budget, future)
+    }
+
+    /// Spawn a `Future` onto the `whorl` runtime, but poll it once on the
+    /// calling thread first. A future that's done after that one poll never
+    /// goes on the queue at all, and its `JoinHandle` is already finished. This
+    /// saves the trip through the queue for trivial futures, at the cost of
+    /// running a little of the future before `spawn_eager` returns.
+    /// 将一个`Future`放入 `whorl` 运行时，但先在调用线程上poll它一次。一个在这一次poll之后就完成了的future根本不会进入队列，
+    /// 它的`JoinHandle`也已经结束了。这为简单的future省去了经过队列的那一趟，代价是在`spawn_eager`返回之前会先运行future的一小部分。
+    ///
+    /// Plain `spawn` doesn't do this, even though it would save the same
+    /// trip. A `spawn` never runs any of the future on the calling thread,
+    /// and plenty relies on that: a paused runtime holding everything that's
+    /// spawned onto it, `queue_capacity` and `OverflowPolicy` seeing every
+    /// spawn, and `Observer::on_spawn` coming before the first poll. So the
+    /// fast path is something you ask for by calling this instead.
+    /// 普通的`spawn`不会这样做，即使它能省去同样的那一趟。一次`spawn`永远不会在调用线程上运行future的任何部分，而有很多东西依赖于这一点：
+    /// 一个暂停了的运行时会留住生成到它上面的所有东西，`queue_capacity`和`OverflowPolicy`能看到每一次生成，`Observer::on_spawn`会在第一次poll之前到来。
+    /// 所以这条快速路径是你通过调用它而不是`spawn`来要求的。
+    pub fn spawn_eager<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_eager(future)
+    }
+
+    /// Spawn a task onto the `whorl` runtime that calls `f` to make the
+    /// `Future` it runs. The call happens on the runtime, not on the thread
+    /// that is spawning, which matters if making the `Future` needs anything
+    /// from the thread it'll be running on.
+    /// 将一个任务放入 `whorl` 运行时，它会调用 `f` 来创建它要运行的 `Future`。
+    /// 这个调用发生在运行时上，而不是在生成任务的线程上，如果创建 `Future` 需要它将要运行的线程上的东西，这就很重要了。
+    pub fn spawn_fn<F, Fut, T>(f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        Spawner::current().spawn_fn(f)
+    }
+
+    /// Spawn a task onto the `whorl` runtime that owns `state`. We hand
+    /// `state` to `f` and spawn the `Future` it makes, which saves you from
+    /// cloning things into an `async move` block by hand, and if `state`
+    /// can't be sent to another thread the error points right at it rather
+    /// than at some `Future` you can't name.
+    /// 将一个拥有`state`的任务放入 `whorl` 运行时。我们把`state`交给`f`，然后生成它创建的`Future`，
+    /// 这省去了你手动把东西克隆进一个`async move`块里的麻烦，而且如果`state`不能被发送到另一个线程，
+    /// 错误会直接指向它，而不是指向某个你叫不出名字的`Future`。
+    pub fn spawn_with<S, F, Fut, T>(state: S, f: F) -> JoinHandle<T>
+    where
+        S: Send + 'static,
+        F: FnOnce(S) -> Fut,
+        Fut: Future<Output = T> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        spawn(f(state))
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime with a name that
+    /// shows up wherever the task does, like in an `Observer` or in `Debug`
+    /// output, so that you can tell your tasks apart.
+    /// 将非阻塞的 `Future` 放入 `whorl` 运行时，并给它一个名字。这个名字会出现在任务出现的地方，
+    /// 比如`Observer`或者`Debug`输出中，这样你就可以区分你的任务了。
+    pub fn spawn_named<T: Send + 'static>(
+        name: &str,
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_named(name, future)
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime with a
+    /// `Priority`. A `High` priority task gets the biggest share of polls
+    /// whenever it's woken up, which is handy for things that need to react
+    /// quickly, like a task handling incoming requests, while a `Low` one
+    /// gets the smallest, for background work that can wait.
+    /// 将一个带`Priority`的非阻塞 `Future` 放入 `whorl` 运行时。一个`High`优先级的任务每次被唤醒时都会得到最大的那一份poll，
+    /// 这对于需要快速响应的东西很有用，比如处理传入请求的任务；而一个`Low`的任务得到最小的那一份，用于可以等一等的后台工作。
+    pub fn spawn_with_priority<T: Send + 'static>(
+        priority: Priority,
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_with_priority(priority, future)
+    }
+
+    /// Spawn a non-blocking `Future` onto the front of the `whorl` runtime's
+    /// queue, so that it gets polled before all the tasks that are already
+    /// waiting there. That's good for latency when something just came in
+    /// and shouldn't have to wait behind a long backlog. Only its first poll
+    /// gets this treatment, after that it gets woken up onto the back of the
+    /// queue like any other task. If it should get ahead every time it's
+    /// woken up, spawn it with `Priority::High` instead.
+    /// 将非阻塞的 `Future` 放到 `whorl` 运行时队列的前端，这样它会在所有已经在那里等待的任务之前被poll。
+    /// 当某个东西刚刚到来、不应该排在一大堆积压的任务后面等待时，这有利于降低延迟。只有它的第一次poll会得到这种待遇，
+    /// 之后它被唤醒时就会像其他任务一样去队列的末尾。如果它每次被唤醒都应该领先，那就改用`Priority::High`来生成它。
+    pub fn spawn_front<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_front(future)
+    }
+
+    /// Spawn a non-blocking `Future` onto the `whorl` runtime that stays on
+    /// whichever worker polls it first. Every time it gets woken up it goes
+    /// back to that worker's own queue rather than the shared one. That
+    /// matters for a task that leaves something behind in the worker's
+    /// thread locals, like an I/O task that registered its socket with a
+    /// reactor living on that thread, since it has to come back to the same
+    /// thread to find it again. We don't have a reactor of our own, but
+    /// whatever wakes the task, a readiness event or anything else, routes it
+    /// back home the same way. A task that's polled somewhere other than on
+    /// a worker, like from `Runtime::tick`, just runs anywhere.
+    /// 将一个非阻塞的 `Future` 放入 `whorl` 运行时，它会一直待在第一个poll它的工作线程上。每次它被唤醒时，
+    /// 它都会回到那个工作线程自己的队列，而不是共享的队列。这对于一个在工作线程的线程局部变量中留下了东西的任务很重要，
+    /// 比如一个把它的socket注册到了住在那个线程上的reactor的I/O任务，因为它必须回到同一个线程才能再次找到它。
+    /// 我们没有自己的reactor，但是无论是什么唤醒了这个任务，一个就绪事件或者别的什么，都会以同样的方式把它送回家。
+    /// 一个在工作线程以外的地方被poll的任务，比如通过`Runtime::tick`，就可以在任何地方运行。
+    pub fn spawn_affine<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> JoinHandle<T> {
+        Spawner::current().spawn_affine(future)
+    }
+
+    /// Spawn a `Future` that's already in a `Box` onto the `whorl` runtime.
+    /// Every other spawn takes a `Future` of any type and boxes it, since a
+    /// `Task` has to be able to hold any `Future` at all, which it does by
+    /// erasing its type behind a `dyn Future`. If you've already got one of
+    /// those, say because you keep a bunch of them from plugins, we can take
+    /// it as it is instead of putting a box in a box. You don't get a
+    /// `JoinHandle` back though, so if the runtime won't take it, it simply
+    /// doesn't run.
+    /// 将一个已经在`Box`里的`Future`放入 `whorl` 运行时。其他每一种生成都接收任意类型的`Future`并把它装箱，
+    /// 因为一个`Task`必须能够持有任何`Future`，它的做法是把它的类型擦除在一个`dyn Future`后面。
+    /// 如果你已经有了一个这样的东西，比如因为你保存着一堆来自插件的future，我们就可以按原样接收它，而不是把一个盒子放进另一个盒子里。
+    /// 不过你不会得到一个`JoinHandle`，所以如果运行时不接受它，它就不会运行。
+    pub fn spawn_boxed(future: BoxFuture<'static, ()>) {
+        Spawner::current().spawn_boxed(future)
+    }
+
+    /// Spawn a batch of non-blocking `Future`s onto the `whorl` runtime at
+    /// once. This does the same as spawning each of them in turn, but the
+    /// workers only get woken up once for the whole batch instead of once
+    /// per task.
+    /// 一次把一批非阻塞的 `Future` 放入 `whorl` 运行时。这和依次生成它们中的每一个是一样的，
+    /// 但是工作线程只会为整批任务被唤醒一次，而不是每个任务一次。
+    pub fn spawn_many<T: Send + 'static, F>(
+        futures: impl IntoIterator<Item = F>,
+    ) -> Vec<JoinHandle<T>>
+    where
+        F: Future<Output = T> + Send + Sync + 'static,
+    {
+        Spawner::current().spawn_many(futures)
+    }
+
+    /// One step of a `pipeline`: an async function from a value to the next
+    /// value.
+    /// `pipeline`中的一步：一个从一个值到下一个值的异步函数。
+    pub type Stage<T> = Box<dyn Fn(T) -> BoxFuture<'static, T> + Send + Sync>;
+
+    /// Connects `stages` into a pipeline on the `whorl` runtime. Values sent
+    /// into the `Sender` go through each stage in turn, every stage running
+    /// as its own task, and come out of the `Receiver` at the other end. The
+    /// stages work on different values at the same time, so while the second
+    /// stage handles one value the first can already get going on the next.
+    /// Drop the `Sender` once you're done and the `Receiver` ends once the
+    /// last value has made its way through.
+    /// 在`whorl`运行时上把`stages`连成一条流水线。发送进`Sender`的值会依次经过每个阶段，每个阶段作为它自己的任务运行，
+    /// 然后从另一端的`Receiver`出来。各个阶段同时处理不同的值，所以当第二个阶段处理一个值时，第一个阶段已经可以开始处理下一个了。
+    /// 用完之后丢弃`Sender`，一旦最后一个值走完了全程，`Receiver`就会结束。
+    pub fn pipeline<T: Send + 'static>(
+        stages: Vec<Stage<T>>,
+    ) -> (mpsc::Sender<T>, mpsc::Receiver<T>) {
+        Spawner::current().pipeline(stages)
+    }
+
+    /// How urgently a task wants to be polled again once it's woken up.
+    /// 一个任务被唤醒后，它有多急切地想要被再次poll。
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum Priority {
+        /// Woken tasks go to the back of the queue and wait their turn.
+        /// 被唤醒的任务会去到队列的末尾，等待轮到它们。
+        #[default]
+        Normal,
+        /// Woken tasks wait with the other `High` ones, which get the most
+        /// polls in a row when it's their turn. See
+        /// `Builder::priority_weights`.
+        /// 被唤醒的任务和其他`High`的任务一起等待，轮到它们的时候它们会连续得到最多的poll。请看`Builder::priority_weights`。
+        High,
+        /// Woken tasks wait with the other `Low` ones, which get the fewest
+        /// polls in a row when it's their turn, but still get a turn however
+        /// busy everyone else is.
+        /// 被唤醒的任务和其他`Low`的任务一起等待，轮到它们的时候它们会连续得到最少的poll，但无论其他人有多忙，它们仍然会得到一个回合。
+        Low,
+    }
+
+    /// Where `Shared::pop_next` is in going around the priority classes:
+    /// `class` is `0` for `High`, `1` for `Normal` and `2` for `Low`, and
+    /// `left` is how many more polls it gets before it's the next one's
+    /// turn.
+    /// `Shared::pop_next`在轮流经过各个优先级类别时走到了哪里：`class`对于`High`是`0`，对于`Normal`是`1`，对于`Low`是`2`，
+    /// 而`left`是在轮到下一个之前它还能得到多少次poll。
+    struct Turn {
+        class: usize,
+        left: usize,
+    }
+
+    /// How long `Runtime::assert_no_leaked_tasks` waits for tasks that are on
+    /// their way out.
+    /// `Runtime::assert_no_leaked_tasks`为正在离开的任务等待多久。
+    const LEAK_GRACE: Duration = Duration::from_millis(100);
+
+    /// How long a single `poll` can take in a debug build before we tell the
+    /// `Observer` about it. A poll is supposed to do a little work and hand
+    /// the thread back, and one that takes this long is almost always
+    /// blocking on something it shouldn't, like `std::thread::sleep`.
+    /// 在调试构建中，单次`poll`可以花多长时间，超过了我们就会告诉`Observer`。一次poll应该只做一点工作然后把线程交还回来，
+    /// 一次花了这么久的poll几乎总是在阻塞在某个它不应该阻塞的东西上，比如`std::thread::sleep`。
+    const SLOW_POLL: Duration = Duration::from_millis(50);
+
+    /// Block the calling thread on a `Future` until it completes and hand
+    /// back its output. There's no task and no `JoinHandle` involved, the
+    /// future lives right here on our stack and we take the value straight
+    /// out of `Poll::Ready`, so blocking on something doesn't cost a single
+    /// allocation.
+    /// 在调用线程上阻塞 `Future` 直到它完成，并交还它的输出。这里没有任务也没有`JoinHandle`，
+    /// future就在我们的栈上，我们直接从`Poll::Ready`中取出值，所以阻塞在某个东西上不需要任何一次内存分配。
+    ///
+    /// Since it isn't a task, `wait` never counts it, and by the time it
+    /// returns there's nothing of it left to count anyway. The tasks it
+    /// spawns are another story, see `wait`.
+    /// 因为它不是一个任务，`wait`永远不会计算它，而且等它返回的时候，它也没有留下任何可以计算的东西。它生成的任务就是另一回事了，请看`wait`。
+    pub fn block_on<T>(future: impl Future<Output = T>) -> T {
+        // println!("block on called {} {}", current_thread_id(), current_time());
+        drive(future)
+    }
+
+    /// Block on a `Future` and stop others on the `whorl` runtime until this
+    /// one completes.
+    /// 阻塞 `Future`，并在 `whorl` 运行时停止其他任务，直到此任务完成。
+    pub fn block_on_worker(future: impl Future<Output = ()> + Send + Sync + 'static) {
+        Spawner::current().spawn_blocking(future);
+    }
+
+    /// Polls `future` on the calling thread until it's done, sleeping in
+    /// between until it gets woken. The timers it waits on get fired by the
+    /// timer thread, so even if we're one of the workers we don't have to
+    /// keep an eye on them.
+    /// 在调用线程上poll `future`直到它完成，在两次poll之间睡觉直到它被唤醒。它等待的定时器由定时器线程触发，
+    /// 所以即使我们是工作线程之一，我们也不需要盯着它们。
+    fn drive<T>(future: impl Future<Output = T>) -> T {
+        thread_local! {
+            /// Each thread keeps a `Parker` around for blocking, so that we
+            /// don't need to allocate a new one every time.
+            /// 每个线程都保留一个用于阻塞的`Parker`，这样我们就不需要每次都分配一个新的。
+            static PARKER: Cell<Option<Arc<Parker>>> = const { Cell::new(None) };
+        }
+        let mut future = std::pin::pin!(future);
+        // If we're blocking inside of something that's already blocking on
+        // this thread, it has the thread's `Parker` and we need our own, or
+        // we could end up using up a wake up that was meant for it.
+        // 如果我们是在这个线程上某个已经在阻塞的东西里面阻塞，那么它拿着这个线程的`Parker`，我们需要自己的，
+        // 否则我们可能会用掉一次本来是给它的唤醒。
+        let parker = PARKER.take().unwrap_or_else(|| Arc::new(Parker::new(1)));
+        let waker = Waker::from(parker.clone());
+        let mut ctx = Context::from_waker(&waker);
+        let output = loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut ctx) {
+                break output;
+            }
+            parker.park();
+        };
+        drop(waker);
+        PARKER.set(Some(parker));
+        output
+    }
+
+    /// Run a blocking closure off of the runtime and get a oneshot `Receiver`
+    /// for its result. This is lighter than a `JoinHandle` when all you want is
+    /// the value: there's no task on the runtime for it at all. We don't keep a
+    /// pool of threads around for blocking work, so each call gets a thread of
+    /// its own, which means the closure can block for as long as it likes
+    /// without getting in the way of any tasks.
+    /// 在运行时之外运行一个阻塞的闭包，并得到一个用于接收其结果的oneshot `Receiver`。
+    /// 当你只想要那个值时，这比`JoinHandle`更轻量：运行时上根本没有它的任务。
+    /// 我们没有为阻塞工作保留一个线程池，所以每次调用都会得到一个它自己的线程，这意味着闭包想阻塞多久就阻塞多久，而不会妨碍任何任务。
+    pub fn spawn_blocking_oneshot<T: Send + 'static>(
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> oneshot::Receiver<T> {
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            // If nobody wants the result anymore we just let it go.
+            // 如果已经没有人想要这个结果了，我们就直接丢掉它。
+            let _ = tx.send(f());
+        });
+        rx
+    }
+
+    /// Spawn a task for something that reports back through a callback
+    /// instead of being a `Future`, like a lot of C libraries and older Rust
+    /// ones do. `f` gets called right away, on this thread, with the callback
+    /// to hand to that API, and the task finishes with whatever value the
+    /// callback gets called with, whichever thread that happens on. It's a
+    /// oneshot channel underneath: the callback is the `Sender` and the task
+    /// awaits the `Receiver`. If the callback gets dropped without ever being
+    /// called the task aborts itself, so the handle gives back
+    /// `JoinError::Cancelled` rather than waiting forever.
+    /// 为某个通过回调而不是作为一个`Future`来汇报结果的东西生成一个任务，很多C库和老一些的Rust库都是这样做的。
+    /// `f`会立刻在这个线程上被调用，参数是要交给那个API的回调，而任务会以回调被调用时传入的值完成，无论那发生在哪个线程上。
+    /// 它底下是一个oneshot通道：回调就是`Sender`，任务在await`Receiver`。如果回调从来没有被调用就被丢弃了，
+    /// 任务会中止它自己，所以handle会返回`JoinError::Cancelled`，而不是永远等下去。
+    pub fn spawn_callback<T: Send + 'static>(
+        f: impl FnOnce(Box<dyn FnOnce(T) + Send>) + Send + 'static,
+    ) -> JoinHandle<T> {
+        let (tx, mut rx) = oneshot::channel();
+        f(Box::new(move |value| {
+            let _ = tx.send(value);
+        }));
+        spawn(poll_fn(move |cx| match Pin::new(&mut rx).poll(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(value),
+            Poll::Ready(Err(_)) => {
+                if let Some(task) = polling_task(cx) {
+                    task.abort();
+                }
+                Poll::Pending
+            }
+            Poll::Pending => Poll::Pending,
+        }))
+    }
+
+    /// Spawn a `Future` and get back its result on a oneshot `Receiver`,
+    /// along with a handle that aborts the task when it's dropped. That's the
+    /// other way around from a `JoinHandle`, where dropping it lets the task
+    /// carry on in the background, so you can't forget about a task by
+    /// accident. If the task gets aborted before it's done, the `Sender` goes
+    /// away with it and awaiting the receiver gives back a `RecvError`.
+    /// 生成一个`Future`并在一个oneshot `Receiver`上拿回它的结果，同时还有一个在被丢弃时就中止任务的handle。
+    /// 这和`JoinHandle`正好相反，丢弃`JoinHandle`会让任务在后台继续运行，所以你不会不小心忘掉一个任务。
+    /// 如果任务在完成之前被中止了，`Sender`就会和它一起消失，await这个receiver会返回一个`RecvError`。
+    pub fn spawn_scoped_result<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> (AbortOnDropHandle, oneshot::Receiver<T>) {
+        let (tx, rx) = oneshot::channel();
+        let handle = spawn_guarded(async move {
+            let _ = tx.send(future.await);
+        });
+        (handle, rx)
+    }
+
+    /// The handle from `spawn_scoped_result`. The task lives only as long as
+    /// this does.
+    /// `spawn_scoped_result`返回的handle。任务只会和它活得一样久。
+    pub type AbortOnDropHandle = TaskGuard<()>;
+
+    /// Spawn a `Future` whose task lives only as long as the `TaskGuard` you
+    /// get back. Dropping the guard aborts the task, so a task can't outlive
+    /// the scope that owns it and forgetting about one can't leave it running
+    /// in the background forever.
+    /// 生成一个`Future`，它的任务只会和你拿回的`TaskGuard`活得一样久。丢弃guard会中止任务，
+    /// 所以一个任务不能比拥有它的作用域活得更久，而忘掉一个任务也不会让它永远在后台运行下去。
+    pub fn spawn_guarded<T: Send + 'static>(
+        future: impl Future<Output = T> + Send + Sync + 'static,
+    ) -> TaskGuard<T> {
+        TaskGuard {
+            handle: spawn(future),
+        }
+    }
+
+    /// The guard from `spawn_guarded`. Unlike a `JoinHandle`, which lets the
+    /// task carry on when it's dropped, dropping this aborts the task. It can
+    /// still be awaited for the task's result, the same as a `JoinHandle`.
+    /// `spawn_guarded`返回的guard。和`JoinHandle`在被丢弃时让任务继续运行不同，丢弃它会中止任务。
+    /// 它仍然可以像`JoinHandle`一样被await来得到任务的结果。
+    pub struct TaskGuard<T> {
+        handle: JoinHandle<T>,
+    }
+
+    impl<T> TaskGuard<T> {
+        /// Whether the task is done, one way or the other.
+        /// 任务是否已经结束了，无论是以哪种方式。
+        pub fn is_finished(&self) -> bool {
+            self.handle.is_finished()
+        }
+    }
+
+    impl<T> Future for TaskGuard<T> {
+        type Output = Result<T, JoinError>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.handle).poll(cx)
+        }
+    }
+
+    impl<T> Drop for TaskGuard<T> {
+        fn drop(&mut self) {
+            // Aborting a task that's already done does nothing.
+            // 中止一个已经完成的任务什么都不会做。
+            self.handle.abort();
+        }
+    }
+
+    /// Drive a `Future` to completion right here on the calling thread, but
+    /// only for up to `ms` milliseconds. If it isn't done by then we drop it,
+    /// which cancels it, and return `Elapsed`. That way a future that never
+    /// finishes can't hang your program's main thread forever.
+    /// 在调用线程上就地驱动一个 `Future` 直到完成，但最多只驱动 `ms` 毫秒。
+    /// 如果到那时它还没有完成，我们就丢弃它，也就是取消它，并返回 `Elapsed`。这样一个永远不会完成的future就不能让你程序的主线程永远挂起了。
+    pub fn block_on_timeout<T>(ms: u128, future: impl Future<Output = T>) -> Result<T, Elapsed> {
+        let deadline = deadline_in(ms);
+        let mut future = std::pin::pin!(future);
+        // Waking the future up just means unparking us, since we're the one
+        // polling it.
+        // 唤醒这个future只是意味着unpark我们，因为是我们在poll它。
+        let parker = Arc::new(Parker::new(1));
+        let waker = Waker::from(parker.clone());
+        let mut ctx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut ctx) {
+                return Ok(output);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Elapsed);
+            }
+            // Either we get woken up, in which case we poll again, or we time
+            // out, in which case the next time around the loop we give up. It's
+            // fine if we wake up spuriously too, we just poll once more.
+            // 要么我们被唤醒，这种情况下我们再poll一次；要么我们超时了，这种情况下下一轮循环我们就放弃。
+            // 即使我们被意外唤醒也没关系，我们只是再多poll一次。
+            parker.park_timeout(deadline - now);
+        }
+    }
+
+    /// A `Parker` is how a thread that has nothing to do goes to sleep
+    /// without spinning, and how whoever has something for it wakes it back
+    /// up. Each `unpark` leaves behind a wake up, and each time a thread
+    /// parks it uses up one of them, or sleeps until there is one. That way a
+    /// wake up that happens just before a thread goes to sleep isn't lost, the
+    /// thread just doesn't end up sleeping at all. We keep at most `max` of
+    /// them around, one for each thread that might be parked here, so that a
+    /// burst of wake ups while everyone was busy doesn't leave the threads
+    /// waking up over and over for nothing afterwards.
+    /// `Parker`让一个无事可做的线程不用空转就能去睡觉，也让有事给它做的人能把它唤醒。
+    /// 每次`unpark`都会留下一次唤醒，线程每次park都会用掉其中一次，或者一直睡到有一次为止。
+    /// 这样一次恰好发生在线程入睡之前的唤醒就不会丢失，线程只是根本不会睡下去而已。
+    /// 我们最多保留`max`次唤醒，每个可能在这里park的线程一次，这样在大家都很忙时的一阵唤醒就不会让线程之后白白地一次又一次醒来。
+    ///
+    /// We also keep track of how many threads are actually asleep, and only
+    /// go to the `Condvar` when one of them hasn't got a wake up coming yet.
+    /// Otherwise a burst of spawns would have every single one of them
+    /// notify, and the sleeping threads would all pile onto the lock at once
+    /// for nothing.
+    /// 我们还会记录实际有多少个线程在睡觉，只有当其中某个线程还没有唤醒在路上时才会去找`Condvar`。
+    /// 否则一阵生成会让它们中的每一个都去notify，而睡着的线程会白白地一下子全都挤到锁上。
+    pub(crate) struct Parker {
+        state: Mutex<ParkerState>,
+        condvar: Condvar,
+        max: usize,
+        /// How many times we've gone to the `Condvar`, so we can tell how
+        /// well we're doing at not bothering it.
+        /// 我们找了`Condvar`多少次，这样我们就能知道我们在不打扰它这件事上做得怎么样。
+        notifies: AtomicUsize,
+    }
+
+    struct ParkerState {
+        wake_ups: usize,
+        /// How many threads are waiting on the `Condvar` right now.
+        /// 现在有多少个线程正在等待`Condvar`。
+        sleepers: usize,
+        /// A wake up that's meant for one thread in particular, which nobody
+        /// else gets to use up, by the thread's number. Most parkers never
+        /// need this, so it only grows once someone's nudged.
+        /// 一次专门给某一个线程的唤醒，其他任何人都不能用掉它，按线程的编号排列。大多数parker永远不需要它，所以只有当有人被点名唤醒时它才会增长。
+        nudged: Vec<bool>,
+    }
+
+    impl Parker {
+        /// A `Parker` for up to `max` threads.
+        /// 一个供最多`max`个线程使用的`Parker`。
+        pub(crate) fn new(max: usize) -> Self {
+            Self {
+                state: Mutex::new(ParkerState {
+                    wake_ups: 0,
+                    sleepers: 0,
+                    nudged: Vec::new(),
+                }),
+                condvar: Condvar::new(),
+                max,
+                notifies: AtomicUsize::new(0),
+            }
+        }
+
+        /// Sleeps until there's a wake up to use.
+        /// 睡觉直到有一次唤醒可用。
+        pub(crate) fn park(&self) {
+            let mut state = self.state.lock().unwrap();
+            state.sleepers += 1;
+            while state.wake_ups == 0 {
+                state = self.condvar.wait(state).unwrap();
+            }
+            state.sleepers -= 1;
+            state.wake_ups -= 1;
+        }
+
+        /// Sleeps until there's a wake up to use or `timeout` has passed.
+        /// 睡觉直到有一次唤醒可用或者`timeout`过去了。
+        pub(crate) fn park_timeout(&self, timeout: Duration) {
+            let mut state = self.state.lock().unwrap();
+            state.sleepers += 1;
+            let (mut state, _) = self
+                .condvar
+                .wait_timeout_while(state, timeout, |state| state.wake_ups == 0)
+                .unwrap();
+            state.sleepers -= 1;
+            state.wake_ups = state.wake_ups.saturating_sub(1);
+        }
+
+        /// Parks until `deadline` if there is one, or for as long as it takes
+        /// otherwise.
+        /// 如果有`deadline`就park到那个时候，否则要多久就park多久。
+        pub(crate) fn park_until(&self, deadline: Option<Instant>) {
+            match deadline {
+                Some(deadline) => {
+                    self.park_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => self.park(),
+            }
+        }
+
+        /// The same as `park_until`, but thread number `thread` also wakes up
+        /// for a wake up that's meant just for it.
+        /// 和`park_until`一样，不过编号为`thread`的线程也会为一次专门给它的唤醒而醒来。
+        pub(crate) fn park_as(&self, thread: usize, deadline: Option<Instant>) {
+            let mut state = self.state.lock().unwrap();
+            state.sleepers += 1;
+            loop {
+                if let Some(nudged @ true) = state.nudged.get_mut(thread) {
+                    *nudged = false;
+                    break;
+                }
+                if state.wake_ups > 0 {
+                    state.wake_ups -= 1;
+                    break;
+                }
+                state = match deadline {
+                    Some(deadline) => {
+                        let timeout = deadline.saturating_duration_since(Instant::now());
+                        if timeout.is_zero() {
+                            break;
+                        }
+                        self.condvar.wait_timeout(state, timeout).unwrap().0
+                    }
+                    None => self.condvar.wait(state).unwrap(),
+                };
+            }
+            state.sleepers -= 1;
+        }
+
+        /// Wakes up thread number `thread` and nobody else. We don't know which
+        /// of the sleepers that is, so we have to notify all of them, but only
+        /// that one finds anything to use.
+        /// 唤醒编号为`thread`的线程，其他人都不唤醒。我们不知道睡着的线程中哪一个是它，所以我们必须notify所有线程，
+        /// 但是只有那一个会找到可以用的东西。
+        pub(crate) fn unpark_one(&self, thread: usize) {
+            let mut state = self.state.lock().unwrap();
+            if state.nudged.len() <= thread {
+                state.nudged.resize(thread + 1, false);
+            }
+            state.nudged[thread] = true;
+            drop(state);
+            self.notifies.fetch_add(1, Ordering::Relaxed);
+            self.condvar.notify_all();
+        }
+
+        /// Leaves a wake up behind and wakes a parked thread to use it. Every
+        /// wake up that's already waiting to be used has had a thread notified
+        /// for it, or will be picked up by a thread that hasn't gone to sleep
+        /// yet, so we only notify if there are more sleepers than that.
+        /// 留下一次唤醒，并唤醒一个被park的线程来使用它。每一次已经在等待被使用的唤醒都已经有一个线程为它被notify过了，
+        /// 或者会被一个还没有入睡的线程拿走，所以只有当睡着的线程比这更多时我们才会notify。
+        pub(crate) fn unpark(&self) {
+            let mut state = self.state.lock().unwrap();
+            if state.wake_ups == self.max {
+                return;
+            }
+            let notify = state.sleepers > state.wake_ups;
+            state.wake_ups += 1;
+            drop(state);
+            if notify {
+                self.notifies.fetch_add(1, Ordering::Relaxed);
+                self.condvar.notify_one();
+            }
+        }
+
+        /// Wakes every thread that's parked here.
+        /// 唤醒每一个在这里被park的线程。
+        pub(crate) fn unpark_all(&self) {
+            self.state.lock().unwrap().wake_ups = self.max;
+            self.notifies.fetch_add(1, Ordering::Relaxed);
+            self.condvar.notify_all();
+        }
+    }
+
+    /// Waking a `Waker` made from a `Parker` unparks whoever is waiting on it.
+    /// 唤醒一个由`Parker`创建的`Waker`会unpark正在等待它的人。
+    impl Wake for Parker {
+        fn wake(self: Arc<Self>) {
+            self.unpark();
+        }
+    }
+
+    /// A `Waker` that wakes up thread number `thread` on `work` and nobody
+    /// else, for a thread that parks there with `park_as`.
+    /// 一个只唤醒`work`上编号为`thread`的线程而不唤醒其他任何人的`Waker`，给用`park_as`在那里park的线程用。
+    struct Nudge {
+        work: Arc<Parker>,
+        thread: usize,
+    }
+
+    impl Wake for Nudge {
+        fn wake(self: Arc<Self>) {
+            self.work.unpark_one(self.thread);
+        }
+    }
+
+    /// Block further execution of a program until all of the tasks on the
+    /// `whorl` runtime are completed.
+    /// 阻止程序的进一步执行，直到 `whorl` 运行时上的所有任务完成。
+    ///
+    /// A task is counted from the moment it's made, before the `spawn` that
+    /// made it returns, until its `Future` has been dropped. So once
+    /// `block_on` returns, every task spawned inside of it is counted, and
+    /// so is every task any of those spawn before they finish. A `wait`
+    /// right after it can't slip through early, it returns once every one
+    /// of them is done, and everything they did happens before it returns.
+    /// The only spawns it doesn't wait on are the ones from `spawn_after`
+    /// that aren't due yet, since they don't have a task yet.
+    /// 一个任务从它被创建的那一刻起就被计数了，在创建它的`spawn`返回之前，一直到它的`Future`被丢弃为止。所以一旦`block_on`返回，
+    /// 在它里面生成的每一个任务都被计数了，这些任务在完成之前生成的每一个任务也是如此。紧跟在它后面的`wait`不可能提前溜过去，
+    /// 它会在它们每一个都完成之后才返回，而它们做过的一切都发生在它返回之前。它唯一不会等待的生成是那些来自`spawn_after`、
+    /// 还没有到期的生成，因为它们还没有任务。
+    pub fn wait() {
+        // println!("wait called {} {}", current_thread_id(), current_time());
+        Spawner::current().shared.wait();
+    }
+
+    /// Blocks the calling thread until every task in `handles` is done, one
+    /// way or the other. Unlike `wait` it doesn't care about any other task,
+    /// so a background task that never ends doesn't keep it waiting, and the
+    /// tasks can even be on different runtimes. Their results stay in the
+    /// handles for whoever awaits them next. Like `block_on` this has to be
+    /// called from outside of the runtime's workers, so that they're free to
+    /// run the tasks we're waiting on.
+    /// 阻塞调用线程，直到`handles`中的每一个任务都结束了，无论是以哪种方式。和`wait`不同，它不关心任何其他任务，
+    /// 所以一个永远不会结束的后台任务不会让它一直等下去，而且这些任务甚至可以在不同的运行时上。它们的结果会留在handle里，给下一个await它们的人。
+    /// 和`block_on`一样，这必须在运行时的工作线程之外调用，这样它们才能空出来运行我们正在等待的任务。
+    pub fn wait_for<T>(handles: &[JoinHandle<T>]) {
+        block_on(async {
+            for handle in handles {
+                handle.finished().await;
+            }
+        })
+    }
+
+    /// A `JoinHandle` is what you get back when you spawn a task. Awaiting it
+    /// gives you whatever the task's `Future` returned, or a `JoinError` if
+    /// the task went away before it could finish, say because its runtime got
+    /// dropped. Dropping the handle doesn't do anything to the task, it just
+    /// keeps on running in the background without anyone to hear its result.
+    /// `JoinHandle`是你生成一个任务时得到的东西。await它会给你任务的`Future`返回的东西，
+    /// 或者如果任务在完成之前就消失了，比如因为它的运行时被丢弃了，就会给你一个`JoinError`。
+    /// 丢弃这个handle不会对任务做任何事情，它只是继续在后台运行，只是没有人会听到它的结果。
+    pub struct JoinHandle<T> {
+        state: Arc<Mutex<JoinState<T>>>,
+        /// The task this is a handle to, for `abort`. It's weak since a
+        /// handle shouldn't keep a task around once it's done.
+        /// 这个handle所对应的任务，用于`abort`。它是弱引用，因为一旦任务完成，handle不应该让它继续存在。
+        task: Weak<Task>,
+    }
+
+    /// What's shared between a `JoinHandle` and the task it belongs to.
+    ///
+    /// It's behind a `Mutex` on purpose and nothing in here should ever turn
+    /// into a `Relaxed` atomic. The task unlocks it after storing its result
+    /// and whoever awaits the handle locks it to take the result out. An
+    /// unlock and the lock that comes after it are a `Release` and an
+    /// `Acquire`, so everything the task did before it finished, and not
+    /// just its result, happens before the code after `.await` on the handle.
+    /// A parent that joins its children can rely on seeing all of their side
+    /// effects.
+    /// `JoinHandle`和它所属的任务之间共享的东西。
+    ///
+    /// 它被特意放在一个`Mutex`后面，这里面的任何东西都不应该变成一个`Relaxed`的原子变量。任务在存好它的结果之后解锁它，
+    /// 而await这个handle的人锁住它来取出结果。一次解锁和它之后的那次加锁就是一个`Release`和一个`Acquire`，
+    /// 所以任务在完成之前做的所有事情，而不仅仅是它的结果，都发生在handle上`.await`之后的代码之前。一个join它的子任务的父任务可以放心地看到它们所有的副作用。
+    struct JoinState<T> {
+        /// Whether the task is done, one way or the other.
+        /// 任务是否已经结束了，无论是以哪种方式。
+        finished: bool,
+        /// The task's result, until the `JoinHandle` takes it.
+        /// 任务的结果，直到`JoinHandle`把它拿走。
+        result: Option<Result<T, JoinError>>,
+        /// The task awaiting the `JoinHandle`, if there is one.
+        /// 正在await这个`JoinHandle`的任务，如果有的话。
+        waker: Option<Waker>,
+        /// Every `checkpoint` the task has got to so far.
+        /// 任务到目前为止已经到达过的每一个`checkpoint`。
+        checkpoints: Vec<String>,
+        /// Everyone waiting in `wait_checkpoint`.
+        /// 每一个在`wait_checkpoint`中等待的人。
+        checkpoint_wakers: Vec<Waker>,
+        /// Everyone waiting in `finished`.
+        /// 每一个在`finished`中等待的人。
+        finish_wakers: Vec<Waker>,
+    }
+
+    /// How a task tells its `JoinHandle` about the checkpoints it gets to, or
+    /// why it's being stopped, without the task having to know what type the
+    /// handle's result is.
+    /// 一个任务如何把它到达的检查点、或者它被停下来的原因告诉它的`JoinHandle`，而不需要任务知道handle的结果是什么类型。
+    trait HandleState: Send + Sync {
+        fn reach(&self, name: &str);
+        fn fail(&self, error: JoinError);
+    }
+
+    impl<T: Send> HandleState for Mutex<JoinState<T>> {
+        fn fail(&self, error: JoinError) {
+            JoinState::finish(self, Err(error));
+        }
+
+        fn reach(&self, name: &str) {
+            let wakers = {
+                let mut state = self.lock().unwrap();
+                if !state.checkpoints.iter().any(|reached| reached == name) {
+                    state.checkpoints.push(name.to_owned());
+                }
+                std::mem::take(&mut state.checkpoint_wakers)
+            };
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    /// The half the task holds on to so it can hand its result over to the
+    /// `JoinHandle`. If it gets dropped before that happens, then the task
+    /// never finished and the `JoinHandle` gets told it was cancelled.
+    /// 任务持有的那一半，这样它就可以把结果交给`JoinHandle`。
+    /// 如果它在那之前就被丢弃了，那么任务就永远不会完成，`JoinHandle`会被告知任务被取消了。
+    struct Completer<T> {
+        state: Arc<Mutex<JoinState<T>>>,
+    }
+
+    impl<T> JoinHandle<T> {
+        fn new() -> (JoinHandle<T>, Completer<T>) {
+            let state = Arc::new(Mutex::new(JoinState {
+                finished: false,
+                result: None,
+                waker: None,
+                checkpoints: Vec::new(),
+                checkpoint_wakers: Vec::new(),
+                finish_wakers: Vec::new(),
+            }));
+            let completer = Completer {
+                state: state.clone(),
+            };
+            let handle = JoinHandle {
+                state,
+                task: Weak::new(),
+            };
+            (handle, completer)
+        }
+
+        /// A handle for a task that never got to run.
+        /// 一个永远没有机会运行的任务的handle。
+        fn cancelled() -> JoinHandle<T> {
+            // Dropping the `Completer` without using it is what tells the
+            // `JoinHandle` it was cancelled.
+            // 不使用就丢弃`Completer`，这就是告诉`JoinHandle`它被取消了的方式。
+            JoinHandle::new().0
+        }
+
+        /// Stops the task in its tracks. Whatever it was in the middle of
+        /// doing gets dropped right away, or once it's done with its current
+        /// `poll` if it's being polled right now, and awaiting the handle gives
+        /// back `JoinError::Cancelled`. Unlike a `CancellationToken` the task
+        /// gets no say in this, so anything it didn't clean up is gone.
+        /// 让任务立刻停下来。它正在做的任何事情都会被立刻丢弃，或者如果它正在被poll，就在它完成当前这次`poll`之后，
+        /// 然后await这个handle会返回`JoinError::Cancelled`。和`CancellationToken`不同，任务对此没有发言权，所以它没来得及清理的东西就都没了。
+        pub fn abort(&self) {
+            if let Some(task) = self.task.upgrade() {
+                task.abort();
+            }
+        }
+
+        /// Whether the task is done, one way or the other, so that awaiting
+        /// the handle won't have to wait.
+        /// 任务是否已经结束了，无论是以哪种方式，这样await这个handle就不需要等待了。
+        pub fn is_finished(&self) -> bool {
+            self.state.lock().unwrap().finished
+        }
+
+        /// Waits for the task to be done, one way or the other, without taking
+        /// its result, so the handle can still be awaited for it afterwards.
+        /// Any number of these can wait on the same handle at once.
+        /// 等待任务结束，无论是以哪种方式，但不拿走它的结果，所以之后仍然可以await这个handle来得到结果。任意多个这样的等待可以同时等在同一个handle上。
+        pub async fn finished(&self) {
+            poll_fn(|cx| {
+                let mut state = self.state.lock().unwrap();
+                if state.finished {
+                    return Poll::Ready(());
+                }
+                if !state
+                    .finish_wakers
+                    .iter()
+                    .any(|waker| waker.will_wake(cx.waker()))
+                {
+                    state.finish_wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            })
+            .await
+        }
+
+        /// Waits for the task to get to the `checkpoint` called `name`, and
+        /// hands back `true` once it has, even if that was before we started
+        /// waiting. If the task is done without ever getting there we hand
+        /// back `false` instead of waiting forever. This is handy in tests
+        /// that need to know a task got somewhere before checking on it.
+        /// 等待任务到达名叫`name`的`checkpoint`，一旦它到了就交出`true`，即使那是在我们开始等待之前。
+        /// 如果任务结束了却从来没有到达那里，我们就交出`false`，而不是永远等下去。这在需要知道一个任务已经到了某个地方再去检查它的测试中很方便。
+        pub async fn wait_checkpoint(&self, name: &str) -> bool {
+            poll_fn(|cx| {
+                let mut state = self.state.lock().unwrap();
+                if state.checkpoints.iter().any(|reached| reached == name) {
+                    return Poll::Ready(true);
+                }
+                if state.finished {
+                    return Poll::Ready(false);
+                }
+                if !state
+                    .checkpoint_wakers
+                    .iter()
+                    .any(|waker| waker.will_wake(cx.waker()))
+                {
+                    state.checkpoint_wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            })
+            .await
+        }
+    }
+
+    impl<T> Completer<T> {
+        fn complete(self, value: T) {
+            self.finish(Ok(value));
+        }
+
+        fn finish(&self, result: Result<T, JoinError>) {
+            JoinState::finish(&self.state, result);
+        }
+    }
+
+    impl<T> JoinState<T> {
+        /// Hands `result` over to the `JoinHandle`, unless it already has one,
+        /// since only the first way a task ends counts.
+        /// 把`result`交给`JoinHandle`，除非它已经有一个了，因为只有任务结束的第一种方式才算数。
+        fn finish(state: &Mutex<Self>, result: Result<T, JoinError>) {
+            let (waker, checkpoint_wakers, finish_wakers) = {
+                let mut state = state.lock().unwrap();
+                if state.finished {
+                    return;
+                }
+                state.finished = true;
+                state.result = Some(result);
+                (
+                    state.waker.take(),
+                    std::mem::take(&mut state.checkpoint_wakers),
+                    std::mem::take(&mut state.finish_wakers),
+                )
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+            // Anyone still waiting on a checkpoint isn't going to get it.
+            // 任何还在等待检查点的人都等不到它了。
+            checkpoint_wakers.into_iter().for_each(Waker::wake);
+            finish_wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    impl<T> Drop for Completer<T> {
+        fn drop(&mut self) {
+            self.finish(Err(JoinError::Cancelled));
+        }
+    }
+
+    impl<T> Future for JoinHandle<T> {
+        type Output = Result<T, JoinError>;
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let mut state = self.state.lock().unwrap();
+            match state.result.take() {
+                Some(result) => Poll::Ready(result),
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Why a `JoinHandle` couldn't give back the task's result.
+    /// `JoinHandle`无法返回任务结果的原因。
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum JoinError {
+        /// The task was dropped before it finished.
+        /// 任务在完成之前被丢弃了。
+        Cancelled,
+        /// The task spent longer being polled than `spawn_cpu_bounded`
+        /// allowed, so it got aborted.
+        /// 任务被poll的时间比`spawn_cpu_bounded`所允许的更久，所以它被中止了。
+        CpuLimit,
+    }
+
+    impl fmt::Display for JoinError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                JoinError::Cancelled => write!(f, "task was cancelled"),
+                JoinError::CpuLimit => write!(f, "task used up its CPU time"),
+            }
+        }
+    }
+
+    impl std::error::Error for JoinError {}
+
+    /// The `Task` is the basic unit for the executor. It represents a `Future`
+    /// that may or may not be completed. We spawn `Task`s to be run and poll
+    /// them until completion in a non-blocking manner unless specifically asked
+    /// for.
+    /// `Task` 是执行器的基本单元。它表示一个可能已完成或未完成的 `Future`。
+    /// 我们启动 `Task` 来运行，并以非阻塞方式轮询它们，直到完成，除非明确制定为阻塞任务。
+    struct Task {
+        /// This is the actual `Future` we will poll inside of a `Task`. We `Box`
+        /// and `Pin` the `Future` when we create a task so that we don't need
+        /// to worry about pinning or more complicated things in the runtime. We
+        /// also need to make sure this is `Send + Sync` so we can use it across threads
+        /// and so we lock the `Pin<Box<dyn Future>>` inside a `Mutex`. Once the
+        /// `Future` completes we drop it and leave `None` behind, since a
+        /// `Waker` that's still floating around might put the task back on the
+        /// queue and polling a completed `Future` again isn't allowed.
+        /// 一旦`Future`完成，我们就丢弃它并留下`None`，因为一个还在外面的`Waker`可能会把任务放回队列，
+        /// 而再次poll一个已完成的`Future`是不允许的。
+        /// `debug::future_size` shows why the box is worth it.
+        /// `debug::future_size`展示了为什么这个box是值得的。
+        future: Mutex<Option<BoxFuture<'static, ()>>>,
+        /// We need a way to check if the runtime should block on this task and
+        /// so we use a boolean here to check that!
+        block: bool,
+        /// Whether a worker has already claimed this blocking task and is
+        /// polling it to completion.
+        /// 是否已经有工作线程认领了这个阻塞任务，并正在poll它直到完成。
+        claimed: AtomicBool,
+        /// The `Spawner` of the runtime this task was spawned on, so that it
+        /// goes back onto the right queue when it's woken up.
+        /// 这个任务所在运行时的`Spawner`，这样它被唤醒时就会回到正确的队列中。
         spawner: Spawner,
-        /// A counter for how many Tasks are on the runtime. We use this in
-        /// conjunction with `wait` to block until there are no more tasks on
-        /// the executor.
-        /// 一个计数器，用于记录运行时上有多少任务。
-        /// 我们将其与 `wait` 结合使用，以阻塞直到执行器上没有更多任务。
+        /// A name for the task so that people can tell what it is.
+        /// 任务的名字，这样人们就能知道它是什么。
+        name: Option<String>,
+        /// Where the task goes in the queue when it's woken up.
+        /// 任务被唤醒时在队列中去的位置。
+        priority: Priority,
+        /// Which task this is.
+        /// 这是哪个任务。
+        id: TaskId,
+        /// Where the worker driving a blocking task sleeps until it's woken.
+        /// 驱动阻塞任务的工作线程睡觉直到它被唤醒的地方。
+        parker: Parker,
+        /// Set once the `Future` has run to completion, so that when the task
+        /// gets dropped we know whether it finished or got cut short.
+        /// 一旦`Future`运行完成就会被设置，这样当任务被丢弃时我们就知道它是完成了还是被中途打断了。
+        completed: AtomicBool,
+        /// Set by `abort`, so that a task that's being polled while it gets
+        /// aborted drops its `Future` as soon as that poll is over.
+        /// 由`abort`设置，这样一个在被poll时被中止的任务会在那次poll结束后立刻丢弃它的`Future`。
+        aborted: AtomicBool,
+        /// Whether the task is on the queue right now. A task that gets woken
+        /// up over and over before it's polled again only needs to be on the
+        /// queue once, and polling it once per wake up would just be wasted
+        /// work.
+        /// 任务此刻是否在队列上。一个在再次被poll之前被一次又一次唤醒的任务只需要在队列上出现一次，
+        /// 每次唤醒都poll它一次只会是白费功夫。
+        scheduled: AtomicBool,
+        /// Which worker the task has to be polled on, if any.
+        /// 任务必须在哪个工作线程上被poll，如果有的话。
+        home: Mutex<Home>,
+        /// How many times the task has been polled, for `Runtime::tasks`.
+        /// 任务被poll了多少次，给`Runtime::tasks`用的。
+        polls: AtomicUsize,
+        /// When the task was made, for `Runtime::tasks`.
+        /// 任务是什么时候被创建的，给`Runtime::tasks`用的。
+        spawned_at: Instant,
+        /// How much of a CPU budget the task gets, if it was spawned with
+        /// `spawn_cpu_bounded`, and how much of it it has used up in
+        /// nanoseconds.
+        /// 任务得到了多少CPU预算，如果它是用`spawn_cpu_bounded`生成的话，以及它已经用掉了多少纳秒。
+        cpu_budget: OnceLock<Duration>,
+        cpu_used: AtomicU64,
+        /// Where `checkpoint` and the CPU budget report to, if the task has a
+        /// `JoinHandle`. It's the handle's own shared state, so it doesn't
+        /// cost anything extra to set up.
+        /// `checkpoint`和CPU预算报告到的地方，如果任务有`JoinHandle`的话。它就是handle自己的共享状态，所以设置它不需要额外的花费。
+        handle: OnceLock<Arc<dyn HandleState>>,
+    }
+
+    impl Task {
+        /// Whether the task has never been polled, so there's no work of its
+        /// own to lose by dropping it.
+        /// 任务是否从来没有被poll过，这样丢弃它就不会丢掉它自己的任何工作。
+        fn is_fresh(&self) -> bool {
+            self.polls.load(Ordering::Relaxed) == 0
+        }
+
+        /// This constructs a new task by increasing the count in the runtime of
+        /// how many tasks there are, pinning the `Future`, and wrapping it all
+        /// in an `Arc`.
+        /// 构造新任务，并增加运行时中的任务数量，pinning `Future`，并将其包装在 `Arc` 中。
+        fn new(
+            spawner: Spawner,
+            block: bool,
+            name: Option<String>,
+            priority: Priority,
+            future: impl Future<Output = ()> + Send + Sync + 'static,
+        ) -> Arc<Self> {
+            Self::new_boxed(spawner, block, name, priority, Box::pin(future))
+        }
+
+        /// The same as `new` for a `Future` that's already been boxed, so we
+        /// don't have to box it again.
+        /// 和`new`一样，用于一个已经被装箱了的`Future`，这样我们就不需要再次装箱它了。
+        fn new_boxed(
+            spawner: Spawner,
+            block: bool,
+            name: Option<String>,
+            priority: Priority,
+            future: BoxFuture<'static, ()>,
+        ) -> Arc<Self> {
+            // This can be `Relaxed`. Read-modify-writes like `fetch_add` and
+            // `fetch_sub` on the same atomic always act on its latest value
+            // no matter the ordering, so the count itself can never be off.
+            // And since we count the task before it goes on the queue and
+            // before `spawn` returns, a task spawned from inside another one is
+            // counted before its parent is done, so the counter can't hit
+            // zero in between.
+            // 这里可以用`Relaxed`。对同一个原子变量的`fetch_add`和`fetch_sub`这样的读-改-写操作，无论使用什么ordering，
+            // 总是作用在它的最新值上，所以计数本身永远不会出错。而且因为我们在任务进入队列之前、在`spawn`返回之前就计了数，
+            // 所以一个在另一个任务里面生成的任务会在它的父任务完成之前被计数，计数器不可能在中间变成零。
+            let previous = spawner.shared.tasks.fetch_add(1, Ordering::Relaxed);
+            debug_assert!(previous != usize::MAX, "the task counter overflowed");
+            let task = Arc::new(Task {
+                future: Mutex::new(Some(future)),
+                block,
+                claimed: AtomicBool::new(false),
+                spawner,
+                name,
+                priority,
+                id: TaskId::next(),
+                parker: Parker::new(1),
+                completed: AtomicBool::new(false),
+                aborted: AtomicBool::new(false),
+                // Every task goes straight onto the queue once it's made.
+                // 每个任务一旦被创建就会直接被放到队列上。
+                scheduled: AtomicBool::new(true),
+                home: Mutex::new(Home::Anywhere),
+                polls: AtomicUsize::new(0),
+                spawned_at: Instant::now(),
+                cpu_budget: OnceLock::new(),
+                cpu_used: AtomicU64::new(0),
+                handle: OnceLock::new(),
+            });
+            let registry = &task.spawner.shared.registry;
+            registry
+                .lock()
+                .unwrap()
+                .insert(task.id, Arc::downgrade(&task));
+            if let Some(observer) = &task.spawner.shared.observer {
+                observer.on_spawn(&task.meta());
+            }
+            task
+        }
+
+        /// Drops the task's `Future` without finishing it. If the task is still
+        /// on the queue or gets woken up later that's fine, polling a task
+        /// without a `Future` does nothing.
+        /// 不等它完成就丢弃任务的`Future`。如果任务还在队列中或者之后被唤醒了也没关系，poll一个没有`Future`的任务什么都不会做。
+        fn abort(&self) {
+            self.aborted.store(true, Ordering::SeqCst);
+            // If the task is being polled right now we'd have to wait for that
+            // poll to be over, and if it's the task aborting itself that would
+            // never happen. So we leave it to `poll`, which looks at `aborted`
+            // once it's done.
+            // 如果任务此刻正在被poll，我们就得等那次poll结束，而如果是任务自己中止自己，那就永远等不到了。
+            // 所以我们把它留给`poll`，它完成之后会查看`aborted`。
+            if let Ok(mut future) = self.future.try_lock() {
+                let future = future.take();
+                let _enter = self.enter();
+                drop(future);
+            }
+            // A worker might be asleep on it if it's a blocking task.
+            // 如果这是一个阻塞任务，可能有工作线程正睡在它上面。
+            self.parker.unpark();
+        }
+
+        /// What we tell an `Observer` about this task.
+        /// 我们告诉`Observer`的关于这个任务的信息。
+        fn meta(&self) -> TaskMeta<'_> {
+            TaskMeta {
+                id: self.id,
+                name: self.name.as_deref(),
+                priority: self.priority,
+            }
+        }
+
+        /// We want to use the `Task` itself as a `Waker` which we'll get more
+        /// into below. This is a convenience method to construct a new `Waker`.
+        /// A neat thing to note for `poll` and here as well is that we can
+        /// restrict a method such that it will only work when `self` is a
+        /// certain type. In this case you can only call `waker` if the type is
+        /// a `&Arc<Task>`. If it was just `Task` it would not compile or work.
+        /// 我们希望将 `Task` 本身用作 `Waker`，我们将在下面进一步讨论。
+        /// 这是一个方便的方法来构造一个新的 `Waker`。
+        /// 有趣的是，对于 `poll` 和这里，我们可以限制一个方法，使其仅在 `self` 是某种类型时才有效。
+        /// 在这种情况下，只有当类型是 `&Arc<Task>` 时，才能调用 `waker`。
         ///
-        tasks: AtomicUsize,
+        /// We don't just use `self.clone().into()` here, which would work fine
+        /// thanks to `Wake`, because we want the `Waker` to be built from our
+        /// own `TASK_WAKER` table. See `context` below for why.
+        /// 我们没有直接使用`self.clone().into()`，虽然多亏了`Wake`它也可以正常工作，
+        /// 因为我们想让`Waker`由我们自己的`TASK_WAKER`表构建。原因请看下面的`context`。
+        fn waker(self: &Arc<Self>) -> Waker {
+            let data = Arc::into_raw(self.clone()).cast::<()>();
+            // SAFETY: `data` is a leaked `Arc<Task>` which is exactly what
+            // every function in `TASK_WAKER` expects.
+            // SAFETY：`data`是一个被泄漏的`Arc<Task>`，这正是`TASK_WAKER`中每个函数所期望的。
+            unsafe { Waker::from_raw(RawWaker::new(data, &TASK_WAKER)) }
+        }
+
+        /// This is a convenience method to `poll` a `Future` by creating the
+        /// `Waker` and `Context` and then getting access to the actual `Future`
+        /// inside the `Mutex` and calling `poll` on that. While we're polling
+        /// we also mark the task's runtime as the current one on this thread.
+        /// 这是一个方便的方法来 `poll` `Future`，通过创建 `Waker` 和 `Context`，
+        /// 然后获取 `Mutex` 内部的实际 `Future` 的访问权限，并对其调用 `poll`。
+        /// 在poll期间，我们还会把任务所在的运行时标记为当前线程的当前运行时。
+        fn poll(self: &Arc<Self>) -> Poll<()> {
+            let waker = self.waker();
+            let mut ctx = Context::from_waker(&waker);
+            let _enter = self.enter();
+            {
+                let mut home = self.home.lock().unwrap();
+                if *home == Home::Unsettled {
+                    if let Some(worker) = self.spawner.shared.worker() {
+                        *home = Home::Worker(worker);
+                    }
+                }
+            }
+            let mut future = self.future.lock().unwrap();
+            let poll = match future.as_mut() {
+                Some(fut) => {
+                    self.polls.fetch_add(1, Ordering::Relaxed);
+                    let budget = self.cpu_budget.get();
+                    let start = (cfg!(debug_assertions) || budget.is_some()).then(Instant::now);
+                    let poll = fut.as_mut().poll(&mut ctx);
+                    if let Some(took) = start.map(|start| start.elapsed()) {
+                        if cfg!(debug_assertions) && took >= SLOW_POLL {
+                            if let Some(observer) = &self.spawner.shared.observer {
+                                observer.on_slow_poll(&self.meta(), took);
+                            }
+                        }
+                        if let Some(budget) = budget {
+                            let took_nanos = took.as_nanos() as u64;
+                            let used = self.cpu_used.fetch_add(took_nanos, Ordering::Relaxed);
+                            let used = Duration::from_nanos(used + took_nanos);
+                            // A task that's done gets to keep its result, even
+                            // if that last poll took it over.
+                            // 一个已经完成了的任务可以保留它的结果，即使是最后那次poll让它超出了。
+                            if poll.is_pending() && used > *budget {
+                                if let Some(handle) = self.handle.get() {
+                                    handle.fail(JoinError::CpuLimit);
+                                }
+                                self.aborted.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    }
+                    if poll.is_ready() {
+                        self.completed.store(true, Ordering::Release);
+                        *future = None;
+                    }
+                    poll
+                }
+                None => Poll::Ready(()),
+            };
+            drop(future);
+            // We got aborted while we were being polled, so it's on us to
+            // drop the `Future`.
+            // 我们在被poll的时候被中止了，所以得由我们来丢弃`Future`。
+            if poll.is_pending() && self.aborted.load(Ordering::SeqCst) {
+                let future = self.future.lock().unwrap().take();
+                drop(future);
+                return Poll::Ready(());
+            }
+            poll
+        }
+
+        /// Marks the task's runtime as the current one on this thread until the
+        /// returned guard gets dropped. Besides polling, we do this whenever
+        /// we drop a task's `Future` so that anything its `Drop` spawns ends up
+        /// on the right runtime.
+        /// 把任务所在的运行时标记为当前线程的当前运行时，直到返回的guard被丢弃。除了poll，
+        /// 每当我们丢弃任务的`Future`时也会这样做，这样它的`Drop`生成的任何东西都会去到正确的运行时。
+        fn enter(&self) -> Restore {
+            Restore(CURRENT.with(|current| current.replace(Some(self.spawner.clone()))))
+        }
+
+        /// Checks the `block` field to see if the `Task` is blocking.
+        /// 检查 `block` 字段，以查看 `Task` 是否阻塞。
+        fn will_block(&self) -> bool {
+            self.block
+        }
+    }
+
+    /// Where a task gets polled.
+    /// 一个任务在哪里被poll。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Home {
+        /// On whichever worker gets to it first.
+        /// 在最先轮到它的那个工作线程上。
+        Anywhere,
+        /// On whichever worker polls it first and then always that one.
+        /// 在第一个poll它的工作线程上，之后就一直是那一个。
+        Unsettled,
+        /// Always on this worker.
+        /// 一直在这个工作线程上。
+        Worker(usize),
+    }
+
+    /// Since we increase the count everytime we create a new task we also need
+    /// to make sure that it *also* decreases the count every time it goes out
+    /// of scope. This implementation of `Drop` does just that so that we don't
+    /// need to bookeep about when and where to subtract from the count.
+    /// 由于我们每次创建新任务时都会增加计数，因此我们还需要确保它在每次移出范围时都会减少计数。
+    /// 实现 `Drop` 可以实现上面功能，因此我们不需要在何时何地减去计数时进行对账。
+    impl Drop for Task {
+        fn drop(&mut self) {
+            // The `Future` has to go before we count the task as gone. If its
+            // `Drop` spawns something, that gets counted first, so `wait` can't
+            // see zero in between and return before it's run.
+            // `Future`必须在我们把任务算作消失之前被丢弃。如果它的`Drop`生成了什么东西，那个东西会先被计数，
+            // 所以`wait`不可能在中间看到零，然后在它运行之前就返回。
+            let future = self
+                .future
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take();
+            if future.is_some() {
+                let _enter = self.enter();
+                drop(future);
+            }
+            let shared = &self.spawner.shared;
+            shared.registry.lock().unwrap().remove(&self.id);
+            // A task only ever gets dropped once, so this is the one place
+            // where it gets counted as completed or cancelled, however it
+            // ended up here.
+            // 一个任务只会被丢弃一次，所以这里是唯一一个把它算作完成或者取消的地方，无论它是怎么走到这里的。
+            if self.completed.load(Ordering::Acquire) {
+                shared.completed.fetch_add(1, Ordering::Relaxed);
+            } else {
+                shared.cancelled.fetch_add(1, Ordering::Relaxed);
+            }
+            // The count is right either way, but `wait` needs more than that:
+            // once it returns, everything the tasks did has to be visible to
+            // whoever called it. `Release` makes sure that everything this
+            // task did happens before the decrement, and the `Acquire` load
+            // in `wait` that sees the decrement then sees all of it too.
+            // 无论如何计数都是对的，但`wait`需要的不止于此：一旦它返回，任务所做的一切都必须对调用它的人可见。
+            // `Release`确保这个任务所做的一切都发生在这次减少之前，而`wait`中看到这次减少的`Acquire`加载也就能看到所有这些。
+            shared.release_task();
+        }
+    }
+
+    impl fmt::Debug for Task {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Task")
+                .field("id", &self.id)
+                .field("name", &self.name)
+                .field("block", &self.block)
+                .field("priority", &self.priority)
+                .finish_non_exhaustive()
+        }
+    }
+
+    /// Puts back whichever runtime was current before a task was polled, even
+    /// if polling it panicked.
+    /// 恢复任务被poll之前的当前运行时，即使poll发生了panic。
+    struct Restore(Option<Spawner>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    /// `Wake` is the crux of all of this executor as it's what lets us
+    /// reschedule a task when it's ready to be polled. For our implementation
+    /// we do a simple check to see if the task blocks or not and then spawn it back
+    /// onto the executor in an appropriate manner. A blocking task that a worker
+    /// is already driving doesn't need to go anywhere, we just wake up the
+    /// worker that's sleeping on it. A `High` priority task gets to skip to the front, as
+    /// long as there's still budget for it.
+    /// `Wake` 是这个执行器的关键，因为它使我们能够在任务准备好被poll时重新安排任务。
+    /// 对于我们的实现，我们进行了一个简单的检查，以查看任务是否阻塞，然后以适当的方式将其重新放回执行器。
+    /// 一个已经有工作线程在驱动的阻塞任务不需要去任何地方，我们只需要唤醒睡在它上面的那个工作线程。
+    /// 一个`High`优先级的任务可以跳到队列前端，只要还有预算给它。
+    impl Wake for Task {
+        fn wake(self: Arc<Self>) {
+            if self.will_block() && self.claimed.load(Ordering::Acquire) {
+                self.parker.unpark();
+                return;
+            }
+            // It's already waiting to be polled, so there's nothing to do.
+            // 它已经在等着被poll了，所以什么都不用做。
+            if self.scheduled.swap(true, Ordering::AcqRel) {
+                return;
+            }
+            // A task with a home goes back to its own worker's queue, and it
+            // has to be that worker that wakes up for it. Any other one would
+            // just find nothing to do.
+            // 一个有家的任务会回到它自己的工作线程的队列中，而且必须是那个工作线程为它醒来。其他任何一个都只会发现无事可做。
+            let home = *self.home.lock().unwrap();
+            if let Home::Worker(worker) = home {
+                let shared = self.spawner.shared.clone();
+                shared.locals[worker].lock().unwrap().push_back(self);
+                shared.work.unpark_one(worker);
+                return;
+            }
+            if self.will_block() {
+                self.spawner.clone().inner_spawn_blocking(self);
+            } else if self.priority != Priority::Normal {
+                self.spawner.shared.clone().push_class(self);
+            } else if self.spawner.shared.wake_strategy == WakeStrategy::Batched {
+                self.spawner.shared.clone().defer_wake(self);
+            } else {
+                self.spawner.clone().inner_spawn(self);
+            }
+        }
+    }
+
+    /// The functions behind every `Waker` made from a `Task`. The data pointer
+    /// is always an `Arc<Task>` that was turned into a raw pointer, so all
+    /// they do is turn it back and hand it off to our `Wake` impl, just like
+    /// the `From<Arc<W>>` impl in the standard library does. It has to be a
+    /// `static` rather than a `const` so that there's exactly one of it, which
+    /// is what lets `context` recognize it.
+    /// 每个由`Task`创建的`Waker`背后的函数。数据指针永远是一个被转换为裸指针的`Arc<Task>`，
+    /// 所以它们所做的只是把它转换回来，然后交给我们的`Wake`实现，就像标准库中的`From<Arc<W>>`实现所做的那样。
+    /// 它必须是一个`static`而不是`const`，这样它就只有一份，这也是`context`能够认出它的原因。
+    static TASK_WAKER: RawWakerVTable =
+        RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+    unsafe fn clone_waker(data: *const ()) -> RawWaker {
+        Arc::increment_strong_count(data.cast::<Task>());
+        RawWaker::new(data, &TASK_WAKER)
+    }
+
+    unsafe fn wake(data: *const ()) {
+        Wake::wake(Arc::from_raw(data.cast::<Task>()));
+    }
+
+    unsafe fn wake_by_ref(data: *const ()) {
+        let task = std::mem::ManuallyDrop::new(Arc::from_raw(data.cast::<Task>()));
+        Wake::wake_by_ref(&*task);
+    }
+
+    unsafe fn drop_waker(data: *const ()) {
+        drop(Arc::from_raw(data.cast::<Task>()));
+    }
+
+    /// So what is `Context` for, if all we ever do with it is get the `Waker`
+    /// out? It's there so that an executor can hand extra information to the
+    /// futures it polls, and since the `Waker` is the only thing in it right
+    /// now, the `Waker` is where that information has to live. Ours points at
+    /// the `Task` being polled, so if a future's `Waker` is one of ours we can
+    /// follow that pointer and find out which task it belongs to. If it isn't,
+    /// say because the future is being polled by `block_on_timeout` or by some
+    /// other executor entirely, we don't know anything and say so.
+    /// 那么如果我们对`Context`所做的只是把`Waker`拿出来，它到底是干什么用的？它的存在是为了让执行器可以把额外的信息交给它所poll的future，
+    /// 而因为目前`Waker`是它里面唯一的东西，所以这些信息只能放在`Waker`里。我们的`Waker`指向正在被poll的`Task`，
+    /// 所以如果一个future的`Waker`是我们的，我们就可以顺着这个指针找到它属于哪个任务。如果不是，
+    /// 比如因为这个future是被`block_on_timeout`或者完全是别的执行器poll的，那我们就什么都不知道，也会如实地说出来。
+    pub fn context<'a>(cx: &'a Context<'_>) -> Option<TaskMeta<'a>> {
+        polling_task(cx).map(Task::meta)
+    }
+
+    /// Marks that the task we're running in has got to the point called
+    /// `name`, which wakes up anyone waiting on it with
+    /// `JoinHandle::wait_checkpoint`. Outside of a task, or in one that
+    /// nobody holds a `JoinHandle` for, it does nothing.
+    /// 标记我们正在运行的任务已经到达了名叫`name`的地方，这会唤醒任何用`JoinHandle::wait_checkpoint`在等待它的人。
+    /// 在任务之外，或者在一个没有人持有它的`JoinHandle`的任务里面，它什么都不做。
+    pub async fn checkpoint(name: &str) {
+        poll_fn(|cx| {
+            if let Some(handle) = polling_task(cx).and_then(|task| task.handle.get()) {
+                handle.reach(name);
+            }
+            Poll::Ready(())
+        })
+        .await
+    }
+
+    /// The task polling us, going by the `Waker` in `cx`, if it's one of ours.
+    /// 根据`cx`中的`Waker`判断正在poll我们的任务，如果它是我们的任务之一的话。
+    fn polling_task<'a>(cx: &'a Context<'_>) -> Option<&'a Task> {
+        let waker = cx.waker();
+        if !std::ptr::eq(waker.vtable(), &TASK_WAKER) {
+            return None;
+        }
+        // SAFETY: Only our `Task` wakers use `TASK_WAKER` and their data is an
+        // `Arc<Task>` that stays alive for at least as long as the `Waker`,
+        // which we're borrowing for `'a`.
+        // SAFETY：只有我们的`Task` waker使用`TASK_WAKER`，它们的数据是一个`Arc<Task>`，
+        // 它至少和`Waker`活得一样久，而我们借用了`Waker` `'a`这么久。
+        Some(unsafe { &*waker.data().cast::<Task>() })
+    }
+
+    pub mod debug {
+        //! Tools for looking inside of the runtime while you learn how it
+        //! works. None of them are needed to run anything.
+        //! 在你学习运行时如何工作的时候用来看清它内部的工具。运行任何东西都不需要它们。
+        use std::{
+            alloc::{self, Layout, LayoutError},
+            future::Future,
+            pin::Pin,
+            ptr::{self, NonNull},
+            sync::{Arc, Mutex},
+            task::{Context, Poll},
+        };
+
+        /// How aligned the start of an `Arena` is. We bump within that, so
+        /// nothing that needs more than this can go in.
+        /// 一个`Arena`的开头对齐到多少。我们在它之内往前推，所以任何需要比这更多对齐的东西都放不进去。
+        const ARENA_ALIGN: usize = 64;
+
+        /// A bump allocator for the futures of tasks spawned with
+        /// `spawn_in`. It's one block of memory and a count of how much of it
+        /// is used. Putting something in it just rounds the count up to the
+        /// right alignment and adds the size on, which is about as cheap as
+        /// an allocation gets, and nothing is ever freed on its own: the
+        /// count only goes back to zero on `reset`, once everything in the
+        /// arena has been dropped. It's a way to see where a task's memory
+        /// comes from and how much of it there is, since every future in it
+        /// shows up in `used`.
+        /// 一个给用`spawn_in`生成的任务的future用的bump分配器。它是一整块内存，加上一个它用了多少的计数。
+        /// 往里面放东西只不过是把计数向上取整到正确的对齐然后加上大小，这差不多是一次分配能有的最便宜的样子了，而且没有什么东西会自己被释放：
+        /// 只有在arena中的所有东西都被丢弃了之后，计数才会在`reset`时回到零。这是一种看清一个任务的内存从哪里来、有多少的方式，
+        /// 因为它里面的每一个future都会体现在`used`里。
+        #[derive(Clone)]
+        pub struct Arena {
+            chunk: Arc<Chunk>,
+        }
+
+        struct Chunk {
+            memory: NonNull<u8>,
+            layout: Layout,
+            /// How many bytes were asked for, which `layout` can be more than.
+            /// 请求了多少字节，`layout`可能会比这更多。
+            capacity: usize,
+            bump: Mutex<Bump>,
+        }
+
+        struct Bump {
+            used: usize,
+            live: usize,
+            allocations: usize,
+        }
+
+        // The memory is only ever reached through the `ArenaBox`es, which
+        // each get a piece of it that nobody else has.
+        // 这块内存只会通过`ArenaBox`来访问，而每一个`ArenaBox`拿到的都是别人没有的一块。
+        unsafe impl Send for Chunk {}
+        unsafe impl Sync for Chunk {}
+
+        impl Drop for Chunk {
+            fn drop(&mut self) {
+                unsafe { alloc::dealloc(self.memory.as_ptr(), self.layout) };
+            }
+        }
+
+        impl Arena {
+            /// An arena with room for `capacity` bytes of futures. This panics
+            /// if `capacity` is too big to ever be allocated, the same way
+            /// `Vec::with_capacity` does, use `try_new` to get an error instead.
+            /// 一个有`capacity`字节空间来放future的arena。如果`capacity`大到根本不可能被分配，它就会panic，
+            /// 和`Vec::with_capacity`一样，用`try_new`可以得到一个错误而不是panic。
+            pub fn new(capacity: usize) -> Self {
+                match Self::try_new(capacity) {
+                    Ok(arena) => arena,
+                    Err(_) => panic!("an arena of {capacity} bytes can't be allocated"),
+                }
+            }
+
+            /// The same as `new`, but `capacity` being too big gives back a
+            /// `LayoutError` rather than panicking.
+            /// 和`new`一样，但是`capacity`太大时会交还一个`LayoutError`而不是panic。
+            pub fn try_new(capacity: usize) -> Result<Self, LayoutError> {
+                // Allocating zero bytes isn't allowed, so an empty arena
+                // still gets one that nothing will ever fit in.
+                // 分配零字节是不允许的，所以一个空的arena还是会得到一个字节，只是什么都放不进去。
+                let layout = Layout::from_size_align(capacity.max(1), ARENA_ALIGN)?;
+                let memory = unsafe { alloc::alloc(layout) };
+                let Some(memory) = NonNull::new(memory) else {
+                    alloc::handle_alloc_error(layout);
+                };
+                Ok(Arena {
+                    chunk: Arc::new(Chunk {
+                        memory,
+                        layout,
+                        capacity,
+                        bump: Mutex::new(Bump {
+                            used: 0,
+                            live: 0,
+                            allocations: 0,
+                        }),
+                    }),
+                })
+            }
+
+            /// How many bytes it can hold all together.
+            /// 它一共能装多少字节。
+            pub fn capacity(&self) -> usize {
+                self.chunk.capacity
+            }
+
+            /// How many bytes have been handed out since the last `reset`,
+            /// padding included.
+            /// 从上一次`reset`以来交出去了多少字节，包括填充。
+            pub fn used(&self) -> usize {
+                self.chunk.bump.lock().unwrap().used
+            }
+
+            /// How many of the futures put in it are still around.
+            /// 放进去的future中还有多少仍然存在。
+            pub fn live(&self) -> usize {
+                self.chunk.bump.lock().unwrap().live
+            }
+
+            /// How many futures have ever been put in it.
+            /// 一共有多少个future被放进去过。
+            pub fn allocations(&self) -> usize {
+                self.chunk.bump.lock().unwrap().allocations
+            }
+
+            /// Makes all of the arena's memory free to use again, if nothing
+            /// in it is still alive. If something is we hand back `false` and
+            /// leave it be, since handing its memory out again would let
+            /// something else write over it.
+            /// 如果arena中已经没有还活着的东西了，就让它所有的内存都可以重新使用。如果还有的话，我们就交出`false`并且不去动它，
+            /// 因为再次把它的内存交出去会让别的东西覆盖它。
+            pub fn reset(&self) -> bool {
+                let mut bump = self.chunk.bump.lock().unwrap();
+                if bump.live > 0 {
+                    return false;
+                }
+                bump.used = 0;
+                true
+            }
+
+            /// Moves `value` into the arena, or hands it back if there isn't
+            /// room for it.
+            /// 把`value`移进arena，如果没有地方放它就把它交还回来。
+            pub(crate) fn alloc<F>(&self, value: F) -> Result<ArenaBox<F>, F> {
+                let layout = Layout::new::<F>();
+                if layout.align() > ARENA_ALIGN {
+                    return Err(value);
+                }
+                let mut bump = self.chunk.bump.lock().unwrap();
+                let start = bump.used.next_multiple_of(layout.align());
+                match start.checked_add(layout.size()) {
+                    Some(end) if end <= self.capacity() => {
+                        bump.used = end;
+                        bump.live += 1;
+                        bump.allocations += 1;
+                    }
+                    _ => return Err(value),
+                }
+                drop(bump);
+                let value_ptr = unsafe { self.chunk.memory.as_ptr().add(start).cast::<F>() };
+                unsafe { value_ptr.write(value) };
+                Ok(ArenaBox {
+                    value: unsafe { NonNull::new_unchecked(value_ptr) },
+                    chunk: self.chunk.clone(),
+                })
+            }
+        }
+
+        /// Owns a value that lives in an `Arena`, the way a `Box` owns one on
+        /// the heap. The value never moves, so it's fine to pin it, and it's
+        /// dropped right where it is when we are. The arena's memory stays
+        /// around for as long as any of these do.
+        /// 拥有一个存放在`Arena`中的值，就像`Box`拥有一个在堆上的值那样。这个值永远不会移动，所以pin住它没有问题，
+        /// 而当我们被丢弃时，它就在原地被丢弃。只要还有任何一个这样的东西存在，arena的内存就会一直留着。
+        pub(crate) struct ArenaBox<F> {
+            value: NonNull<F>,
+            chunk: Arc<Chunk>,
+        }
+
+        unsafe impl<F: Send> Send for ArenaBox<F> {}
+        unsafe impl<F: Sync> Sync for ArenaBox<F> {}
+
+        impl<F: Future> Future for ArenaBox<F> {
+            type Output = F::Output;
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+                unsafe { Pin::new_unchecked(&mut *self.value.as_ptr()) }.poll(cx)
+            }
+        }
+
+        impl<F> Drop for ArenaBox<F> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(self.value.as_ptr()) };
+                self.chunk.bump.lock().unwrap().live -= 1;
+            }
+        }
+
+        /// How many bytes `future` takes up. An `async` block gets compiled
+        /// into a state machine, an enum with one variant for each `.await`
+        /// it can be stopped at, and each variant has to hold everything
+        /// that's still alive at that point: the locals, and the future being
+        /// awaited, which is a state machine of its own. So futures that
+        /// await futures that await futures keep getting bigger, and every
+        /// one of them is a different type. That's why a `Task` keeps its
+        /// future in a `Box`. The box is the same small pointer whatever is
+        /// inside of it, so every task can be the same type, and moving a
+        /// task around never copies a big state machine.
+        /// `future`占用了多少字节。一个`async`块会被编译成一个状态机，一个它可以停在的每个`.await`都对应一个变体的枚举，
+        /// 而每个变体都要保存那个时刻还活着的所有东西：局部变量，以及正在被await的future，而它本身又是一个状态机。
+        /// 所以await那些await别的future的future会越变越大，而且它们每一个都是不同的类型。这就是为什么`Task`把它的future放在一个`Box`里。
+        /// 无论里面是什么，这个box都是同一个小小的指针，所以每个任务都可以是同一个类型，而到处移动一个任务也永远不会复制一个大的状态机。
+        pub fn future_size<F: std::future::Future>(future: &F) -> usize {
+            std::mem::size_of_val(future)
+        }
+
+        #[test]
+        /// A future with nested `async` blocks holding locals across their
+        /// awaits should be bigger than a flat one, which should be bigger
+        /// than one that does nothing, while boxing any of them gives the
+        /// same size.
+        /// 一个有着嵌套的`async`块、并且在await时还拿着局部变量的future应该比一个扁平的更大，而扁平的应该比一个什么都不做的更大，
+        /// 而把它们中的任何一个装箱都会得到同样的大小。
+        fn nested_futures_are_bigger() {
+            use crate::futures::{boxed, BoxFuture, Sleep};
+
+            let trivial = async { 0 };
+            let flat = async {
+                Sleep::new(1).await;
+                0
+            };
+            let nested = async {
+                let outer = [1u8; 64];
+                let inner = async {
+                    let inner = [2u8; 64];
+                    async { Sleep::new(1).await }.await;
+                    inner.len()
+                }
+                .await;
+                outer.len() + inner
+            };
+            assert!(future_size(&trivial) < future_size(&flat));
+            assert!(future_size(&flat) < future_size(&nested));
+            assert!(future_size(&nested) >= 128);
+
+            let boxed: BoxFuture<'static, usize> = boxed(nested);
+            assert_eq!(
+                future_size(&boxed),
+                std::mem::size_of::<BoxFuture<'static, ()>>()
+            );
+            assert_eq!(crate::runtime::block_on(boxed), 128);
+        }
+
+        #[test]
+        /// Futures spawned in an arena should take up its memory while they
+        /// run and let it be reset once they're done, while one that
+        /// doesn't fit should still run without counting as an allocation.
+        /// 生成在一个arena中的future应该在运行时占用它的内存，并在完成之后让它可以被重置，而一个放不下的future应该仍然会运行，而且不被算作一次分配。
+        fn spawned_futures_live_in_the_arena() {
+            use crate::{futures::sync::CountDownLatch, runtime::Builder};
+
+            let runtime = Builder::new().build();
+            let arena = Arena::new(4096);
+            let latch = CountDownLatch::new(1);
+            let handles: Vec<_> = (0..3)
+                .map(|i| {
+                    let latch = latch.clone();
+                    runtime.spawn_in(&arena, async move {
+                        let buffer = [i as u8; 64];
+                        latch.wait().await;
+                        buffer.len() + i
+                    })
+                })
+                .collect();
+            assert_eq!(arena.allocations(), 3);
+            assert_eq!(arena.live(), 3);
+            assert!(arena.used() >= 3 * 64);
+            assert!(!arena.reset());
+
+            latch.count_down();
+            for (i, handle) in handles.into_iter().enumerate() {
+                assert_eq!(runtime.block_on(handle), Ok(64 + i));
+            }
+            runtime.wait();
+            assert_eq!(arena.live(), 0);
+            assert!(arena.reset());
+            assert_eq!(arena.used(), 0);
+
+            let small = Arena::new(16);
+            let handle = runtime.spawn_in(&small, async {
+                let buffer = [1u8; 64];
+                crate::futures::Sleep::new(1).await;
+                buffer.len()
+            });
+            assert_eq!(runtime.block_on(handle), Ok(64));
+            assert_eq!(small.allocations(), 0);
+
+            assert_eq!(Arena::new(0).capacity(), 0);
+            assert!(Arena::try_new(usize::MAX).is_err());
+        }
+    }
+
+    #[test]
+    /// Spawning and completing tasks over and over again should keep reusing the
+    /// same handful of queue nodes rather than allocating a new one every time.
+    /// 反复地生成和完成任务应该一直复用同一小批队列节点，而不是每次都分配一个新的节点。
+    fn task_queue_reuses_nodes() {
+        let mut queue = TaskQueue::new();
+        for _ in 0..1000 {
+            for _ in 0..10 {
+                queue.push_back(Task::new(
+                    Runtime::get().spawner(),
+                    false,
+                    None,
+                    Priority::Normal,
+                    async {},
+                ));
+            }
+            queue.push_front(Task::new(
+                Runtime::get().spawner(),
+                true,
+                None,
+                Priority::Normal,
+                async {},
+            ));
+            assert!(queue.pop_front().unwrap().will_block());
+            while let Some(task) = queue.pop_front() {
+                assert!(!task.will_block());
+            }
+        }
+        assert_eq!(queue.allocated, 11);
+        assert_eq!(queue.spare.len(), 11);
+
+        // A burst bigger than the pool is handed back to the allocator once it
+        // drains instead of being held on to.
+        // 比池更大的突发在排空后会被归还给分配器，而不是一直被持有。
+        for _ in 0..MAX_SPARE_NODES * 2 {
+            queue.push_back(Task::new(
+                Runtime::get().spawner(),
+                false,
+                None,
+                Priority::Normal,
+                async {},
+            ));
+        }
+        while queue.pop_front().is_some() {}
+        assert_eq!(queue.spare.len(), MAX_SPARE_NODES);
+    }
+
+    #[test]
+    /// A shorter sleep registered after a longer one must still fire first.
+    /// 在较长的sleep之后注册的较短sleep仍然必须先触发。
+    fn timers_fire_in_deadline_order() {
+        use crate::futures::Sleep;
+        use std::{sync::mpsc, time::Duration};
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (tx, rx) = mpsc::channel();
+        for ms in [300, 100, 200] {
+            let order = order.clone();
+            let tx = tx.clone();
+            spawn(async move {
+                Sleep::new(ms).await;
+                order.lock().unwrap().push(ms);
+                tx.send(()).unwrap();
+            });
+        }
+        for _ in 0..3 {
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![100, 200, 300]);
+    }
+
+    #[test]
+    /// Stepping the runtime by hand should get every task to completion and
+    /// then report that there's nothing left to step.
+    /// 手动单步驱动运行时应该能让每个任务都完成，然后报告已经没有可以执行的任务了。
+    fn tick_steps_the_runtime() {
+        use crate::futures::Sleep;
+        use std::time::Duration;
+
+        let done = Arc::new(AtomicUsize::new(0));
+        for _ in 0..5 {
+            let done = done.clone();
+            spawn(async move {
+                Sleep::new(10).await;
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        let runtime = Runtime::get();
+        let start = Instant::now();
+        while done.load(Ordering::SeqCst) < 5 {
+            runtime.tick();
+            assert!(start.elapsed() < Duration::from_secs(30));
+        }
+        while runtime.tick() {
+            assert!(start.elapsed() < Duration::from_secs(30));
+        }
+    }
+
+    #[test]
+    /// Each worker of a multi-worker runtime should be able to drive its own
+    /// blocking task, so two one second blocking tasks on two workers take
+    /// about one second and not two.
+    /// 多工作线程运行时的每个工作线程都应该能驱动自己的阻塞任务，
+    /// 所以两个工作线程上的两个一秒的阻塞任务总共大约需要一秒，而不是两秒。
+    fn blocking_tasks_run_on_separate_workers() {
+        use crate::futures::Sleep;
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = Builder::new().worker_threads(2).build();
+        let (tx, rx) = mpsc::channel();
+        let start = Instant::now();
+        for _ in 0..2 {
+            let tx = tx.clone();
+            runtime.block_on_worker(async move {
+                Sleep::new(1000).await;
+                tx.send(()).unwrap();
+            });
+        }
+        for _ in 0..2 {
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(1800));
+    }
+
+    #[test]
+    /// With dedicated blocking threads, a long blocking task on a runtime
+    /// with a single worker shouldn't hold up anything else on it.
+    /// 有了专用的阻塞线程，一个只有一个工作线程的运行时上的一个很长的阻塞任务不应该耽误它上面的其他任何东西。
+    fn dedicated_blocking_threads_leave_the_workers_alone() {
+        use crate::futures::Sleep;
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = Builder::new()
+            .worker_threads(1)
+            .dedicated_blocking_threads(true)
+            .build();
+        let (tx, rx) = mpsc::channel();
+        let start = Instant::now();
+        runtime.block_on_worker({
+            let tx = tx.clone();
+            async move {
+                Sleep::new(1000).await;
+                tx.send("blocking").unwrap();
+            }
+        });
+        runtime.spawn(async move { tx.send("async").unwrap() });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(30)).unwrap(), "async");
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(30)).unwrap(),
+            "blocking"
+        );
+        assert!(start.elapsed() >= Duration::from_millis(1000));
+    }
+
+    #[test]
+    /// A task that panics once should be restarted and finish fine, while
+    /// one that always panics should only be tried `max_retries + 1` times.
+    /// 一个panic了一次的任务应该被重启并且正常完成，而一个总是panic的任务应该只被尝试`max_retries + 1`次。
+    fn spawn_resilient_restarts_panicking_tasks() {
+        let runtime = Builder::new().build();
+        let (output, once, always) = runtime.block_on(async {
+            let once = Arc::new(AtomicUsize::new(0));
+            let output = spawn_resilient(3, {
+                let once = once.clone();
+                move || {
+                    let once = once.clone();
+                    async move {
+                        if once.fetch_add(1, Ordering::SeqCst) == 0 {
+                            panic!("first try");
+                        }
+                        42
+                    }
+                }
+            })
+            .await
+            .unwrap();
+            let always = Arc::new(AtomicUsize::new(0));
+            let gave_up = spawn_resilient(2, {
+                let always = always.clone();
+                move || {
+                    let always = always.clone();
+                    async move {
+                        always.fetch_add(1, Ordering::SeqCst);
+                        panic!("every try");
+                    }
+                }
+            })
+            .await
+            .unwrap();
+            let payload = gave_up.err().unwrap();
+            assert_eq!(payload.downcast_ref::<&str>(), Some(&"every try"));
+            (output, once, always)
+        });
+        assert_eq!(output.ok(), Some(42));
+        assert_eq!(once.load(Ordering::SeqCst), 2);
+        assert_eq!(always.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    /// `spawn_after` should leave the queue and the task count alone until
+    /// the time is up, and then run the task right away.
+    /// `spawn_after`应该在时间到之前不去动队列和任务计数，然后马上运行这个任务。
+    fn spawn_after_defers_making_the_task() {
+        use std::time::Duration;
+
+        let runtime = Builder::new().build();
+        let start = Instant::now();
+        let handle = runtime.spawn_after(500, async move { start.elapsed() });
+        let never = runtime.spawn_after(u128::MAX, async {});
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(runtime.shared.queue.lock().unwrap().list.is_empty());
+        assert_eq!(runtime.shared.tasks.load(Ordering::SeqCst), 0);
+        let ran_at = runtime.block_on(handle).unwrap();
+        assert!(ran_at >= Duration::from_millis(500));
+        assert!(ran_at < Duration::from_secs(30));
+        assert!(!never.is_finished());
+    }
+
+    #[test]
+    /// A task that keeps busy in its polls should be aborted with
+    /// `CpuLimit` once they add up past its budget, and so should one that
+    /// blows through it in a single poll, while one that stays under it
+    /// should finish.
+    /// 一个在poll中一直忙着的任务应该在这些poll加起来超出预算时被以`CpuLimit`中止，一个在单次poll中就冲过了预算的任务也应该如此，
+    /// 而一个保持在预算之内的任务应该能完成。
+    fn spawn_cpu_bounded_aborts_busy_tasks() {
+        use std::time::Duration;
+
+        fn spin(ms: u64) {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(ms) {
+                std::hint::spin_loop();
+            }
+        }
+
+        let runtime = Builder::new().build();
+        let polls = Arc::new(AtomicUsize::new(0));
+        let busy = runtime.spawn_cpu_bounded(Duration::from_millis(100), {
+            let polls = polls.clone();
+            poll_fn(move |cx| {
+                polls.fetch_add(1, Ordering::Relaxed);
+                spin(20);
+                cx.waker().wake_by_ref();
+                Poll::<()>::Pending
+            })
+        });
+        assert_eq!(runtime.block_on(busy), Err(JoinError::CpuLimit));
+        let polls = polls.load(Ordering::Relaxed);
+        assert!((5..=7).contains(&polls), "polled {polls} times");
+
+        let one_shot = runtime.spawn_cpu_bounded(Duration::from_millis(50), async {
+            spin(200);
+            Sleep::new(10).await;
+        });
+        assert_eq!(runtime.block_on(one_shot), Err(JoinError::CpuLimit));
+
+        let sleepy = runtime.spawn_cpu_bounded(Duration::from_millis(50), async {
+            Sleep::new(200).await;
+            7
+        });
+        assert_eq!(runtime.block_on(sleepy), Ok(7));
+    }
+
+    #[test]
+    /// A `wait` right after a `block_on` should always see every task the
+    /// block spawned, and every task those spawned, run to the end.
+    /// 紧跟在`block_on`之后的`wait`应该总是能看到这次阻塞生成的每一个任务，以及这些任务生成的每一个任务，都运行到了最后。
+    fn wait_after_block_on_sees_every_spawned_task() {
+        use crate::futures::Sleep;
+
+        let runtime = Builder::new().worker_threads(2).build();
+        for round in 0..5 {
+            let done = Arc::new(AtomicUsize::new(0));
+            runtime.block_on({
+                let done = done.clone();
+                async move {
+                    for i in 0..10 {
+                        let done = done.clone();
+                        spawn(async move {
+                            Sleep::new(i % 3).await;
+                            let inner = done.clone();
+                            spawn(async move {
+                                Sleep::new(i % 2).await;
+                                inner.fetch_add(1, Ordering::Relaxed);
+                            });
+                            done.fetch_add(1, Ordering::Relaxed);
+                        });
+                    }
+                }
+            });
+            runtime.block_on_worker({
+                let done = done.clone();
+                async move {
+                    Sleep::new(5).await;
+                    done.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+            runtime.wait();
+            assert_eq!(done.load(Ordering::Relaxed), 21, "round {round}");
+        }
+    }
+
+    #[test]
+    /// `wait_for` should return as soon as the tasks it was given are done,
+    /// while a task that never ends keeps going in the background, and the
+    /// results should still be there afterwards.
+    /// `wait_for`应该在交给它的任务完成之后就立刻返回，而一个永远不会结束的任务在后台继续运行，并且结果之后应该仍然在那里。
+    fn wait_for_only_waits_on_its_own_tasks() {
+        let runtime = Builder::new().build();
+        let background = runtime.spawn(async {
+            loop {
+                Sleep::new(5).await;
+            }
+        });
+        let targets = vec![
+            runtime.spawn(async {
+                Sleep::new(20).await;
+                1
+            }),
+            runtime.spawn(async {
+                Sleep::new(40).await;
+                2
+            }),
+        ];
+        wait_for(&targets);
+        assert!(targets.iter().all(JoinHandle::is_finished));
+        assert!(!background.is_finished());
+        let results: Vec<_> = targets
+            .into_iter()
+            .map(|handle| runtime.block_on(handle))
+            .collect();
+        assert_eq!(results, [Ok(1), Ok(2)]);
+        background.abort();
+        runtime.wait();
+    }
+
+    #[test]
+    /// The name a task gets spawned with should be handed to the observer.
+    /// 任务生成时的名字应该被交给观察者。
+    fn observer_sees_task_names() {
+        struct Names(Arc<Mutex<Vec<Option<String>>>>);
+
+        impl Observer for Names {
+            fn on_spawn(&self, task: &TaskMeta<'_>) {
+                self.0.lock().unwrap().push(task.name().map(str::to_owned));
+            }
+        }
+
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let runtime = Builder::new()
+            .worker_threads(0)
+            .observer(Names(names.clone()))
+            .build();
+        runtime.spawn_named("accept loop", async {});
+        runtime.spawn(async {});
+        assert_eq!(
+            *names.lock().unwrap(),
+            [Some("accept loop".to_owned()), None]
+        );
+        let task = runtime.shared.queue.lock().unwrap().pop_front().unwrap();
+        assert!(format!("{:?}", task).contains("accept loop"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    /// A task that calls `std::thread::sleep` should get reported as a slow
+    /// poll, while one that uses `async_sleep` shouldn't.
+    /// 一个调用`std::thread::sleep`的任务应该被报告为慢poll，而一个使用`async_sleep`的则不应该。
+    fn blocking_sleeps_are_reported_as_slow_polls() {
+        struct SlowPolls(Arc<Mutex<Vec<(String, Duration)>>>);
+
+        impl Observer for SlowPolls {
+            fn on_slow_poll(&self, task: &TaskMeta<'_>, took: Duration) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((task.name().unwrap_or_default().to_owned(), took));
+            }
+        }
+
+        let slow = Arc::new(Mutex::new(Vec::new()));
+        let runtime = Builder::new()
+            .worker_threads(2)
+            .observer(SlowPolls(slow.clone()))
+            .build();
+        runtime.spawn_named("blocking", async {
+            thread::sleep(Duration::from_millis(100));
+        });
+        runtime.spawn_named("polite", async_sleep(100));
+        runtime.wait();
+        let slow = slow.lock().unwrap();
+        assert_eq!(slow.len(), 1);
+        assert_eq!(slow[0].0, "blocking");
+        assert!(slow[0].1 >= Duration::from_millis(100));
+    }
+
+    #[test]
+    /// The closure given to `spawn_fn` should be called on a worker and not
+    /// on the thread that spawned it.
+    /// 交给`spawn_fn`的闭包应该在工作线程上被调用，而不是在生成它的线程上。
+    fn spawn_fn_calls_the_closure_on_a_worker() {
+        use crate::current_thread_id;
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = Builder::new().build();
+        let (tx, rx) = mpsc::channel();
+        runtime.spawn_fn(move || {
+            let called_on = current_thread_id();
+            async move { tx.send(called_on).unwrap() }
+        });
+        let called_on = rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        assert_ne!(called_on, current_thread_id());
+    }
+
+    #[test]
+    /// Cancelling a parent token has to wake up everything waiting on any of
+    /// its children, grandchildren included.
+    /// 取消一个父token必须唤醒所有在它的任何子token上等待的东西，包括孙子token。
+    fn cancelling_a_parent_wakes_its_children() {
+        use std::{sync::mpsc, thread, time::Duration};
+
+        let runtime = Builder::new().build();
+        let parent = CancellationToken::new();
+        let (tx, rx) = mpsc::channel();
+        let child = parent.child_token();
+        let tokens = [child.child_token(), child, parent.child_token()];
+        for token in tokens {
+            let tx = tx.clone();
+            runtime.spawn(async move {
+                token.cancelled().await;
+                tx.send(()).unwrap();
+            });
+        }
+        // Let the tasks park themselves before we cancel.
+        // 在我们取消之前让任务先挂起。
+        thread::sleep(Duration::from_millis(50));
+        assert!(rx.try_recv().is_err());
+        parent.cancel();
+        for _ in 0..3 {
+            rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        }
+        assert!(parent.child_token().is_cancelled());
+    }
+
+    #[test]
+    /// A task spawned with a value already in hand should hand that value
+    /// straight to its `JoinHandle`.
+    /// 一个生成时手头已经有值的任务应该把这个值直接交给它的`JoinHandle`。
+    fn spawn_ready_resolves_to_its_value() {
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = Builder::new().build();
+        let (tx, rx) = mpsc::channel();
+        let handle = runtime.spawn_ready(3);
+        runtime.spawn(async move { tx.send(handle.await).unwrap() });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(30)).unwrap(), Ok(3));
+        runtime.wait();
+    }
+
+    #[test]
+    /// A future that's ready straight away should never go on the queue when
+    /// it's spawned with `spawn_eager`, and one that isn't should still run
+    /// to completion.
+    /// 一个马上就就绪的future在用`spawn_eager`生成时永远不应该进入队列，而一个没有就绪的仍然应该运行到完成。
+    fn spawn_eager_skips_the_queue_for_ready_futures() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let handle = runtime.spawn_eager(std::future::ready(5));
+        assert!(handle.is_finished());
+        assert!(runtime.shared.queue.lock().unwrap().list.is_empty());
+        assert_eq!(runtime.metrics().alive, 0);
+        assert_eq!(runtime.block_on(handle), Ok(5));
+
+        let handle = runtime.spawn_eager(async {
+            Sleep::new(10).await;
+            7
+        });
+        assert!(!handle.is_finished());
+        assert_eq!(runtime.block_on_cooperative(handle), Ok(7));
+    }
+
+    #[test]
+    /// A burst of futures that are ready straight away should never put
+    /// anything on the queue, even with no workers to take it off again, and
+    /// they should all still be counted as completed tasks.
+    /// 一大批马上就就绪的future永远不应该往队列上放任何东西，即使没有工作线程来把它们取走，而且它们仍然都应该被算作已完成的任务。
+    fn spawn_eager_bursts_never_grow_the_queue() {
+        let runtime = Builder::new().worker_threads(0).build();
+        for _ in 0..100_000 {
+            runtime.spawn_eager(std::future::ready(()));
+            assert!(runtime.shared.queue.lock().unwrap().list.is_empty());
+        }
+        runtime.wait();
+        assert_eq!(
+            runtime.metrics(),
+            Metrics {
+                alive: 0,
+                completed: 100_000,
+                cancelled: 0,
+            }
+        );
+    }
+
+    #[test]
+    /// A checkpoint reached during the first poll, the one `spawn_eager` does
+    /// on the calling thread, should count the same as one reached later on.
+    /// 在第一次poll期间到达的检查点，也就是`spawn_eager`在调用线程上做的那一次，应该和之后到达的检查点一样算数。
+    fn spawn_eager_sees_checkpoints_in_its_first_poll() {
+        use crate::futures::sync::CountDownLatch;
+
+        let runtime = Builder::new().worker_threads(1).build();
+        let latch = CountDownLatch::new(1);
+        let release = latch.clone();
+        let handle = runtime.spawn_eager(async move {
+            checkpoint("a").await;
+            latch.wait().await;
+        });
+        assert!(runtime.block_on(handle.wait_checkpoint("a")));
+        assert!(!handle.is_finished());
+        release.count_down();
+        assert_eq!(runtime.block_on(handle), Ok(()));
+    }
+
+    #[test]
+    /// The first poll `spawn_eager` does on the calling thread should know
+    /// which task it's in, and in debug builds get reported when it's slow,
+    /// the same as a poll on a worker.
+    /// `spawn_eager`在调用线程上做的第一次poll应该知道它在哪个任务中，而且在调试构建中，它慢的时候应该被报告，和工作线程上的一次poll一样。
+    fn spawn_eager_first_polls_know_their_task() {
+        struct SlowPolls(Arc<AtomicUsize>);
+
+        impl Observer for SlowPolls {
+            fn on_slow_poll(&self, _task: &TaskMeta<'_>, _took: Duration) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let slow = Arc::new(AtomicUsize::new(0));
+        let runtime = Builder::new()
+            .worker_threads(0)
+            .observer(SlowPolls(slow.clone()))
+            .build();
+        let handle = runtime.spawn_eager(poll_fn(|cx| {
+            thread::sleep(SLOW_POLL);
+            Poll::Ready(context(cx).is_some())
+        }));
+        assert!(handle.is_finished());
+        assert_eq!(runtime.block_on(handle), Ok(true));
+        assert_eq!(slow.load(Ordering::SeqCst), cfg!(debug_assertions) as usize);
+    }
+
+    #[test]
+    /// Both wake strategies should see a workload through, but when a latch
+    /// wakes up a hundred tasks at once, batching them should take the
+    /// queue's lock once instead of a hundred times.
+    /// 两种唤醒策略都应该能把一个工作负载跑完，但是当一个门闩一次唤醒一百个任务时，把它们攒成一批应该只拿一次队列的锁，而不是一百次。
+    fn batched_wakes_take_the_queue_lock_less() {
+        use crate::futures::sync::CountDownLatch;
+
+        let enqueues = |strategy| {
+            let runtime = Builder::new()
+                .worker_threads(0)
+                .wake_strategy(strategy)
+                .build();
+            let latch = CountDownLatch::new(1);
+            let handles: Vec<_> = (0..100)
+                .map(|i| {
+                    let latch = latch.clone();
+                    runtime.spawn(async move {
+                        latch.wait().await;
+                        i
+                    })
+                })
+                .collect();
+            while runtime.tick() {}
+            let before = runtime.shared.enqueues.load(Ordering::Relaxed);
+            latch.count_down();
+            while runtime.tick() {}
+            let outputs: Vec<_> = handles
+                .into_iter()
+                .map(|handle| runtime.block_on(handle).unwrap())
+                .collect();
+            assert_eq!(outputs, (0..100).collect::<Vec<_>>());
+            runtime.shared.enqueues.load(Ordering::Relaxed) - before
+        };
+        assert_eq!(enqueues(WakeStrategy::Immediate), 100);
+        assert_eq!(enqueues(WakeStrategy::Batched), 1);
+    }
+
+    #[test]
+    /// On a runtime where everything runs on the calling thread, a future
+    /// that holds an `Rc` and hands one back should be fine to block on,
+    /// while still driving the `Send` tasks it waits on.
+    /// 在一个所有东西都在调用线程上运行的运行时上，阻塞在一个持有`Rc`并交出`Rc`的future上应该没有问题，同时仍然驱动着它所等待的`Send`任务。
+    fn block_on_cooperative_takes_non_send_futures() {
+        use std::rc::Rc;
+
+        let runtime = Builder::new().worker_threads(0).build();
+        let handle = runtime.spawn(async {
+            Sleep::new(10).await;
+            2
+        });
+        let five = runtime.block_on_cooperative(async {
+            let three = Rc::new(3);
+            let two = handle.await.unwrap();
+            Rc::new(*three + two)
+        });
+        let also_five = Rc::clone(&five);
+        assert_eq!(*also_five, 5);
+        assert_eq!(Rc::strong_count(&five), 2);
+    }
+
+    #[test]
+    /// On a runtime with workers, a future woken by a timer should wake the
+    /// thread that's blocked on it, not one of the workers that happen to be
+    /// sleeping in the same place.
+    /// 在一个有工作线程的运行时上，一个被定时器唤醒的future应该唤醒阻塞在它上面的线程，而不是恰好睡在同一个地方的某个工作线程。
+    fn block_on_cooperative_wakes_up_with_workers() {
+        use std::sync::mpsc;
+
+        for workers in [1, 2, 4] {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let runtime = Builder::new().worker_threads(workers).build();
+                runtime.block_on_cooperative(Sleep::new(5));
+                runtime.block_on_cooperative(async {
+                    Sleep::new(5).await;
+                    Sleep::new(5).await;
+                });
+                tx.send(()).unwrap();
+            });
+            assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(()));
+        }
+    }
+
+    #[test]
+    /// A woken `High` priority task should get more polls in a row than the
+    /// normal tasks it shares the runtime with, but not all of them.
+    /// 一个被唤醒的`High`优先级任务应该比和它共享运行时的普通任务连续得到更多的poll，但不是全部。
+    fn high_priority_tasks_get_more_turns() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let log = order.clone();
+        runtime.spawn_with_priority(Priority::High, async move {
+            for _ in 0..8 {
+                log.lock().unwrap().push("high");
+                crate::futures::yield_now().await;
+            }
+        });
+        for _ in 0..2 {
+            let log = order.clone();
+            runtime.spawn(async move { log.lock().unwrap().push("normal") });
+        }
+        while runtime.tick() {}
+        // The first poll of every task is on the `Normal` turn, which the
+        // `High` task was spawned first in. After that it's four polls for
+        // `High` for every two `Normal` ones.
+        // 每一个任务的第一次poll都在`Normal`的回合里，而`High`任务是在这个回合里最先被生成的。之后`High`每得到四次poll，`Normal`得到两次。
+        let mut expected = vec!["high", "normal"];
+        expected.extend(["high"; 4]);
+        expected.push("normal");
+        expected.extend(["high"; 3]);
+        assert_eq!(*order.lock().unwrap(), expected);
+    }
+
+    #[test]
+    /// `Low` priority tasks should keep getting their share of polls even
+    /// when `High` priority ones never stop waking up.
+    /// 即使`High`优先级的任务从来不停止醒来，`Low`优先级的任务也应该一直得到它们的那一份poll。
+    fn low_priority_tasks_make_progress_under_a_flood() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let spin = |priority, polls: Arc<AtomicUsize>| {
+            runtime.spawn_with_priority(priority, async move {
+                loop {
+                    polls.fetch_add(1, Ordering::Relaxed);
+                    crate::futures::yield_now().await;
+                }
+            })
+        };
+        let high = Arc::new(AtomicUsize::new(0));
+        let low = Arc::new(AtomicUsize::new(0));
+        for _ in 0..8 {
+            spin(Priority::High, high.clone());
+        }
+        spin(Priority::Low, low.clone());
+        for _ in 0..1000 {
+            runtime.tick();
+        }
+        let (high, low) = (high.load(Ordering::Relaxed), low.load(Ordering::Relaxed));
+        assert_eq!(high + low, 1000);
+        // Nothing's `Normal`, so it's four `High` polls to every `Low` one.
+        // 没有`Normal`的任务，所以每一次`Low`的poll对应四次`High`的poll。
+        assert!((195..=205).contains(&low), "low got {} polls", low);
+        runtime.abort_matching(|_| true);
+    }
+
+    #[test]
+    /// A future that never finishes should give up with `Elapsed` once its
+    /// time is up, instead of hanging forever.
+    /// 一个永远不会完成的future应该在时间到了的时候以`Elapsed`放弃，而不是永远挂起。
+    fn block_on_timeout_gives_up() {
+        let start = Instant::now();
+        assert_eq!(
+            block_on_timeout(100, std::future::pending::<()>()),
+            Err(Elapsed)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(block_on_timeout(100, async { 5 }), Ok(5));
+        // Time limits too long to hold mean no limit at all.
+        // 长到装不下的时间限制意味着根本没有限制。
+        assert_eq!(block_on_timeout(1 << 64, Sleep::new(10)), Ok(()));
+        assert_eq!(block_on_timeout(u128::MAX, Sleep::new(10)), Ok(()));
+    }
+
+    #[test]
+    /// Lots of threads spawning at the very first use of a runtime should
+    /// only ever build it once and not lose any of their tasks.
+    /// 许多线程在第一次使用运行时时同时生成任务，应该只会构建它一次，而且不会丢失它们的任何任务。
+    fn concurrent_first_spawns_all_run() {
+        use crate::lazy::Lazy;
+        use std::{sync::Barrier, time::Duration};
+
+        static FRESH: Lazy<Runtime> = Lazy::new();
+        static BUILDS: AtomicUsize = AtomicUsize::new(0);
+        fn build() -> Runtime {
+            BUILDS.fetch_add(1, Ordering::SeqCst);
+            setup_runtime()
+        }
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(8));
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let done = done.clone();
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    FRESH.get_or_init(build).spawn(async move {
+                        done.fetch_add(1, Ordering::SeqCst);
+                    });
+                })
+            })
+            .collect();
+        threads.into_iter().for_each(|t| t.join().unwrap());
+        let start = Instant::now();
+        while done.load(Ordering::SeqCst) < 8 {
+            assert!(start.elapsed() < Duration::from_secs(30));
+            thread::yield_now();
+        }
+        assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    /// Awaiting the receiver from `spawn_blocking_oneshot` should give back
+    /// what the closure returned.
+    /// await `spawn_blocking_oneshot`返回的receiver应该得到闭包返回的值。
+    fn spawn_blocking_oneshot_hands_back_the_result() {
+        use std::{sync::mpsc, time::Duration};
+
+        let runtime = Builder::new().build();
+        let (tx, rx) = mpsc::channel();
+        let result = spawn_blocking_oneshot(|| {
+            thread::sleep(Duration::from_millis(20));
+            6 * 7
+        });
+        runtime.spawn(async move { tx.send(result.await).unwrap() });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(30)).unwrap(), Ok(42));
+    }
+
+    #[test]
+    /// A callback that gets called on another thread should finish the task
+    /// with its value, and one that gets dropped should cancel it.
+    /// 一个在另一个线程上被调用的回调应该以它的值完成任务，而一个被丢弃了的回调应该取消任务。
+    fn spawn_callback_finishes_with_the_callback_value() {
+        let runtime = Builder::new().build();
+        let (called, dropped) = runtime.block_on(async {
+            let called = spawn_callback(|callback| {
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(20));
+                    callback(6 * 7);
+                });
+            });
+            let dropped = spawn_callback::<u32>(|callback| {
+                thread::spawn(move || drop(callback));
+            });
+            (called.await, dropped.await)
+        });
+        assert_eq!(called, Ok(42));
+        assert_eq!(dropped, Err(JoinError::Cancelled));
+    }
+
+    #[test]
+    /// Dropping the handle from `spawn_scoped_result` should abort the task,
+    /// and the receiver should say so, while keeping it around should let
+    /// the result through.
+    /// 丢弃`spawn_scoped_result`返回的handle应该中止任务，而receiver应该告诉我们这一点，而留着它则应该让结果通过。
+    fn dropping_a_scoped_handle_aborts_the_task() {
+        let runtime = Builder::new().build();
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = finished.clone();
+        let (results, aborted) = runtime.block_on(async move {
+            let (handle, rx) = spawn_scoped_result(async { 6 * 7 });
+            let results = rx.await;
+            drop(handle);
+            let (handle, rx) = spawn_scoped_result(async move {
+                Sleep::new(10_000).await;
+                flag.store(true, Ordering::SeqCst);
+            });
+            drop(handle);
+            (results, rx.await)
+        });
+        assert_eq!(results, Ok(42));
+        assert_eq!(aborted, Err(oneshot::RecvError));
+        assert!(!finished.load(Ordering::SeqCst));
+        // The receiver hears about the abort as soon as the future is
+        // dropped, which is a little before the task counts as cancelled.
+        // receiver在future被丢弃时就知道了中止，这比任务被算作取消要早一点。
+        runtime.wait();
+        assert_eq!(runtime.metrics().cancelled, 1);
+    }
+
+    #[test]
+    /// Once the guard from `spawn_guarded` goes out of scope its task, which
+    /// would otherwise never end, should be aborted, so `wait` returns
+    /// straight away, while awaiting a guard should hand back the result.
+    /// 一旦`spawn_guarded`返回的guard离开了作用域，它的任务，本来永远不会结束的，就应该被中止，所以`wait`会立刻返回，
+    /// 而await一个guard应该交还结果。
+    fn dropping_a_task_guard_aborts_the_task() {
+        let runtime = Builder::new().build();
+        runtime.block_on(async {
+            let guard = spawn_guarded(async {
+                loop {
+                    Sleep::new(5).await;
+                }
+            });
+            Sleep::new(20).await;
+            assert!(!guard.is_finished());
+            let answer = spawn_guarded(async { 6 * 7 });
+            assert_eq!(answer.await, Ok(42));
+        });
+        let start = Instant::now();
+        runtime.wait();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(runtime.metrics().cancelled, 1);
+    }
+
+    #[test]
+    /// A future should be able to find out which task it's running in through
+    /// its `Context`, but only when one of our tasks is polling it.
+    /// 一个future应该能够通过它的`Context`知道它运行在哪个任务中，但只有当我们的任务在poll它的时候才行。
+    fn futures_can_read_their_task_id() {
+        use std::{sync::mpsc, time::Duration};
+
+        struct Spawned(mpsc::Sender<TaskId>);
+
+        impl Observer for Spawned {
+            fn on_spawn(&self, task: &TaskMeta<'_>) {
+                self.0.send(task.id()).unwrap();
+            }
+        }
+
+        let (spawned_tx, spawned_rx) = mpsc::channel();
+        let runtime = Builder::new().observer(Spawned(spawned_tx)).build();
+        let (tx, rx) = mpsc::channel();
+        runtime.spawn_named("reader", async move {
+            poll_fn(|cx| {
+                let meta = context(cx).unwrap();
+                tx.send((meta.id(), meta.name().map(str::to_owned)))
+                    .unwrap();
+                Poll::Ready(())
+            })
+            .await
+        });
+        let timeout = Duration::from_secs(30);
+        let (id, name) = rx.recv_timeout(timeout).unwrap();
+        assert_eq!(id, spawned_rx.recv_timeout(timeout).unwrap());
+        assert_eq!(name.as_deref(), Some("reader"));
+        let outside = block_on_timeout(1000, poll_fn(|cx| Poll::Ready(context(cx).is_none())));
+        assert_eq!(outside, Ok(true));
+    }
+
+    #[test]
+    /// A parent waiting on a checkpoint should be let go as soon as the child
+    /// gets there, long before it finishes, and hear `false` about one the
+    /// child never got to.
+    /// 一个在等待检查点的父任务应该在子任务到达那里时马上被放行，远早于子任务完成，而对于一个子任务从来没有到达过的检查点，应该听到`false`。
+    fn wait_checkpoint_lets_go_before_the_task_finishes() {
+        let runtime = Builder::new().worker_threads(2).build();
+        let (reached, finished) = runtime.block_on(async {
+            let child = spawn(async {
+                Sleep::new(10).await;
+                checkpoint("ready").await;
+                Sleep::new(10_000).await;
+            });
+            let reached = child.wait_checkpoint("ready").await;
+            let finished = child.is_finished();
+            // It got there a while ago, so this doesn't wait.
+            // 它早就到了那里，所以这个不需要等待。
+            assert!(child.wait_checkpoint("ready").await);
+            child.abort();
+            assert!(!child.wait_checkpoint("done").await);
+            (reached, finished)
+        });
+        assert!(reached);
+        assert!(!finished);
+    }
+
+    #[test]
+    /// A task that nobody else will ever wake should still get polled again
+    /// when another thread wakes it through a `WakeNotifier`.
+    /// 一个没有别人会唤醒的任务，在另一个线程通过`WakeNotifier`唤醒它时，仍然应该被再次poll。
+    fn wake_notifier_wakes_a_task_from_another_thread() {
+        use std::sync::mpsc;
+
+        let runtime = Builder::new().build();
+        let (id_tx, id_rx) = mpsc::channel();
+        let (tx, rx) = mpsc::channel();
+        runtime.spawn(async move {
+            let id = poll_fn(|cx| Poll::Ready(context(cx).unwrap().id())).await;
+            id_tx.send(id).unwrap();
+            notified().await;
+            tx.send(()).unwrap();
+        });
+        let timeout = Duration::from_secs(30);
+        let id = id_rx.recv_timeout(timeout).unwrap();
+        let notifier = runtime.wake_notifier();
+        // The task might not have gotten to `notified` yet, in which case
+        // there's nobody to wake and we try again.
+        // 任务可能还没有走到`notified`，这种情况下没有人可以唤醒，我们再试一次。
+        thread::spawn(move || {
+            while !notifier.wake(id) {
+                thread::yield_now();
+            }
+        });
+        rx.recv_timeout(timeout).unwrap();
+        runtime.wait();
+        assert!(!runtime.wake_notifier().wake(id));
+    }
+
+    /// Counts the allocations made on each thread so that tests can check
+    /// that something doesn't allocate when it shouldn't.
+    /// 统计每个线程上的内存分配次数，这样测试就可以检查某个东西在不应该分配的时候没有分配。
+    #[cfg(test)]
+    struct CountingAllocator;
+
+    #[cfg(test)]
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[cfg(test)]
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[cfg(test)]
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    /// Blocking on a future should hand its output straight back, and the
+    /// only allocation should be the one the future makes itself.
+    /// 阻塞在一个future上应该直接交还它的输出，唯一的内存分配应该是这个future自己做的那一次。
+    fn block_on_hands_back_the_output_without_allocating() {
+        let runtime = Builder::new().worker_threads(0).build();
+        // The first time around the thread sets up its `Parker`.
+        // 第一次的时候线程会设置好它的`Parker`。
+        runtime.block_on(async {});
+        let before = ALLOCATIONS.get();
+        let output = runtime.block_on(async { vec![1, 2, 3] });
+        assert_eq!(ALLOCATIONS.get() - before, 1);
+        assert_eq!(output, [1, 2, 3]);
+        let slept = runtime.block_on(async {
+            Sleep::new(10).await;
+            "slept"
+        });
+        assert_eq!(slept, "slept");
+    }
+
+    #[test]
+    /// A boxed `Future` handed to `spawn_boxed` should run, and spawning it
+    /// should only allocate the `Task` itself rather than boxing it again.
+    /// 交给`spawn_boxed`的一个装箱了的`Future`应该会运行，而且生成它应该只会分配`Task`本身，而不会再次装箱它。
+    fn spawn_boxed_does_not_box_again() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let ran = Arc::new(AtomicUsize::new(0));
+        let boxed = |ran: Arc<AtomicUsize>| -> BoxFuture<'static, ()> {
+            Box::pin(async move {
+                ran.fetch_add(1, Ordering::Relaxed);
+            })
+        };
+        // The first spawn sets up the registry and a spare queue node.
+        // 第一次生成会设置好registry和一个备用的队列节点。
+        runtime.spawn_boxed(boxed(ran.clone()));
+        while runtime.tick() {}
+        let future = boxed(ran.clone());
+        let before = ALLOCATIONS.get();
+        runtime.spawn_boxed(future);
+        assert_eq!(ALLOCATIONS.get() - before, 1);
+        while runtime.tick() {}
+        assert_eq!(ran.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    /// A boxed future only has to be `Send` to be spawned, so one holding a
+    /// `Cell` across an `await` should run just fine.
+    /// 一个装箱了的future只需要是`Send`的就能被生成，所以一个跨越`await`持有`Cell`的future应该可以正常运行。
+    fn spawn_boxed_takes_futures_that_are_not_sync() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let ran = Arc::new(AtomicUsize::new(0));
+        let counted = ran.clone();
+        runtime.spawn_boxed(Box::pin(async move {
+            let count = Cell::new(1);
+            crate::futures::yield_now().await;
+            count.set(count.get() + 1);
+            counted.store(count.get(), Ordering::Relaxed);
+        }));
+        while runtime.tick() {}
+        assert_eq!(ran.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    /// Tasks spawned while the runtime is paused should wait on the queue and
+    /// only run once it's resumed.
+    /// 在运行时暂停时生成的任务应该在队列上等待，只有在它恢复之后才会运行。
+    fn paused_runtimes_hold_their_tasks() {
+        let runtime = Builder::new().worker_threads(2).build();
+        runtime.pause();
+        let ran = Arc::new(AtomicUsize::new(0));
+        for _ in 0..5 {
+            let ran = ran.clone();
+            runtime.spawn(async move {
+                ran.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        thread::sleep(Duration::from_millis(50));
+        assert!(!runtime.tick());
+        assert_eq!(ran.load(Ordering::SeqCst), 0);
+        assert_eq!(runtime.shared.queue.lock().unwrap().list.len(), 5);
+        runtime.resume();
+        runtime.wait();
+        assert_eq!(ran.load(Ordering::SeqCst), 5);
     }
 
-    /// Our runtime type is designed such that we only ever have one running.
-    /// You might want to have multiple running in production code though. For
-    /// instance you limit what happens on one runtime for a free tier version
-    /// and let the non-free version use as many resources as it can. We
-    /// implement 3 functions: `start` to actually get async code running, `get`
-    /// so that we can get references to the runtime, and `spawner` a
-    /// convenience function to get a `Spawner` to spawn tasks onto the `Runtime`.
-    /// 我们的运行时类型被设计成只有一个运行时。但是在生产代码中，您可能希望有多个运行时。
-    /// 例如，您限制了免费版本上运行时的功能，并让非免费版本可以使用尽可能多的资源。
-    /// 我们实现了3个函数：
-    /// `start` 来实际运行异步代码。
-    /// `get` 以便我们可以获取对运行时的引用。
-    /// `spawner` 一个方便的函数，获取一个 `Spawner` 来将任务放入 `Runtime`。
-    impl Runtime {
-        /// This is what actually drives all of our async code. We spawn a
-        /// separate thread that loops getting the next task off the queue and
-        /// if it exists polls it or continues if not. It also checks if the
-        /// task should block and if it does it just keeps polling the task
-        /// until it completes! Otherwise it wakes the task to put it back in
-        /// the queue in the non-blocking version if it's still pending.
-        /// Otherwise it drops the task by not putting it back into the queue
-        /// since it's completed.
-        /// 这就是实际驱动我们所有异步代码的程序。
-        /// 我们在一个单独的线程中启动一个循环，从队列中获取下一个任务，
-        /// 如果存在，则poll它，如果不存在，则继续循环获取任务。
-        /// 获取任务后，检查任务是否应该阻塞，如果是，则只会持续poll该任务，直到任务完成！
-        /// 否则，它会poll一次任务，如果任务仍然未完成，则以非阻塞的方式将其放回队列中。
-        fn start() {
-            std::thread::spawn(|| {
-                loop {
-                    let task = match Runtime::get().queue.lock().unwrap().pop_front() {
-                        Some(task) => task,
-                        None => continue,
-                    };
-                    if task.will_block() {
-                        while let Poll::Pending = task.poll() {
-                            if Self::check_if_print() {
-                                // println!("blocking {} {}", current_thread_id(), current_time());
-                            }
-                        }
-                    } else {
-                        if let Poll::Pending = task.poll() {
-                            if Self::check_if_print() {
-                                // println!("waking {} {}", current_thread_id(), current_time());
-                            }
-                            task.wake();
-                        }
+    #[test]
+    /// `on_idle` should go off once when a busy runtime runs out of work, and
+    /// not again until it's been busy again.
+    /// `on_idle`应该在一个忙碌的运行时没活干的时候触发一次，在它再次忙碌之前不会再触发。
+    fn on_idle_fires_once_per_idle_spell() {
+        let runtime = Builder::new().worker_threads(2).build();
+        let idles = Arc::new(AtomicUsize::new(0));
+        let counter = idles.clone();
+        runtime.on_idle(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        // The workers were never busy, so starting up doesn't count.
+        // 工作线程从来没有忙过，所以启动不算数。
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(idles.load(Ordering::SeqCst), 0);
+        for round in 1..=2 {
+            // Holding everything on the queue until it's all there keeps the
+            // workers from running out of work part way through spawning.
+            // 把所有东西都留在队列上直到它们全部到齐，这样工作线程就不会在生成到一半的时候没活干。
+            runtime.pause();
+            for _ in 0..20 {
+                runtime.spawn(async {
+                    let mut total = 0u64;
+                    for i in 0..10_000 {
+                        total = std::hint::black_box(total + i);
                     }
-                }
+                });
+            }
+            runtime.resume();
+            runtime.wait();
+            thread::sleep(Duration::from_millis(100));
+            assert_eq!(idles.load(Ordering::SeqCst), round);
+        }
+    }
+
+    #[test]
+    /// `abort_matching` should abort the tasks it picks out and leave the
+    /// rest alone.
+    /// `abort_matching`应该中止它挑出来的任务，而不去管其他的。
+    fn abort_matching_only_aborts_what_it_picks() {
+        let runtime = Builder::new().worker_threads(2).build();
+        let doomed: Vec<_> = (0..3)
+            .map(|_| runtime.spawn_named("cancelme", Sleep::new(10_000)))
+            .collect();
+        let spared: Vec<_> = (0..2)
+            .map(|i| {
+                runtime.spawn_named("keepme", async move {
+                    Sleep::new(50).await;
+                    i
+                })
+            })
+            .collect();
+        assert_eq!(
+            runtime.abort_matching(|task| task.name() == Some("cancelme")),
+            3
+        );
+        runtime.block_on(async move {
+            for handle in doomed {
+                assert_eq!(handle.await, Err(JoinError::Cancelled));
+            }
+            for (i, handle) in spared.into_iter().enumerate() {
+                assert_eq!(handle.await, Ok(i));
+            }
+        });
+    }
+
+    #[test]
+    /// A predicate that spawns, or that lets go of the last thing keeping a
+    /// task alive, shouldn't deadlock `abort_matching` on the registry.
+    /// 一个会生成任务的predicate，或者一个放掉了让某个任务存活的最后一样东西的predicate，都不应该让`abort_matching`在registry上死锁。
+    fn abort_matching_predicates_can_spawn_and_drop_tasks() {
+        use std::sync::mpsc;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let runtime = Builder::new().worker_threads(0).build();
+            let stored = Arc::new(Mutex::new(None));
+            let store = stored.clone();
+            runtime.spawn(poll_fn(move |cx| {
+                *store.lock().unwrap() = Some(cx.waker().clone());
+                Poll::<()>::Pending
+            }));
+            assert!(runtime.tick());
+            let aborted = runtime.abort_matching(|_| {
+                drop(stored.lock().unwrap().take());
+                runtime.spawn(async {});
+                false
             });
+            tx.send(aborted).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(30)), Ok(0));
+    }
+
+    #[test]
+    /// After spawning three named tasks, `tasks` should list exactly those
+    /// three with what they were spawned with, and forget them once they're
+    /// gone.
+    /// 在生成了三个有名字的任务之后，`tasks`应该恰好列出这三个任务以及它们生成时的信息，并在它们消失之后忘掉它们。
+    fn tasks_lists_what_is_alive() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let handles: Vec<_> = [
+            ("reader", Priority::Normal),
+            ("writer", Priority::High),
+            ("janitor", Priority::Normal),
+        ]
+        .into_iter()
+        .map(|(name, priority)| {
+            runtime
+                .spawner()
+                .spawn_with(Some(name.to_owned()), priority, async {
+                    Sleep::new(10).await
+                })
+        })
+        .collect();
+        runtime.tick();
+        let tasks = runtime.tasks();
+        let listed: Vec<_> = tasks
+            .iter()
+            .map(|task| (task.name.as_deref(), task.priority, task.blocking))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                (Some("reader"), Priority::Normal, false),
+                (Some("writer"), Priority::High, false),
+                (Some("janitor"), Priority::Normal, false),
+            ]
+        );
+        // Only the first one has been polled so far.
+        // 到目前为止只有第一个被poll过。
+        let polls: Vec<_> = tasks.iter().map(|task| task.polls).collect();
+        assert_eq!(polls, [1, 0, 0]);
+        assert!(tasks.iter().all(|task| task.age < Duration::from_secs(30)));
+        for handle in handles {
+            assert_eq!(runtime.block_on_cooperative(handle), Ok(()));
         }
+        runtime.assert_no_leaked_tasks();
+        assert!(runtime.tasks().is_empty());
+    }
+
+    #[test]
+    /// A task waiting on `shutdown_signal` should hear about a shutdown
+    /// right away and get to finish on its own.
+    /// 一个在等待`shutdown_signal`的任务应该马上听到关闭的消息，并且可以自己结束。
+    fn shutdown_signal_lets_tasks_leave_cleanly() {
+        let runtime = Builder::new().build();
+        let handle = runtime.spawn(async {
+            crate::select_biased! {
+                () = shutdown_signal() => "shut down",
+                () = Sleep::new(10_000) => "slept",
+            }
+        });
+        thread::sleep(Duration::from_millis(20));
+        let start = Instant::now();
+        runtime.shutdown();
+        assert_eq!(runtime.block_on(handle), Ok("shut down"));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(runtime.metrics().cancelled, 0);
+    }
+
+    #[test]
+    /// With room on the queue for two, a third spawn should wait, get
+    /// dropped, push out the first, or be refused, depending on the
+    /// `OverflowPolicy`.
+    /// 在队列上有两个位置的情况下，第三次生成应该根据`OverflowPolicy`等待、被丢弃、挤掉第一个、或者被拒绝。
+    fn overflow_policies_shed_the_right_task() {
+        let build = |policy| {
+            Builder::new()
+                .worker_threads(0)
+                .queue_capacity(2)
+                .overflow_policy(policy)
+                .build()
+        };
+        let results = |runtime: &Runtime, handles: Vec<JoinHandle<i32>>| {
+            while runtime.tick() {}
+            handles
+                .into_iter()
+                .map(|handle| runtime.block_on(handle).ok())
+                .collect::<Vec<_>>()
+        };
+
+        let runtime = build(OverflowPolicy::DropNewest);
+        let handles = (0..3).map(|i| runtime.spawn(async move { i })).collect();
+        assert_eq!(results(&runtime, handles), [Some(0), Some(1), None]);
+
+        let runtime = build(OverflowPolicy::DropOldest);
+        let handles = (0..3).map(|i| runtime.spawn(async move { i })).collect();
+        assert_eq!(results(&runtime, handles), [None, Some(1), Some(2)]);
+
+        let runtime = build(OverflowPolicy::Reject);
+        let mut handles: Vec<_> = (0..2).map(|i| runtime.spawn(async move { i })).collect();
+        assert_eq!(
+            runtime.try_spawn(async { 2 }).err(),
+            Some(SpawnError::QueueFull)
+        );
+        handles.push(runtime.spawn(async { 2 }));
+        assert_eq!(results(&runtime, handles), [Some(0), Some(1), None]);
+
+        let runtime = build(OverflowPolicy::Reject);
+        let handles = runtime.spawn_many((0..4).map(|i| async move { i }));
+        assert_eq!(runtime.metrics().alive, 2);
+        assert_eq!(results(&runtime, handles), [Some(0), Some(1), None, None]);
+
+        let runtime = build(OverflowPolicy::Block);
+        let mut handles: Vec<_> = (0..2).map(|i| runtime.spawn(async move { i })).collect();
+        let spawned = AtomicBool::new(false);
+        let third = thread::scope(|scope| {
+            let third = scope.spawn(|| {
+                let handle = runtime.spawn(async { 2 });
+                spawned.store(true, Ordering::SeqCst);
+                handle
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!spawned.load(Ordering::SeqCst));
+            // Running one task makes room for the third.
+            // 运行一个任务就为第三个腾出了空间。
+            assert!(runtime.tick());
+            third.join().unwrap()
+        });
+        assert!(spawned.load(Ordering::SeqCst));
+        handles.push(third);
+        assert_eq!(results(&runtime, handles), [Some(0), Some(1), Some(2)]);
+    }
+
+    #[test]
+    /// A task that's been woken back up shouldn't count against a full queue,
+    /// and `DropOldest` should push out the oldest new task rather than it,
+    /// even when it's at the front of the queue.
+    /// 一个被唤醒回来的任务不应该算在满了的队列里，而`DropOldest`应该挤掉最老的新任务而不是它，即使它在队列的最前面。
+    fn overflow_policies_keep_woken_tasks() {
+        let runtime = Builder::new()
+            .worker_threads(0)
+            .queue_capacity(2)
+            .overflow_policy(OverflowPolicy::DropOldest)
+            .build();
+        let (tx, rx) = crate::futures::sync::oneshot::channel();
+        let mut handles = vec![runtime.spawn(async move { rx.await.unwrap() })];
+        assert!(runtime.tick());
+        tx.send(0).unwrap();
+        handles.extend((1..4).map(|i| runtime.spawn(async move { i })));
+        assert_eq!(runtime.shared.queue.lock().unwrap().list.len(), 3);
+        while runtime.tick() {}
+        let results: Vec<_> = handles
+            .into_iter()
+            .map(|handle| runtime.block_on(handle).ok())
+            .collect();
+        assert_eq!(results, [Some(0), None, Some(2), Some(3)]);
+    }
+
+    #[test]
+    /// Every worker should call `on_thread_start` before `build` returns, and
+    /// `on_thread_stop` by the time the runtime is dropped.
+    /// 每个工作线程都应该在`build`返回之前调用`on_thread_start`，并在运行时被丢弃时已经调用了`on_thread_stop`。
+    fn thread_hooks_run_once_per_worker() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let runtime = Builder::new()
+            .worker_threads(3)
+            .on_thread_start({
+                let started = started.clone();
+                move || {
+                    started.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .on_thread_stop({
+                let stopped = stopped.clone();
+                move || {
+                    stopped.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .build();
+        assert_eq!(started.load(Ordering::SeqCst), 3);
+        assert_eq!(stopped.load(Ordering::SeqCst), 0);
+        drop(runtime);
+        assert_eq!(stopped.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    /// A task should be able to make a runtime of its own, run work on it,
+    /// and drop it again, stopping its workers, while the rest of the outer
+    /// runtime carries on and both shut down cleanly.
+    /// 一个任务应该能够创建一个它自己的运行时，在上面运行工作，然后再丢弃它并停止它的工作线程，
+    /// 与此同时外层运行时的其余部分继续运转，而两个运行时都能干净地关闭。
+    fn tasks_can_run_a_runtime_of_their_own() {
+        use crate::futures::Sleep;
+        use std::{sync::mpsc, time::Duration};
+
+        let outer = Builder::new().worker_threads(2).build();
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel();
+        outer.spawn({
+            let stopped = stopped.clone();
+            let tx = tx.clone();
+            async move {
+                let inner = Builder::new()
+                    .worker_threads(2)
+                    .on_thread_stop(move || {
+                        stopped.fetch_add(1, Ordering::SeqCst);
+                    })
+                    .build();
+                let handles: Vec<_> = (0..3)
+                    .map(|i| {
+                        inner.spawn(async move {
+                            Sleep::new(50).await;
+                            i * 2
+                        })
+                    })
+                    .collect();
+                let total = inner.block_on(async {
+                    let mut total = 0;
+                    for handle in handles {
+                        total += handle.await.unwrap();
+                    }
+                    // Back on the outer runtime's worker, but anything we
+                    // spawn right now still belongs to the inner one.
+                    // 我们还在外层运行时的工作线程上，但我们此刻生成的任何东西仍然属于内层运行时。
+                    spawn(async { 1 }).await.unwrap() + total
+                });
+                drop(inner);
+                tx.send(("inner", total)).unwrap();
+            }
+        });
+        outer.spawn(async move {
+            Sleep::new(10).await;
+            tx.send(("outer", 0)).unwrap();
+        });
+        let mut got: Vec<_> = (0..2)
+            .map(|_| rx.recv_timeout(Duration::from_secs(30)).unwrap())
+            .collect();
+        got.sort();
+        assert_eq!(got, [("inner", 7), ("outer", 0)]);
+        assert_eq!(stopped.load(Ordering::SeqCst), 2);
+        outer.wait();
+        drop(outer);
+    }
 
-        fn check_if_print() -> bool {
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
+    #[test]
+    /// Two runtimes with the same seed should hand a task the same random
+    /// delays, and one with a different seed shouldn't.
+    /// 两个有同样种子的运行时应该交给任务同样的随机延迟，而一个有不同种子的运行时不应该。
+    fn seeded_runtimes_hand_out_the_same_numbers() {
+        let delays = |seed| {
+            Builder::new().seed(seed).build().block_on(async {
+                spawn(async {
+                    let rng = rng();
+                    (0..8).map(|_| rng.gen_range(1..5)).collect::<Vec<_>>()
+                })
+                .await
                 .unwrap()
-                .as_micros()
-                % 1000000
-                == 1
-        }
+            })
+        };
+        let first = delays(42);
+        assert!(first.iter().all(|delay| (1..5).contains(delay)));
+        assert_eq!(first, delays(42));
+        assert_ne!(first, delays(7));
+    }
 
-        /// A function to get a reference to the `Runtime`
-        /// 一个获取 `Runtime` 引用的函数
-        pub(crate) fn get() -> &'static Runtime {
-            RUNTIME.get_or_init(setup_runtime)
-        }
+    #[test]
+    #[cfg(debug_assertions)]
+    /// Taking a task off the count one time too many should be caught rather
+    /// than wrapping the counter around.
+    /// 多从计数中减去一次任务应该被抓住，而不是让计数器绕回去。
+    fn task_counter_underflow_is_caught() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
 
-        /// A function to get a new `Spawner` from the `Runtime`
-        /// 一个从 `Runtime` 获取新 `Spawner` 的函数
-        pub(crate) fn spawner() -> Spawner {
-            Runtime::get().spawner.clone()
+        let runtime = Builder::new().worker_threads(0).build();
+        runtime.spawn(async {});
+        while runtime.tick() {}
+        assert_eq!(runtime.metrics().alive, 0);
+        let underflow = catch_unwind(AssertUnwindSafe(|| runtime.shared.release_task()));
+        assert!(underflow.is_err());
+    }
+
+    #[test]
+    /// A parent that awaits its children's handles should always see every
+    /// write the children made before they finished, even ones made with
+    /// `Relaxed` stores that nothing else synchronizes.
+    /// 一个await它的子任务的handle的父任务应该总是能看到子任务在完成之前所做的每一次写入，
+    /// 即使是那些用`Relaxed`存储所做的、没有任何其他东西同步的写入。
+    fn joining_a_child_sees_its_writes() {
+        let runtime = Builder::new().worker_threads(4).build();
+        for round in 0..100 {
+            let seen = runtime.block_on(async move {
+                let slots: Arc<Vec<AtomicUsize>> =
+                    Arc::new((0..8).map(|_| AtomicUsize::new(0)).collect());
+                let handles: Vec<_> = (0..8)
+                    .map(|i| {
+                        let slots = slots.clone();
+                        spawn(async move {
+                            for value in 0..=round + i {
+                                slots[i].store(value, Ordering::Relaxed);
+                            }
+                            i
+                        })
+                    })
+                    .collect();
+                let mut seen = Vec::new();
+                for handle in handles {
+                    let i = handle.await.unwrap();
+                    seen.push(slots[i].load(Ordering::Relaxed));
+                }
+                seen
+            });
+            assert_eq!(seen, (0..8).map(|i| round + i).collect::<Vec<_>>());
         }
     }
 
-    /// This is the initialization function for our `RUNTIME` static below. We
-    /// make a call to start it up and then return a `Runtime` to be put in the
-    /// static value
-    /// 这是我们下面的 `RUNTIME` 静态变量的初始化函数。
-    /// 我们调用它来启动`RUNTIME`，然后返回一个 `Runtime` 以放入静态值 RUNTIME 中。
-    fn setup_runtime() -> Runtime {
-        // This is okay to call because any calls to `Runtime::get()` in here will be blocked
-        // until we fully initialize the `Lazy` type thanks to the `call_once`
-        // function on `Once` which blocks until it finishes initializing.
-        // So we start the runtime inside the initialization function, which depends
-        // on it being initialized, but it is able to wait until the runtime is
-        // actually initialized and so it all just works.
-        // 这里调用是可以的，因为在这里调用 `Runtime::get()` 时，由于 `Once` 上的 `call_once` 函数会阻塞，直到它完成初始化，
-        // 所以任何对 `Runtime::get()` 的调用都会被阻塞，直到我们完全初始化了 `Lazy` 类型。
-        // 所以我们在初始化函数中启动运行时，这取决于它是否已初始化，这里阻塞直至运行时完成初始化，所以一切都能正常工作。
-        Runtime::start();
-        let queue = Arc::new(Mutex::new(LinkedList::new()));
-        Runtime {
-            spawner: Spawner {
-                queue: queue.clone(),
-            },
-            queue,
-            tasks: AtomicUsize::new(0),
+    #[test]
+    /// Shutting down with a deadline should let a quick task finish, abort a
+    /// slow one once the deadline hits, and turn away anything new.
+    /// 带截止时间的关闭应该让一个快的任务完成，在截止时间到达时中止一个慢的任务，并拒绝任何新任务。
+    fn shutdown_timeout_aborts_stragglers() {
+        use crate::futures::Sleep;
+
+        let runtime = Builder::new().build();
+        let quick = runtime.spawn(async {
+            Sleep::new(10).await;
+            "quick"
+        });
+        let slow = runtime.spawn(async {
+            Sleep::new(10_000).await;
+            "slow"
+        });
+        let start = Instant::now();
+        runtime.shutdown_timeout(200);
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(30));
+        assert_eq!(block_on_timeout(1000, quick), Ok(Ok("quick")));
+        assert_eq!(block_on_timeout(1000, slow), Ok(Err(JoinError::Cancelled)));
+        let late = runtime.spawn(async { "late" });
+        assert_eq!(block_on_timeout(1000, late), Ok(Err(JoinError::Cancelled)));
+
+        // A deadline too far off to hold just means waiting for the tasks.
+        // 一个远到装不下的截止时间只是意味着等着这些任务。
+        let runtime = Builder::new().build();
+        let quick = runtime.spawn(async {
+            Sleep::new(10).await;
+            "quick"
+        });
+        runtime.shutdown_timeout(u128::MAX);
+        assert_eq!(block_on_timeout(1000, quick), Ok(Ok("quick")));
+    }
+
+    #[test]
+    /// Spawning past `max_tasks` should be turned away until some of the
+    /// tasks that are already there finish.
+    /// 超过`max_tasks`的生成应该被拒绝，直到一些已经存在的任务完成。
+    fn spawning_past_max_tasks_is_rejected() {
+        let runtime = Builder::new().worker_threads(0).max_tasks(2).build();
+        assert!(runtime.try_spawn(async {}).is_ok());
+        assert!(runtime.try_spawn(async {}).is_ok());
+        assert_eq!(
+            runtime.try_spawn(async {}).err(),
+            Some(SpawnError::TooManyTasks)
+        );
+        let rejected = runtime.spawn(async { 1 });
+        assert_eq!(
+            block_on_timeout(1000, rejected),
+            Ok(Err(JoinError::Cancelled))
+        );
+        while runtime.tick() {}
+        let accepted = runtime.try_spawn(async { 2 }).unwrap();
+        runtime.tick();
+        assert_eq!(block_on_timeout(1000, accepted), Ok(Ok(2)));
+    }
+
+    #[test]
+    /// A task spawned onto the front should run before the ones that were
+    /// spawned onto the back earlier.
+    /// 生成到前端的任务应该在之前生成到末尾的任务之前运行。
+    fn spawn_front_runs_before_the_backlog() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for i in 0..3 {
+            let order = order.clone();
+            runtime.spawn(async move { order.lock().unwrap().push(i) });
         }
+        let front = order.clone();
+        runtime.spawn_front(async move { front.lock().unwrap().push(99) });
+        while runtime.tick() {}
+        assert_eq!(*order.lock().unwrap(), [99, 0, 1, 2]);
     }
 
-    /// With all of the work we did in `crate::lazy` we can now create our static type to represent
-    /// the singular `Runtime` when it is finally initialized by the `setup_runtime` function.
-    /// 在 `crate::lazy` 中完成了所有工作后，我们现在可以创建一个静态类型，以表示最终由 `setup_runtime` 函数初始化的单个 `Runtime`。
-    static RUNTIME: crate::lazy::Lazy<Runtime> = crate::lazy::Lazy::new();
+    #[test]
+    /// A task that finishes, one that gets aborted while it's still on the
+    /// queue, and one that aborts itself in the middle of being polled should
+    /// each be counted exactly once, and as the right thing.
+    /// 一个完成了的任务、一个还在队列上时被中止的任务、以及一个在被poll的过程中中止了自己的任务，
+    /// 都应该恰好被计数一次，并且被算作正确的那一类。
+    fn metrics_count_every_way_a_task_ends() {
+        let runtime = Builder::new().worker_threads(0).build();
+        runtime.spawn(async {});
+        while runtime.tick() {}
 
-    // The queue is a single linked list that contains all of the tasks being
-    // run on it. We hand out access to it using a Mutex that has an Arc
-    // pointing to it so that we can make sure only one thing is touching the
-    // queue state at a given time. This isn't the most efficient pattern
-    // especially if we wanted to have the runtime be truly multi-threaded, but
-    // for the purposes of the code this works just fine.
-    // 队列是一个单链表，其中包含在其上运行的所有任务。
-    // 我们使用一个带有指向它的 Arc 的 Mutex 来访问它，以便我们可以确保在给定时间只有一个「事物」能够获取队列状态。
-    // 这种模式不是最高效的，特别是如果我们想让运行时真正地多线程化，但是对于这段代码来说，这是可以的。
-    type Queue = Arc<Mutex<LinkedList<Arc<Task>>>>;
+        let queued = runtime.spawn(std::future::pending::<()>());
+        queued.abort();
+        while runtime.tick() {}
 
-    /// We've talked about the `Spawner` a lot up till this point, but it's
-    /// really just a light wrapper around the queue that knows how to push
-    /// tasks onto the queue and create new ones.
-    /// 我们一直在讨论 `Spawner`，但它实际上只是一个轻量级的包装器，它知道如何将任务推送到队列中并创建新任务。
-    #[derive(Clone)]
-    pub(crate) struct Spawner {
-        queue: Queue,
+        let slot: Arc<Mutex<Option<JoinHandle<()>>>> = Arc::new(Mutex::new(None));
+        let own = slot.clone();
+        let handle = runtime.spawn(poll_fn(move |_| {
+            own.lock().unwrap().take().unwrap().abort();
+            Poll::Pending
+        }));
+        *slot.lock().unwrap() = Some(handle);
+        while runtime.tick() {}
+
+        assert_eq!(
+            runtime.metrics(),
+            Metrics {
+                alive: 0,
+                completed: 1,
+                cancelled: 2,
+            }
+        );
     }
 
-    impl Spawner {
-        /// This is the function that gets called by the `spawn` function to
-        /// actually create a new `Task` in our queue. It takes the `Future`,
-        /// constructs a `Task` and then pushes it to the back of the queue.
-        /// 这是 `spawn` 函数，用于在队列中实际创建新的 `Task`。
-        /// 它接收 `Future`，构造一个 `Task`，然后将其推送到队列的末尾。
-        fn spawn(self, future: impl Future<Output = ()> + Send + Sync + 'static) {
-            self.inner_spawn(Task::new(false, future));
+    #[test]
+    /// Values should come out of a two stage pipeline having gone through
+    /// both stages, in the order they went in.
+    /// 值从一个两阶段的流水线出来时应该经过了两个阶段，并且保持它们进去时的顺序。
+    fn pipeline_runs_values_through_every_stage() {
+        let runtime = Builder::new().build();
+        let (tx, mut rx) = runtime.pipeline(vec![
+            Box::new(|x: i32| Box::pin(async move { x * 2 })),
+            Box::new(|x: i32| Box::pin(async move { x + 1 })),
+        ]);
+        for i in 1..=3 {
+            tx.send(i).unwrap();
         }
-        /// This is the function that gets called by the `spawn_blocking` function to
-        /// actually create a new `Task` in our queue. It takes the `Future`,
-        /// constructs a `Task` and then pushes it to the front of the queue
-        /// where the runtime will check if it should block and then block until
-        /// this future completes.
-        /// 这是 `spawn_blocking` 函数，用于在队列中实际创建新的 `Task`。
-        /// 它接收 `Future`，构造一个 `Task`，然后将其推送到队列的前端，运行时将检查它是否应该阻塞，然后阻塞直到此 future 完成。
-        fn spawn_blocking(self, future: impl Future<Output = ()> + Send + Sync + 'static) {
-            self.inner_spawn_blocking(Task::new(true, future));
+        drop(tx);
+        let outputs = runtime.block_on(async move {
+            let mut outputs = Vec::new();
+            while let Some(output) = rx.recv().await {
+                outputs.push(output);
+            }
+            outputs
+        });
+        assert_eq!(outputs, [3, 5, 7]);
+    }
+
+    #[test]
+    /// Waking a task a hundred times before it gets polled again should only
+    /// put it on the queue once, and only get it polled once more.
+    /// 在一个任务再次被poll之前唤醒它一百次，应该只会把它放到队列上一次，也只会让它再被poll一次。
+    fn repeated_wakes_only_queue_a_task_once() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let polls = Arc::new(AtomicUsize::new(0));
+        let waker = Arc::new(Mutex::new(None));
+        let (task_polls, task_waker) = (polls.clone(), waker.clone());
+        runtime.spawn(poll_fn(move |cx| {
+            if task_polls.fetch_add(1, Ordering::Relaxed) > 0 {
+                return Poll::Ready(());
+            }
+            *task_waker.lock().unwrap() = Some(cx.waker().clone());
+            Poll::Pending
+        }));
+        runtime.tick();
+        let waker: Waker = waker.lock().unwrap().take().unwrap();
+        for _ in 0..100 {
+            waker.wake_by_ref();
         }
-        /// This function just takes a `Task` and pushes it onto the queue. We use this
-        /// both for spawning new `Task`s and to push old ones that get woken up
-        /// back onto the queue.
-        /// 这个函数只是接收一个 `Task` 并将其推送到队列中。
-        /// 我们用它来启动新的 `Task`，以及将唤醒的旧任务推送回队列。
-        fn inner_spawn(self, task: Arc<Task>) {
-            self.queue.lock().unwrap().push_back(task);
+        assert_eq!(runtime.shared.queue.lock().unwrap().list.len(), 1);
+        while runtime.tick() {}
+        assert_eq!(polls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    /// Tasks spawned right before awaiting `next_tick` should all have been
+    /// polled by the time it's done.
+    /// 在await `next_tick`之前刚刚生成的任务，在它完成时都应该已经被poll过了。
+    fn next_tick_waits_for_the_queue_to_drain() {
+        let runtime = Builder::new().worker_threads(0).build();
+        let polled = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = std::sync::mpsc::channel();
+        let counter = polled.clone();
+        runtime.spawn(async move {
+            for _ in 0..3 {
+                let counter = counter.clone();
+                spawn(async move {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+            let before = counter.load(Ordering::Relaxed);
+            next_tick().await;
+            tx.send((before, counter.load(Ordering::Relaxed))).unwrap();
+        });
+        while runtime.tick() {}
+        assert_eq!(rx.try_recv(), Ok((0, 3)));
+    }
+
+    #[test]
+    /// `wait` should never return while some of the tasks spawned before it
+    /// haven't finished, and once it does everything they did should be
+    /// visible.
+    /// `wait`在它之前生成的某些任务还没有完成时永远不应该返回，一旦它返回，它们所做的一切都应该是可见的。
+    fn wait_never_returns_early() {
+        let runtime = Builder::new().worker_threads(4).build();
+        let done = Arc::new(AtomicUsize::new(0));
+        for round in 1..=200 {
+            for _ in 0..10 {
+                let done = done.clone();
+                runtime.spawn(async move {
+                    spawn(async move {
+                        done.fetch_add(1, Ordering::Relaxed);
+                    });
+                });
+            }
+            runtime.wait();
+            assert_eq!(done.load(Ordering::Relaxed), round * 10);
         }
-        /// This function takes a `Task` and pushes it to the front of the queue
-        /// if it is meant to block. We use this both for spawning new blocking
-        /// `Task`s and to push old ones that get woken up back onto the queue.
-        /// 如果它是用于阻塞的，则此函数将 `Task` 推送到队列的前端。
-        /// 我们用它来启动新的阻塞 `Task`，以及将唤醒的旧任务推送回队列。
-        fn inner_spawn_blocking(self, task: Arc<Task>) {
-            self.queue.lock().unwrap().push_front(task);
+    }
+
+    #[test]
+    /// A `Future` whose `Drop` spawns a task, whether it finishes or gets
+    /// aborted, should have that task run on the same runtime before `wait`
+    /// returns.
+    /// 一个在`Drop`中生成任务的`Future`，无论它是完成了还是被中止了，那个任务都应该在同一个运行时上、在`wait`返回之前运行。
+    fn spawning_from_drop_runs_before_wait_returns() {
+        struct Logger {
+            log: Arc<Mutex<Vec<&'static str>>>,
+            name: &'static str,
+        }
+
+        impl Drop for Logger {
+            fn drop(&mut self) {
+                let log = self.log.clone();
+                let name = self.name;
+                spawn_detached_from_drop(async move {
+                    Sleep::new(10).await;
+                    log.lock().unwrap().push(name);
+                });
+            }
         }
+
+        let runtime = Builder::new().worker_threads(2).build();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let finished = Logger {
+            log: log.clone(),
+            name: "finished",
+        };
+        runtime.spawn(async move {
+            let _logger = finished;
+        });
+        let aborted = Logger {
+            log: log.clone(),
+            name: "aborted",
+        };
+        let handle = runtime.spawn(async move {
+            let _logger = aborted;
+            std::future::pending::<()>().await;
+        });
+        handle.abort();
+        runtime.wait();
+        let mut log = log.lock().unwrap().clone();
+        log.sort();
+        assert_eq!(log, ["aborted", "finished"]);
     }
 
-    /// Spawn a non-blocking `Future` onto the `whorl` runtime
-    /// 将非阻塞的 `Future` 放入 `whorl` 运行时
-    pub fn spawn(future: impl Future<Output = ()> + Send + Sync + 'static) {
-        Runtime::spawner().spawn(future);
+    #[test]
+    /// Two runtimes made with `Runtime::new` should each run their own tasks
+    /// without the other one ever seeing them, and both should drop cleanly.
+    /// 用`Runtime::new`创建的两个运行时应该各自运行自己的任务，而另一个永远看不到它们，并且两个都应该能干净地被丢弃。
+    fn runtimes_do_not_share_a_queue() {
+        let first = Runtime::new();
+        let second = Runtime::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        for (runtime, tasks) in [(&first, 3), (&second, 5)] {
+            for _ in 0..tasks {
+                let tx = tx.clone();
+                runtime.spawn(async move {
+                    let ran_on = CURRENT.with(|current| {
+                        current
+                            .borrow()
+                            .as_ref()
+                            .map(|spawner| Arc::as_ptr(&spawner.shared) as usize)
+                    });
+                    tx.send(ran_on).unwrap();
+                });
+            }
+        }
+        first.wait();
+        second.wait();
+        drop(tx);
+        let ran_on: Vec<_> = rx.iter().map(Option::unwrap).collect();
+        let on = |runtime: &Runtime| {
+            let shared = Arc::as_ptr(&runtime.shared) as usize;
+            ran_on.iter().filter(|&&ran_on| ran_on == shared).count()
+        };
+        assert_eq!((on(&first), on(&second)), (3, 5));
+        assert_eq!(first.metrics().completed, 3);
+        assert_eq!(second.metrics().completed, 5);
+        drop(first);
+        drop(second);
     }
 
-    /// Block on a `Future` and stop others on the `whorl` runtime until this
-    /// one completes.
-    /// 阻塞 `Future`，并在 `whorl` 运行时停止其他任务，直到此任务完成。
-    pub fn block_on(future: impl Future<Output = ()> + Send + Sync + 'static) {
-        // println!("block on called {} {}", current_thread_id(), current_time());
-        Runtime::spawner().spawn_blocking(future);
+    #[test]
+    /// Cancelling the token should cut a long `sleep_cancellable` short, while
+    /// a short one with nobody cancelling should sleep all the way through.
+    /// 取消token应该让一个很长的`sleep_cancellable`提前结束，而一个没有人取消的短睡眠应该睡满全程。
+    fn cancelling_wakes_a_cancellable_sleep() {
+        let runtime = Builder::new().build();
+        let token = CancellationToken::new();
+        assert_eq!(
+            runtime.block_on(sleep_cancellable(10, &token)),
+            SleepOutcome::Elapsed
+        );
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+        let start = Instant::now();
+        assert_eq!(
+            runtime.block_on(sleep_cancellable(5000, &token)),
+            SleepOutcome::Cancelled
+        );
+        assert!(start.elapsed() < Duration::from_millis(2000));
     }
 
-    /// Block further execution of a program until all of the tasks on the
-    /// `whorl` runtime are completed.
-    /// 阻止程序的进一步执行，直到 `whorl` 运行时上的所有任务完成。
-    pub fn wait() {
-        // println!("wait called {} {}", current_thread_id(), current_time());
-        let runtime = Runtime::get();
-        while runtime.tasks.load(Ordering::Relaxed) > 0 {}
+    #[test]
+    /// Spawning a thousand tasks at once should go to the `Condvar` far fewer
+    /// than a thousand times, and every one of them should still run.
+    /// 一次生成一千个任务去找`Condvar`的次数应该远远少于一千次，而且它们中的每一个都仍然应该运行。
+    fn spawning_a_burst_does_not_notify_every_time() {
+        let runtime = Builder::new().worker_threads(4).build();
+        let notifies = || runtime.shared.work.notifies.load(Ordering::Relaxed);
+        let before = notifies();
+        let handles = runtime.spawn_many((0..1000).map(|i| async move { i * 2 }));
+        runtime.wait();
+        assert!(notifies() - before <= 4, "{} notifies", notifies() - before);
+        let outputs: Vec<_> = handles
+            .into_iter()
+            .map(|handle| runtime.block_on(handle).unwrap())
+            .collect();
+        assert_eq!(outputs, (0..1000).map(|i| i * 2).collect::<Vec<_>>());
     }
 
-    /// The `Task` is the basic unit for the executor. It represents a `Future`
-    /// that may or may not be completed. We spawn `Task`s to be run and poll
-    /// them until completion in a non-blocking manner unless specifically asked
-    /// for.
-    /// `Task` 是执行器的基本单元。它表示一个可能已完成或未完成的 `Future`。
-    /// 我们启动 `Task` 来运行，并以非阻塞方式轮询它们，直到完成，除非明确制定为阻塞任务。
-    struct Task {
-        /// This is the actual `Future` we will poll inside of a `Task`. We `Box`
-        /// and `Pin` the `Future` when we create a task so that we don't need
-        /// to worry about pinning or more complicated things in the runtime. We
-        /// also need to make sure this is `Send + Sync` so we can use it across threads
-        /// and so we lock the `Pin<Box<dyn Future>>` inside a `Mutex`.
-        future: Mutex<Pin<Box<dyn Future<Output = ()> + Send + Sync + 'static>>>,
-        /// We need a way to check if the runtime should block on this task and
-        /// so we use a boolean here to check that!
-        block: bool,
+    #[test]
+    /// The `Future` made by `spawn_with` should get the state it was given
+    /// and hand back whatever it works out from it.
+    /// `spawn_with`创建的`Future`应该拿到给它的状态，并交还它从中算出来的东西。
+    fn spawn_with_moves_the_state_in() {
+        let runtime = Builder::new().build();
+        let numbers = vec![1, 2, 3, 4];
+        let handle = runtime.spawn_with(numbers, |numbers| async move {
+            numbers.into_iter().map(|n| n * n).sum::<i32>()
+        });
+        assert_eq!(runtime.block_on(handle), Ok(30));
+        let letters = runtime.block_on(async {
+            spawn_with(vec!["a", "b"], |letters| async move { letters.concat() }).await
+        });
+        assert_eq!(letters.unwrap(), "ab");
     }
 
-    impl Task {
-        /// This constructs a new task by increasing the count in the runtime of
-        /// how many tasks there are, pinning the `Future`, and wrapping it all
-        /// in an `Arc`.
-        /// 构造新任务，并增加运行时中的任务数量，pinning `Future`，并将其包装在 `Arc` 中。
-        fn new(block: bool, future: impl Future<Output = ()> + Send + Sync + 'static) -> Arc<Self> {
-            Runtime::get().tasks.fetch_add(1, Ordering::Relaxed);
-            Arc::new(Task {
-                future: Mutex::new(Box::pin(future)),
-                block,
-            })
-        }
+    #[test]
+    /// A task spawned with `spawn_affine` should be polled on the same worker
+    /// every time it's woken up, even with other workers free to take it.
+    /// 用`spawn_affine`生成的任务每次被唤醒时都应该在同一个工作线程上被poll，即使有其他空闲的工作线程可以接手它。
+    fn affine_tasks_stay_on_their_worker() {
+        let runtime = Builder::new().worker_threads(4).build();
+        let (ready, mut events) = mpsc::channel();
+        let handle = runtime.spawn_affine(async move {
+            let mut polled_on = vec![thread::current().id()];
+            while events.recv().await.is_some() {
+                polled_on.push(thread::current().id());
+            }
+            polled_on
+        });
+        thread::spawn(move || {
+            for _ in 0..20 {
+                thread::sleep(Duration::from_millis(5));
+                ready.send(()).unwrap();
+            }
+        });
+        let polled_on = runtime.block_on(handle).unwrap();
+        assert_eq!(polled_on.len(), 21);
+        assert!(polled_on.iter().all(|&id| id == polled_on[0]));
+        assert_ne!(polled_on[0], thread::current().id());
+    }
 
-        /// We want to use the `Task` itself as a `Waker` which we'll get more
-        /// into below. This is a convenience method to construct a new `Waker`.
-        /// A neat thing to note for `poll` and here as well is that we can
-        /// restrict a method such that it will only work when `self` is a
-        /// certain type. In this case you can only call `waker` if the type is
-        /// a `&Arc<Task>`. If it was just `Task` it would not compile or work.
-        /// 我们希望将 `Task` 本身用作 `Waker`，我们将在下面进一步讨论。
-        /// 这是一个方便的方法来构造一个新的 `Waker`。
-        /// 有趣的是，对于 `poll` 和这里，我们可以限制一个方法，使其仅在 `self` 是某种类型时才有效。
-        /// 在这种情况下，只有当类型是 `&Arc<Task>` 时，才能调用 `waker`。
-        fn waker(self: &Arc<Self>) -> Waker {
-            self.clone().into()
-        }
+    #[test]
+    /// `assert_no_leaked_tasks` should be happy once everything has been
+    /// waited on, and panic when a task is left sleeping in the background.
+    /// 一旦所有东西都被等待完了，`assert_no_leaked_tasks`应该没有意见，而当一个任务被留在后台睡觉时它应该panic。
+    fn leaked_tasks_are_caught() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
 
-        /// This is a convenience method to `poll` a `Future` by creating the
-        /// `Waker` and `Context` and then getting access to the actual `Future`
-        /// inside the `Mutex` and calling `poll` on that.
-        /// 这是一个方便的方法来 `poll` `Future`，通过创建 `Waker` 和 `Context`，
-        /// 然后获取 `Mutex` 内部的实际 `Future` 的访问权限，并对其调用 `poll`。
-        fn poll(self: &Arc<Self>) -> Poll<()> {
-            let waker = self.waker();
-            let mut ctx = Context::from_waker(&waker);
-            self.future.lock().unwrap().as_mut().poll(&mut ctx)
+        let runtime = Builder::new().build();
+        for _ in 0..10 {
+            runtime.spawn(Sleep::new(10));
         }
+        runtime.wait();
+        runtime.assert_no_leaked_tasks();
 
-        /// Checks the `block` field to see if the `Task` is blocking.
-        /// 检查 `block` 字段，以查看 `Task` 是否阻塞。
-        fn will_block(&self) -> bool {
-            self.block
+        runtime.spawn(Sleep::new(10_000));
+        let leaked = catch_unwind(AssertUnwindSafe(|| runtime.assert_no_leaked_tasks()));
+        assert!(leaked.is_err());
+    }
+
+    #[test]
+    /// The benchmark should account for every task and every poll whether
+    /// the runtime has workers or not.
+    /// 无论运行时有没有工作线程，基准测试都应该算上每一个任务和每一次poll。
+    fn benchmark_counts_tasks_and_polls() {
+        for workers in [0, 1, 4] {
+            let runtime = Builder::new().worker_threads(workers).build();
+            let stats = runtime.run_benchmark(20, 5);
+            assert_eq!(stats.tasks, 20);
+            assert_eq!(stats.polls, 100);
+            assert!(stats.polls_per_sec() > 0.0);
         }
     }
 
-    /// Since we increase the count everytime we create a new task we also need
-    /// to make sure that it *also* decreases the count every time it goes out
-    /// of scope. This implementation of `Drop` does just that so that we don't
-    /// need to bookeep about when and where to subtract from the count.
-    /// 由于我们每次创建新任务时都会增加计数，因此我们还需要确保它在每次移出范围时都会减少计数。
-    /// 实现 `Drop` 可以实现上面功能，因此我们不需要在何时何地减去计数时进行对账。
-    impl Drop for Task {
-        fn drop(&mut self) {
-            Runtime::get().tasks.fetch_sub(1, Ordering::Relaxed);
+    #[test]
+    /// Tasks spawned before a cooperative block on a runtime with no workers
+    /// should still get run while we're blocked.
+    /// 在一个没有工作线程的运行时上，在协作式阻塞之前生成的任务在我们阻塞期间应该仍然会被运行。
+    fn cooperative_block_on_drives_other_tasks() {
+        use crate::futures::Sleep;
+
+        let runtime = Builder::new().worker_threads(0).build();
+        let finished = Arc::new(AtomicUsize::new(0));
+        for i in 0..3 {
+            let finished = finished.clone();
+            runtime.spawn(async move {
+                Sleep::new(10 * i).await;
+                finished.fetch_add(1, Ordering::Relaxed);
+            });
         }
+        let output = runtime.block_on_cooperative(async {
+            Sleep::new(500).await;
+            "done"
+        });
+        assert_eq!(output, "done");
+        assert_eq!(finished.load(Ordering::Relaxed), 3);
     }
 
-    /// `Wake` is the crux of all of this executor as it's what lets us
-    /// reschedule a task when it's ready to be polled. For our implementation
-    /// we do a simple check to see if the task blocks or not and then spawn it back
-    /// onto the executor in an appropriate manner.
-    /// `Wake` 是这个执行器的关键，因为它使我们能够在任务准备好被poll时重新安排任务。
-    /// 对于我们的实现，我们进行了一个简单的检查，以查看任务是否阻塞，然后以适当的方式将其重新放回执行器。
-    impl Wake for Task {
-        fn wake(self: Arc<Self>) {
-            if self.will_block() {
-                Runtime::spawner().inner_spawn_blocking(self);
-            } else {
-                Runtime::spawner().inner_spawn(self);
+    #[test]
+    /// A runtime with nothing to do should be asleep, not spinning around
+    /// looking for work.
+    /// 一个无事可做的运行时应该在睡觉，而不是到处空转找活干。
+    fn idle_workers_sleep_instead_of_spinning() {
+        let runtime = Builder::new().worker_threads(4).build();
+        let parks = || runtime.shared.parks.load(Ordering::Relaxed);
+        let notifies = || runtime.shared.work.notifies.load(Ordering::Relaxed);
+        let (parks_before, notifies_before) = (parks(), notifies());
+        thread::sleep(Duration::from_millis(500));
+        runtime.wait();
+        // Four workers spinning for half a second would go around millions
+        // of times. Stray wake ups on a busy machine are a handful at most.
+        // 四个工作线程空转半秒钟会转上几百万圈。一台繁忙的机器上零星的唤醒最多也就几次。
+        assert!(
+            parks() - parks_before < 100,
+            "{} parks",
+            parks() - parks_before
+        );
+        assert!(
+            notifies() - notifies_before < 100,
+            "{} notifies",
+            notifies() - notifies_before
+        );
+    }
+
+    #[test]
+    /// A lone `Sleep` should go off right when it's due, with the workers
+    /// asleep the whole time rather than checking the clock over and over.
+    /// 一个单独的`Sleep`应该在到期时准时触发，工作线程在整个过程中都在睡觉，而不是一遍又一遍地看时间。
+    fn a_lone_sleep_fires_on_time() {
+        let runtime = Builder::new().worker_threads(2).build();
+        let parks = || runtime.shared.parks.load(Ordering::Relaxed);
+        let notifies = || runtime.shared.work.notifies.load(Ordering::Relaxed);
+        let (parks_before, notifies_before) = (parks(), notifies());
+        let start = Instant::now();
+        runtime.block_on(Sleep::new(1000));
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(1000));
+        assert!(elapsed < Duration::from_secs(30), "{elapsed:?}");
+        // Checking the clock every millisecond would mean a thousand of each
+        // over that second. A busy machine can add a few stray wake ups, but
+        // nowhere near that many.
+        // 每毫秒看一次时间意味着在那一秒里每样都有一千次。一台繁忙的机器可能会多出几次零星的唤醒，但远远没有那么多。
+        assert!(
+            parks() - parks_before < 100,
+            "{} parks",
+            parks() - parks_before
+        );
+        assert!(
+            notifies() - notifies_before < 100,
+            "{} notifies",
+            notifies() - notifies_before
+        );
+    }
+
+    #[test]
+    /// A `Sleep` that gets polled with a different `Waker` than the first
+    /// time should wake the new one when it's due, not the old one.
+    /// 一个被用和第一次不同的`Waker` poll的`Sleep`应该在到期时唤醒新的那个，而不是旧的那个。
+    fn sleeps_wake_the_last_waker_they_saw() {
+        use std::sync::mpsc;
+
+        struct Named(&'static str, Mutex<mpsc::Sender<&'static str>>);
+
+        impl Wake for Named {
+            fn wake(self: Arc<Self>) {
+                let _ = self.1.lock().unwrap().send(self.0);
             }
         }
+
+        let (tx, rx) = mpsc::channel();
+        let first = Waker::from(Arc::new(Named("first", Mutex::new(tx.clone()))));
+        let second = Waker::from(Arc::new(Named("second", Mutex::new(tx))));
+        let mut sleep = std::pin::pin!(Sleep::new(20));
+        assert!(sleep
+            .as_mut()
+            .poll(&mut Context::from_waker(&first))
+            .is_pending());
+        assert!(sleep
+            .as_mut()
+            .poll(&mut Context::from_waker(&second))
+            .is_pending());
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok("second"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    /// A `Sleep` for longer than a `u64` worth of milliseconds, or for longer
+    /// than an `Instant` can hold, should carry on sleeping rather than wrap
+    /// around to a short sleep or overflow.
+    /// 一个比`u64`那么多毫秒更长，或者比`Instant`能装下的更长的`Sleep`应该继续睡下去，而不是绕回成一次很短的睡眠或者溢出。
+    fn sleeps_too_long_to_hold_never_finish() {
+        assert_eq!(block_on_timeout(20, Sleep::new(1 << 64)), Err(Elapsed));
+        assert_eq!(block_on_timeout(20, Sleep::new(u128::MAX)), Err(Elapsed));
     }
 }
 
@@ -0,0 +1,32 @@
+//! `set_test_runtime` swaps the global runtime for the whole process, so it
+//! lives in a test binary of its own where nothing else can land on it.
+//! `set_test_runtime`会替换整个进程的全局运行时，所以它待在一个它自己的测试二进制文件中，在那里没有别的东西会落到它上面。
+
+use whorl::{
+    futures::sync::CountDownLatch,
+    runtime::{self, Builder, Runtime},
+};
+
+#[test]
+/// Once a test runtime is swapped in, spawning from outside of a task
+/// should land on it, and clearing it should go back to the usual one.
+/// 一旦换进来一个测试运行时，从任务之外生成的东西就应该落到它上面，而清除它应该回到平常的那一个。
+fn spawn_routes_to_the_test_runtime() {
+    runtime::set_test_runtime(Builder::new().build());
+    let injected: *const Runtime = Runtime::get();
+    let latch = CountDownLatch::new(1);
+    let waiting = latch.clone();
+    let handle = runtime::spawn_named("routed", async move { waiting.wait().await });
+    let routed = Runtime::get()
+        .tasks()
+        .iter()
+        .any(|task| task.name.as_deref() == Some("routed"));
+    assert!(routed);
+    latch.count_down();
+    assert_eq!(Runtime::get().block_on(handle), Ok(()));
+    // SAFETY: The only task we spawned is done, and `injected` is only ever
+    // compared, never used.
+    // SAFETY：我们生成的唯一一个任务已经完成了，而`injected`只会被拿来比较，从来不会被使用。
+    unsafe { runtime::clear_test_runtime() };
+    assert!(!std::ptr::eq(Runtime::get(), injected));
+}